@@ -46,6 +46,12 @@ async fn main() -> anyhow::Result<()> {
         .args_json(serde_json::json!({"owner_id": owner.id()}))?
         .transact()
         .await?;
+    // let every test account mint its own tokens
+    owner
+        .call(&worker, nft_contract.id(), "set_public_minting")
+        .args_json(serde_json::json!({"enabled": true}))?
+        .transact()
+        .await?;
     market_contract
         .call(&worker, "new")
         .args_json(serde_json::json!({"owner_id": owner.id()}))?
@@ -119,16 +125,23 @@ async fn test_nft_mint_call(
         .await?
         .json()?;
 
+    // issued_at is set from the block timestamp at mint
+    let issued_at = tokens[0]["metadata"]["issued_at"].clone();
+    assert!(issued_at.is_string());
+
     let expected = json!([
         {   
             "approved_account_ids": {},
             "royalty": {},
+            "soulbound": false,
+            "expired": false,
+            "token_type": null,
             "token_id": "1",
             "owner_id": user.id(),
             "metadata": {
                 "expires_at": serde_json::Value::Null, 
                 "extra": serde_json::Value::Null, 
-                "issued_at": serde_json::Value::Null, 
+                "issued_at": issued_at,
                 "copies": serde_json::Value::Null,
                 "media_hash": serde_json::Value::Null,
                 "reference": serde_json::Value::Null,
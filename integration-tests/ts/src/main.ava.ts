@@ -25,6 +25,9 @@ test.beforeEach(async (t) => {
     }
   );
 
+  // let every test account mint its own tokens
+  await root.call(nft_contract, "set_public_minting", { enabled: true });
+
   const marketContractLocation = path.join(__dirname, "../../../out/market.wasm");
   const market_contract = await root.devDeploy(
     marketContractLocation,
@@ -93,7 +96,10 @@ test("nft contract: nft mint call", async (t) => {
     defaultCallOptions()
   );
 
-  const tokens = await nft_contract.view("nft_tokens");
+  const tokens: any = await nft_contract.view("nft_tokens");
+  // issued_at is set from the block timestamp at mint
+  const issued_at = tokens[0].metadata.issued_at;
+  t.is(typeof issued_at, "string");
   const expected = [
     {
       approved_account_ids: {},
@@ -102,7 +108,7 @@ test("nft contract: nft mint call", async (t) => {
         description: "Alright time's up, let's do this.",
         expires_at: null,
         extra: null,
-        issued_at: null,
+        issued_at,
         media:
           "https://external-content.duckduckgo.com/iu/?u=https%3A%2F%2Ftse3.mm.bing.net%2Fth%3Fid%3DOIP.Fhp4lHufCdTzTeGCAblOdgHaF7%26pid%3DApi&f=1",
         media_hash: null,
@@ -114,6 +120,9 @@ test("nft contract: nft mint call", async (t) => {
       },
       owner_id: alice.accountId,
       royalty: {},
+      soulbound: false,
+      expired: false,
+      token_type: null,
       token_id: "TEST123",
     },
   ];
@@ -1,2 +1,2 @@
 #!/bin/bash
-set -e && RUSTFLAGS='-C link-arg=-s' cargo build --target wasm32-unknown-unknown --release && mkdir -p ../out && cp target/wasm32-unknown-unknown/release/*.wasm ../out/market.wasm
\ No newline at end of file
+set -e && GIT_COMMIT=$(git rev-parse HEAD) RUSTFLAGS='-C link-arg=-s' cargo build --target wasm32-unknown-unknown --release && mkdir -p ../out && cp target/wasm32-unknown-unknown/release/*.wasm ../out/market.wasm
\ No newline at end of file
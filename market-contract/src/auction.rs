@@ -0,0 +1,144 @@
+use crate::*;
+
+//bids placed this close to the end of an auction push the end back, so nobody can win by bidding at the last block
+const ANTI_SNIPING_WINDOW_MS: u64 = 5 * 60 * 1000;
+
+//a bid on an auction. The amount is escrowed by the market until the bidder is outbid or the auction is settled
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Bid {
+    pub bidder_id: AccountId,
+    pub amount: U128,
+}
+
+//an English auction: the highest bid when the auction ends buys the token
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Auction {
+    //lowest amount the first bid can be
+    pub reserve_price: U128,
+    //how much every bid must add to the current highest bid
+    pub min_increment: U128,
+    //when the auction ends, as a Unix epoch in milliseconds. Late bids push it back
+    pub ends_at: u64,
+    //current highest bid, if any
+    pub highest_bid: Option<Bid>,
+}
+
+impl Auction {
+    //the lowest amount the next bid can be
+    pub(crate) fn min_next_bid(&self) -> u128 {
+        match &self.highest_bid {
+            Some(bid) => bid.amount.0 + self.min_increment.0,
+            None => self.reserve_price.0,
+        }
+    }
+}
+
+//a Dutch auction: the price declines from the start price to the floor price, and the first offer at the current price buys the token
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct DutchAuction {
+    //price when the auction starts
+    pub start_price: U128,
+    //lowest price, reached once the decay duration has passed
+    pub floor_price: U128,
+    //when the auction starts, as a Unix epoch in milliseconds
+    pub starts_at: u64,
+    //how long the price takes to decline to the floor price, in milliseconds
+    pub duration: u64,
+}
+
+impl DutchAuction {
+    //the price at the passed in time (in milliseconds). It declines linearly and stays at the floor price afterwards
+    pub(crate) fn price_at(&self, now: u64) -> u128 {
+        let elapsed = now.saturating_sub(self.starts_at);
+        if elapsed >= self.duration {
+            return self.floor_price.0;
+        }
+        //split the decay so that multiplying by the elapsed time can't overflow
+        let (elapsed, duration) = (u128::from(elapsed), u128::from(self.duration));
+        let range = self.start_price.0 - self.floor_price.0;
+        let decay = range / duration * elapsed + range % duration * elapsed / duration;
+        self.start_price.0 - decay
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    //place a bid on an auction. The attached deposit is the bid and is held until the bidder is outbid,
+    //in which case it's refunded, or the auction is settled
+    #[payable]
+    pub fn place_bid(&mut self, nft_contract_id: AccountId, token_id: String) {
+        //make sure purchasing isn't paused
+        self.assert_not_paused(PauseFeature::Purchasing);
+
+        //get the unique sale ID (contract + DELIMITER + token ID)
+        let contract_and_token_id = format!("{}{}{}", nft_contract_id, DELIMETER, token_id);
+        //get the sale object from the unique sale ID. If the sale doesn't exist, panic.
+        let mut sale = self.sales.get(&contract_and_token_id).expect("No sale");
+
+        let bidder_id = env::predecessor_account_id();
+        assert_ne!(sale.owner_id, bidder_id, "Cannot bid on your own sale.");
+
+        let auction = sale.auction.as_mut().expect("Sale is not an auction");
+        let now = env::block_timestamp_ms();
+        assert!(now < auction.ends_at, "Auction has ended");
+
+        //make sure the bid beats the reserve price or the current highest bid by the minimum increment
+        let amount = env::attached_deposit();
+        let min_next_bid = auction.min_next_bid();
+        assert!(amount >= min_next_bid, "Bid must be at least {}", min_next_bid);
+
+        //refund the bidder that was outbid
+        if let Some(outbid) = auction.highest_bid.replace(Bid {
+            bidder_id,
+            amount: U128(amount),
+        }) {
+            Promise::new(outbid.bidder_id).transfer(outbid.amount.0);
+        }
+
+        //a bid at the very end of the auction gives everyone else time to answer
+        if auction.ends_at - now < ANTI_SNIPING_WINDOW_MS {
+            auction.ends_at = now + ANTI_SNIPING_WINDOW_MS;
+        }
+
+        EventLog::emit(EventLogVariant::BidPlaced(vec![BidPlacedLog {
+            nft_contract_id: sale.nft_contract_id.clone(),
+            token_id: sale.token_id.clone(),
+            owner_id: sale.owner_id.to_string(),
+            bidder_id: env::predecessor_account_id().to_string(),
+            amount: U128(amount),
+            ends_at: auction.ends_at,
+            memo: None,
+        }]));
+
+        //insert the sale back into the map for the unique sale ID
+        self.sales.insert(&contract_and_token_id, &sale);
+    }
+
+    //settle an auction that has ended. Anyone can call this. The token goes to the highest bidder and the bid is paid
+    //out like a regular purchase, including royalties. An auction without bids is simply removed
+    pub fn settle_auction(&mut self, nft_contract_id: AccountId, token_id: String) {
+        //get the unique sale ID (contract + DELIMITER + token ID)
+        let contract_and_token_id = format!("{}{}{}", nft_contract_id, DELIMETER, token_id);
+        let sale = self.sales.get(&contract_and_token_id).expect("No sale");
+        let auction = sale.auction.expect("Sale is not an auction");
+        assert!(
+            env::block_timestamp_ms() >= auction.ends_at,
+            "Auction has not ended yet"
+        );
+
+        match auction.highest_bid {
+            //process the purchase (which will remove the sale, transfer and get the payout from the nft contract, and then distribute royalties).
+            //if the transfer fails, the bid is refunded to the bidder
+            Some(bid) => {
+                self.process_purchase(nft_contract_id, token_id, bid.amount, bid.bidder_id);
+            }
+            None => {
+                let sale = self.internal_remove_sale(nft_contract_id, token_id);
+                EventLog::emit(EventLogVariant::SaleRemoved(vec![sale.to_log()]));
+            }
+        }
+    }
+}
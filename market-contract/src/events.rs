@@ -0,0 +1,249 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use near_sdk::json_types::U128;
+use near_sdk::serde::{Deserialize, Serialize};
+
+use crate::{PauseFeature, MARKET_EVENTS_SPEC, MARKET_STANDARD_NAME};
+
+/// Enum that represents the data type of the EventLog.
+/// The enum can either be an OwnershipTransferPropose, an OwnershipTransferAccept, an OwnershipTransferCancel,
+/// a Pause, an Unpause, a SaleCreated, a SaleUpdated, a SaleRemoved, a SalePurchased, a PurchaseFailed or a BidPlaced.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "event", content = "data")]
+#[serde(rename_all = "snake_case")]
+#[serde(crate = "near_sdk::serde")]
+#[non_exhaustive]
+pub enum EventLogVariant {
+    OwnershipTransferPropose(Vec<OwnershipTransferLog>),
+    OwnershipTransferAccept(Vec<OwnershipTransferLog>),
+    OwnershipTransferCancel(Vec<OwnershipTransferLog>),
+    Pause(Vec<PauseLog>),
+    Unpause(Vec<PauseLog>),
+    SaleCreated(Vec<SaleLog>),
+    SaleUpdated(Vec<SaleLog>),
+    SaleRemoved(Vec<SaleLog>),
+    SalePurchased(Vec<SalePurchasedLog>),
+    PurchaseFailed(Vec<PurchaseFailedLog>),
+    BidPlaced(Vec<BidPlacedLog>),
+}
+
+/// Interface to capture data about an event
+///
+/// Arguments:
+/// * `standard`: name of standard e.g. nft_market
+/// * `version`: e.g. 1.0.0
+/// * `event`: associate event data
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct EventLog {
+    pub standard: String,
+    pub version: String,
+
+    // `flatten` to not have "event": {<EventLogVariant>} in the JSON, just have the contents of {<EventLogVariant>}.
+    #[serde(flatten)]
+    pub event: EventLogVariant,
+}
+
+impl fmt::Display for EventLog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_fmt(format_args!(
+            "EVENT_JSON:{}",
+            &near_sdk::serde_json::to_string(self).map_err(|_| fmt::Error)?
+        ))
+    }
+}
+
+impl EventLog {
+    /// Log the event under this market's own standard
+    pub(crate) fn emit(event: EventLogVariant) {
+        let log = EventLog {
+            // Standard name ("nft_market").
+            standard: MARKET_STANDARD_NAME.to_string(),
+            // Version of the standard ("1.0.0").
+            version: MARKET_EVENTS_SPEC.to_string(),
+            // The data related with the event stored in a vector.
+            event,
+        };
+
+        // Log the serialized json.
+        near_sdk::env::log_str(&log.to_string());
+    }
+}
+
+/// An event log to capture each step of a contract ownership transfer
+///
+/// Arguments
+/// * `old_owner_id`: "owner.near"
+/// * `new_owner_id`: "multisig.near"
+/// * `memo`: optional message
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct OwnershipTransferLog {
+    pub old_owner_id: String,
+    pub new_owner_id: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+/// An event log to capture a feature being paused or unpaused
+///
+/// Arguments
+/// * `feature`: "listing" or "purchasing"
+/// * `account_id`: the account that paused or unpaused the feature
+/// * `memo`: optional message
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PauseLog {
+    pub feature: PauseFeature,
+    pub account_id: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+/// An event log to capture a sale being listed, having its price updated or being removed
+///
+/// Arguments
+/// * `nft_contract_id`: "nft.near"
+/// * `token_id`: "1"
+/// * `owner_id`: the seller
+/// * `price`: the listed price. For an auction, this is the reserve price and for a Dutch auction the start price
+/// * `ft_token_id`: the fungible token the sale is priced in, if it isn't priced in NEAR
+/// * `memo`: optional message
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SaleLog {
+    pub nft_contract_id: String,
+    pub token_id: String,
+    pub owner_id: String,
+    pub price: U128,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ft_token_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+/// An event log to capture a sale being bought, once the payout went through
+///
+/// Arguments
+/// * `nft_contract_id`: "nft.near"
+/// * `token_id`: "1"
+/// * `owner_id`: the seller
+/// * `buyer_id`: the buyer
+/// * `price`: the price the buyer paid
+/// * `market_fee`: the part of the price the market kept
+/// * `payout`: the amount paid to each account, e.g. {"seller.near": "950", "artist.near": "50"}
+/// * `ft_token_id`: the fungible token the price was paid in, if it wasn't paid in NEAR
+/// * `memo`: optional message
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SalePurchasedLog {
+    pub nft_contract_id: String,
+    pub token_id: String,
+    pub owner_id: String,
+    pub buyer_id: String,
+    pub price: U128,
+    pub market_fee: U128,
+    pub payout: BTreeMap<String, U128>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ft_token_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+/// An event log to capture a purchase that failed and was refunded to the buyer
+///
+/// Arguments
+/// * `nft_contract_id`: "nft.near"
+/// * `token_id`: "1"
+/// * `owner_id`: the seller
+/// * `buyer_id`: the buyer
+/// * `price`: the price that was refunded to the buyer
+/// * `ft_token_id`: the fungible token the price was paid in, if it wasn't paid in NEAR
+/// * `memo`: optional message
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PurchaseFailedLog {
+    pub nft_contract_id: String,
+    pub token_id: String,
+    pub owner_id: String,
+    pub buyer_id: String,
+    pub price: U128,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ft_token_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+/// An event log to capture a bid becoming the highest bid of an auction
+///
+/// Arguments
+/// * `nft_contract_id`: "nft.near"
+/// * `token_id`: "1"
+/// * `owner_id`: the seller
+/// * `bidder_id`: the bidder
+/// * `amount`: the bid
+/// * `ends_at`: when the auction ends after the bid, as a Unix epoch in milliseconds
+/// * `memo`: optional message
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct BidPlacedLog {
+    pub nft_contract_id: String,
+    pub token_id: String,
+    pub owner_id: String,
+    pub bidder_id: String,
+    pub amount: U128,
+    pub ends_at: u64,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn market_format_ownership_transfer_cancel() {
+        let expected = r#"EVENT_JSON:{"standard":"nft_market","version":"1.0.0","event":"ownership_transfer_cancel","data":[{"old_owner_id":"owner.near","new_owner_id":"multisig.near"}]}"#;
+        let log = EventLog {
+            standard: "nft_market".to_string(),
+            version: "1.0.0".to_string(),
+            event: EventLogVariant::OwnershipTransferCancel(vec![OwnershipTransferLog {
+                old_owner_id: "owner.near".to_string(),
+                new_owner_id: "multisig.near".to_string(),
+                memo: None,
+            }]),
+        };
+        assert_eq!(expected, log.to_string());
+    }
+
+    #[test]
+    fn market_format_sale_purchased() {
+        let expected = r#"EVENT_JSON:{"standard":"nft_market","version":"1.0.0","event":"sale_purchased","data":[{"nft_contract_id":"nft.near","token_id":"1","owner_id":"seller.near","buyer_id":"buyer.near","price":"1000","market_fee":"0","payout":{"artist.near":"50","seller.near":"950"}}]}"#;
+        let log = EventLog {
+            standard: "nft_market".to_string(),
+            version: "1.0.0".to_string(),
+            event: EventLogVariant::SalePurchased(vec![SalePurchasedLog {
+                nft_contract_id: "nft.near".to_string(),
+                token_id: "1".to_string(),
+                owner_id: "seller.near".to_string(),
+                buyer_id: "buyer.near".to_string(),
+                price: U128(1000),
+                market_fee: U128(0),
+                payout: BTreeMap::from([
+                    ("seller.near".to_string(), U128(950)),
+                    ("artist.near".to_string(), U128(50)),
+                ]),
+                ft_token_id: None,
+                memo: None,
+            }]),
+        };
+        assert_eq!(expected, log.to_string());
+    }
+}
@@ -20,4 +20,10 @@ trait ExtContract {
         //the maximum amount of accounts the market can payout at once (this is limited by GAS)
 		max_len_payout: u32,
     );
+}
+
+//transfer fungible tokens from the market. Used to pay out and refund sales that are priced in a fungible token
+#[ext_contract(ext_ft_contract)]
+trait ExtFtContract {
+    fn ft_transfer(&mut self, receiver_id: AccountId, amount: U128, memo: Option<String>);
 }
\ No newline at end of file
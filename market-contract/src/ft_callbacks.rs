@@ -0,0 +1,162 @@
+use crate::*;
+use near_sdk::is_promise_success;
+
+/// transfer callbacks from whitelisted FT Contracts
+
+//the sale to buy, passed in the msg of ft_transfer_call
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PurchaseArgs {
+    pub nft_contract_id: AccountId,
+    pub token_id: TokenId,
+}
+
+/*
+    trait that will be used as the callback from the FT contract. When ft_transfer_call is
+    called, the FT contract transfers the tokens to this marketplace and this is the function
+    that is invoked. 
+*/
+pub trait FungibleTokenReceiver {
+    fn ft_on_transfer(
+        &mut self,
+        sender_id: AccountId,
+        amount: U128,
+        msg: String,
+    ) -> PromiseOrValue<U128>;
+}
+
+//implementation of the trait
+#[near_bindgen]
+impl FungibleTokenReceiver for Contract {
+    /// where we buy a sale that is priced in a fungible token. The returned amount is refunded to the sender by the FT contract
+    fn ft_on_transfer(
+        &mut self,
+        sender_id: AccountId,
+        amount: U128,
+        msg: String,
+    ) -> PromiseOrValue<U128> {
+        //make sure purchasing isn't paused
+        self.assert_not_paused(PauseFeature::Purchasing);
+
+        // get the FT contract ID which is the predecessor
+        let ft_token_id = env::predecessor_account_id();
+        assert!(
+            self.ft_token_ids.contains(&ft_token_id),
+            "Fungible token {} is not supported",
+            ft_token_id
+        );
+
+        //the sale to buy comes from the msg field. If it isn't valid, the FT contract refunds the sender
+        let PurchaseArgs { nft_contract_id, token_id } =
+            near_sdk::serde_json::from_str(&msg).expect("Not valid PurchaseArgs");
+
+        //get the sale object from the unique sale ID. If the sale doesn't exist, panic.
+        let contract_and_token_id = format!("{}{}{}", nft_contract_id, DELIMETER, token_id);
+        let sale = self.sales.get(&contract_and_token_id).expect("No sale");
+
+        //the buyer is the account that sent the tokens and can't be the owner of the sale
+        assert_ne!(sale.owner_id, sender_id, "Cannot buy your own sale.");
+        //auctions are bought through place_bid and settle_auction
+        assert!(sale.auction.is_none(), "Sale is an auction, use place_bid");
+        assert_eq!(
+            sale.ft_token_id.as_ref(),
+            Some(&ft_token_id),
+            "Sale is not priced in {}",
+            ft_token_id
+        );
+        if let Some(dutch_auction) = &sale.dutch_auction {
+            assert!(
+                env::block_timestamp_ms() >= dutch_auction.starts_at,
+                "Dutch auction has not started yet"
+            );
+        }
+
+        //make sure the amount is greater than the price
+        let price = sale.current_price();
+        assert!(amount.0 >= price, "Transferred amount must be greater than or equal to the current price: {:?}", price);
+
+        //process the purchase (which will remove the sale, transfer and get the payout from the nft contract, and then distribute royalties
+        //with ft_transfer). If the purchase fails, resolve_purchase transfers the price back to the buyer
+        self.process_purchase(nft_contract_id, token_id, U128(price), sender_id);
+
+        //anything over the price is returned to the FT contract as unused, which refunds it to the buyer
+        PromiseOrValue::Value(U128(amount.0 - price))
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    //whitelist fungible tokens that sales can be priced in. Returns whether each token was newly added
+    pub fn add_ft_token_ids(&mut self, ft_token_ids: Vec<FungibleTokenId>) -> Vec<bool> {
+        self.assert_contract_owner();
+        ft_token_ids
+            .iter()
+            .map(|ft_token_id| self.ft_token_ids.insert(ft_token_id))
+            .collect()
+    }
+
+    //remove fungible tokens from the whitelist. Sales that are priced in them can't be bought until they're added back
+    pub fn remove_ft_token_ids(&mut self, ft_token_ids: Vec<FungibleTokenId>) -> Vec<bool> {
+        self.assert_contract_owner();
+        ft_token_ids
+            .iter()
+            .map(|ft_token_id| self.ft_token_ids.remove(ft_token_id))
+            .collect()
+    }
+
+    //get the fungible tokens that sales can be priced in
+    pub fn supported_ft_token_ids(&self) -> Vec<FungibleTokenId> {
+        self.ft_token_ids.to_vec()
+    }
+
+    //claim the fungible tokens that couldn't be transferred to the caller, e.g. because they weren't registered
+    //with the FT contract yet. If the transfer fails again, the amount stays claimable
+    #[payable]
+    pub fn claim_ft(&mut self, ft_token_id: FungibleTokenId) -> U128 {
+        //assert that the user attached exactly 1 yoctoNEAR for security reasons. It pays for the ft_transfer
+        assert_one_yocto();
+        let account_id = env::predecessor_account_id();
+        let amount = self
+            .ft_claimable
+            .remove(&(account_id.clone(), ft_token_id.clone()))
+            .expect("Nothing to claim");
+        transfer_ft(ft_token_id, account_id, U128(amount));
+        U128(amount)
+    }
+
+    //get the amount of a fungible token that an account can claim
+    pub fn get_claimable_ft(&self, account_id: AccountId, ft_token_id: FungibleTokenId) -> U128 {
+        U128(self.ft_claimable.get(&(account_id, ft_token_id)).unwrap_or(0))
+    }
+
+    //private method used to resolve an ft_transfer of the market. If the transfer failed, the tokens are still on
+    //the market, so the amount is credited to the receiver to claim later
+    #[private]
+    pub fn resolve_ft_transfer(
+        &mut self,
+        ft_token_id: FungibleTokenId,
+        receiver_id: AccountId,
+        amount: U128,
+    ) -> U128 {
+        if is_promise_success() {
+            return U128(0);
+        }
+        let key = (receiver_id, ft_token_id);
+        let claimable = self.ft_claimable.get(&key).unwrap_or(0) + amount.0;
+        self.ft_claimable.insert(&key, &claimable);
+        amount
+    }
+}
+
+//transfer fungible tokens from the market and credit them to the receiver if the transfer fails. The market pays the
+//FT_TRANSFER_DEPOSIT that ft_transfer requires
+pub(crate) fn transfer_ft(ft_token_id: FungibleTokenId, receiver_id: AccountId, amount: U128) -> Promise {
+    ext_ft_contract::ext(ft_token_id.clone())
+        .with_attached_deposit(FT_TRANSFER_DEPOSIT)
+        .ft_transfer(receiver_id.clone(), amount, Some("payout from market".to_string()))
+        .then(
+            Contract::ext(env::current_account_id())
+                .with_static_gas(GAS_FOR_RESOLVE_FT_TRANSFER)
+                .resolve_ft_transfer(ft_token_id, receiver_id, amount),
+        )
+}
@@ -1,27 +1,50 @@
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
-use near_sdk::collections::{LookupMap, UnorderedMap, UnorderedSet};
+use near_sdk::collections::{LookupMap, LookupSet, UnorderedMap, UnorderedSet};
 use near_sdk::json_types::{U128, U64};
 use near_sdk::serde::{Deserialize, Serialize};
 use near_sdk::{
     assert_one_yocto, env, ext_contract, near_bindgen, AccountId, Balance, Gas, PanicOnDefault,
-    Promise, CryptoHash, BorshStorageKey,
+    Promise, PromiseOrValue, CryptoHash, BorshStorageKey,
 };
 use std::collections::HashMap;
 
+pub use crate::events::*;
 use crate::external::*;
 use crate::internal::*;
+pub use crate::pause::*;
+use crate::migrate::*;
 use crate::sale::*;
+use crate::auction::*;
 use near_sdk::env::STORAGE_PRICE_PER_BYTE;
 
+mod auction;
+mod events;
 mod external;
+mod ft_callbacks;
 mod internal;
+mod market_fee;
+mod migrate;
 mod nft_callbacks;
+mod owner;
+mod pause;
 mod sale;
 mod sale_views;
+mod source_metadata;
+
+/// Name of the standard used for the events this market logs
+pub const MARKET_STANDARD_NAME: &str = "nft_market";
+/// Version of the market events
+pub const MARKET_EVENTS_SPEC: &str = "1.0.0";
 
 //GAS constants to attach to calls
 const GAS_FOR_RESOLVE_PURCHASE: Gas = Gas(115_000_000_000_000);
 const GAS_FOR_NFT_TRANSFER: Gas = Gas(15_000_000_000_000);
+const GAS_FOR_RESOLVE_FT_TRANSFER: Gas = Gas(5_000_000_000_000);
+
+//yoctoNEAR attached to every ft_transfer the market makes, as the FT standard requires. The market pays it out of its own
+//balance: at most 10 payout receivers per purchase (see resolve_purchase) or a single refund for a failed one. Every
+//purchase costs its caller far more in GAS than that, so repeated failed purchases can't drain the market's balance
+const FT_TRANSFER_DEPOSIT: Balance = 1;
 
 //the minimum storage to have a sale on the contract.
 const STORAGE_PER_SALE: u128 = 1000 * STORAGE_PRICE_PER_BYTE;
@@ -64,6 +87,30 @@ pub struct Contract {
 
     //keep track of the storage that accounts have payed
     pub storage_deposits: LookupMap<AccountId, Balance>,
+
+    //account the ownership was proposed to. It becomes the owner once it calls accept_ownership
+    pub pending_owner_id: Option<AccountId>,
+
+    //features that are currently paused
+    pub paused_features: LookupSet<PauseFeature>,
+
+    //accounts that can pause and unpause features besides the contract owner
+    pub pausers: LookupSet<AccountId>,
+
+    //fungible tokens that sales can be priced in besides NEAR
+    pub ft_token_ids: UnorderedSet<FungibleTokenId>,
+
+    //fungible tokens that couldn't be transferred to an account, which the account can claim with claim_ft
+    pub ft_claimable: LookupMap<(AccountId, FungibleTokenId), Balance>,
+
+    //fee the market takes out of the price of every purchase, in basis points
+    pub market_fee_bps: u16,
+
+    //fees collected in NEAR that the owner hasn't withdrawn yet
+    pub collected_market_fees: Balance,
+
+    //fees collected in each fungible token that the owner hasn't withdrawn yet
+    pub collected_ft_market_fees: LookupMap<FungibleTokenId, Balance>,
 }
 
 /// Helper structure to for keys of the persistent collections.
@@ -78,6 +125,10 @@ pub enum StorageKey {
     ByNFTTokenTypeInner { token_type_hash: CryptoHash },
     FTTokenIds,
     StorageDeposits,
+    PausedFeatures,
+    Pausers,
+    CollectedFtMarketFees,
+    FtClaimable,
 }
 
 #[near_bindgen]
@@ -98,8 +149,23 @@ impl Contract {
             by_owner_id: LookupMap::new(StorageKey::ByOwnerId),
             by_nft_contract_id: LookupMap::new(StorageKey::ByNFTContractId),
             storage_deposits: LookupMap::new(StorageKey::StorageDeposits),
+
+            //no ownership transfer is pending
+            pending_owner_id: None,
+            paused_features: LookupSet::new(StorageKey::PausedFeatures),
+            pausers: LookupSet::new(StorageKey::Pausers),
+            ft_token_ids: UnorderedSet::new(StorageKey::FTTokenIds),
+            ft_claimable: LookupMap::new(StorageKey::FtClaimable),
+
+            //no market fee until the owner sets one
+            market_fee_bps: 0,
+            collected_market_fees: 0,
+            collected_ft_market_fees: LookupMap::new(StorageKey::CollectedFtMarketFees),
         };
 
+        //record that the stored state uses the current layout
+        write_state_version();
+
         //return the Contract object
         this
     }
@@ -0,0 +1,95 @@
+use crate::*;
+use near_sdk::is_promise_success;
+
+//the highest fee the owner can set, in basis points (10%)
+pub const MAX_MARKET_FEE_BPS: u16 = 1_000;
+
+#[near_bindgen]
+impl Contract {
+    //set the fee the market takes out of the price of every purchase, in basis points. Purchases that are
+    //already in flight keep the fee they started with
+    pub fn set_market_fee(&mut self, fee_bps: u16) {
+        self.assert_contract_owner();
+        assert!(
+            fee_bps <= MAX_MARKET_FEE_BPS,
+            "Market fee can't be more than {} basis points",
+            MAX_MARKET_FEE_BPS
+        );
+        self.market_fee_bps = fee_bps;
+    }
+
+    //get the fee the market takes out of the price of every purchase, in basis points
+    pub fn get_market_fee(&self) -> u16 {
+        self.market_fee_bps
+    }
+
+    //get the fees collected so far that weren't withdrawn yet, in NEAR or in the passed in fungible token
+    pub fn get_collected_market_fees(&self, ft_token_id: Option<FungibleTokenId>) -> U128 {
+        U128(match ft_token_id {
+            Some(ft_token_id) => self.collected_ft_market_fees.get(&ft_token_id).unwrap_or(0),
+            None => self.collected_market_fees,
+        })
+    }
+
+    //withdraw the collected fees in NEAR, or in the passed in fungible token, to the contract owner.
+    //if the owner isn't registered with the FT contract, the fees are collected again and can be withdrawn later
+    #[payable]
+    pub fn withdraw_market_fees(&mut self, ft_token_id: Option<FungibleTokenId>) -> U128 {
+        //assert that the user attached exactly 1 yoctoNEAR for security reasons
+        assert_one_yocto();
+        self.assert_contract_owner();
+
+        let amount = match &ft_token_id {
+            Some(ft_token_id) => self.collected_ft_market_fees.remove(ft_token_id).unwrap_or(0),
+            None => std::mem::take(&mut self.collected_market_fees),
+        };
+        if amount > 0 {
+            match ft_token_id {
+                Some(ft_token_id) => {
+                    ext_ft_contract::ext(ft_token_id.clone())
+                        .with_attached_deposit(1)
+                        .ft_transfer(self.owner_id.clone(), U128(amount), Some("market fees".to_string()))
+                        .then(
+                            Self::ext(env::current_account_id())
+                                .with_static_gas(GAS_FOR_RESOLVE_FT_TRANSFER)
+                                .resolve_withdraw_market_fees(ft_token_id, U128(amount)),
+                        );
+                }
+                None => {
+                    Promise::new(self.owner_id.clone()).transfer(amount);
+                }
+            }
+        }
+        U128(amount)
+    }
+
+    //private method used to resolve a withdrawal of fees in a fungible token. If the transfer failed,
+    //the fees are still on the market, so they're collected again
+    #[private]
+    pub fn resolve_withdraw_market_fees(&mut self, ft_token_id: FungibleTokenId, amount: U128) -> U128 {
+        if is_promise_success() {
+            return amount;
+        }
+        self.internal_collect_market_fee(&Some(ft_token_id), amount.0);
+        U128(0)
+    }
+}
+
+impl Contract {
+    //the part of the price the market keeps as its fee. The price is split so that multiplying by the fee can't overflow
+    pub(crate) fn market_fee_for(&self, price: u128) -> u128 {
+        let bps = u128::from(self.market_fee_bps);
+        price / 10_000 * bps + price % 10_000 * bps / 10_000
+    }
+
+    //add a fee of a purchase that went through to the collected fees
+    pub(crate) fn internal_collect_market_fee(&mut self, ft_token_id: &Option<FungibleTokenId>, fee: u128) {
+        match ft_token_id {
+            Some(ft_token_id) => {
+                let collected = self.collected_ft_market_fees.get(ft_token_id).unwrap_or(0);
+                self.collected_ft_market_fees.insert(ft_token_id, &(collected + fee));
+            }
+            None => self.collected_market_fees += fee,
+        }
+    }
+}
@@ -0,0 +1,257 @@
+use crate::*;
+use near_sdk::GasWeight;
+
+//the key the state version is stored under. Deployments made before state versioning don't have it and are V1
+const STATE_VERSION_KEY: &[u8] = b"STATE_VERSION";
+//version of the current state layout. Bump it and add a conversion to `VersionedContract` whenever a release
+//changes the fields of `Contract` or `Sale`
+pub const CURRENT_STATE_VERSION: u8 = 3;
+//the key the index of the next sale to migrate is stored under while a migration is in progress
+const MIGRATION_CURSOR_KEY: &[u8] = b"MIGRATION_CURSOR";
+//maximum number of sales converted by a single migrate call
+pub const MAX_MIGRATION_BATCH_SIZE: u64 = 100;
+
+//minimum GAS attached to the `migrate` call. Any unused GAS is forwarded to it as well
+const GAS_FOR_MIGRATE: Gas = Gas(10_000_000_000_000);
+
+//sale layout of the first deployments (before auctions)
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct SaleV1 {
+    pub owner_id: AccountId,
+    pub approval_id: u64,
+    pub nft_contract_id: String,
+    pub token_id: String,
+    pub sale_conditions: SalePriceInYoctoNear,
+}
+
+//state layout of the first deployments (before ownership could be transferred)
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct ContractV1 {
+    pub owner_id: AccountId,
+    pub sales: UnorderedMap<ContractAndTokenId, SaleV1>,
+    pub by_owner_id: LookupMap<AccountId, UnorderedSet<ContractAndTokenId>>,
+    pub by_nft_contract_id: LookupMap<AccountId, UnorderedSet<TokenId>>,
+    pub storage_deposits: LookupMap<AccountId, Balance>,
+}
+
+impl From<SaleV1> for Sale {
+    //every sale listed before auctions is a fixed price sale in NEAR
+    fn from(sale: SaleV1) -> Self {
+        Sale {
+            owner_id: sale.owner_id,
+            approval_id: sale.approval_id,
+            nft_contract_id: sale.nft_contract_id,
+            token_id: sale.token_id,
+            sale_conditions: sale.sale_conditions,
+            auction: None,
+            dutch_auction: None,
+            ft_token_id: None,
+        }
+    }
+}
+
+//state layout that introduced state versioning and the two-step ownership transfer. Sales still had the first layout
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct ContractV2 {
+    pub owner_id: AccountId,
+    pub sales: UnorderedMap<ContractAndTokenId, SaleV1>,
+    pub by_owner_id: LookupMap<AccountId, UnorderedSet<ContractAndTokenId>>,
+    pub by_nft_contract_id: LookupMap<AccountId, UnorderedSet<TokenId>>,
+    pub storage_deposits: LookupMap<AccountId, Balance>,
+    pub pending_owner_id: Option<AccountId>,
+}
+
+impl From<ContractV1> for ContractV2 {
+    fn from(state: ContractV1) -> Self {
+        ContractV2 {
+            owner_id: state.owner_id,
+            sales: state.sales,
+            by_owner_id: state.by_owner_id,
+            by_nft_contract_id: state.by_nft_contract_id,
+            storage_deposits: state.storage_deposits,
+            pending_owner_id: None,
+        }
+    }
+}
+
+impl From<ContractV2> for Contract {
+    //every sale must already have been rewritten with the current layout (see migrate)
+    fn from(state: ContractV2) -> Self {
+        Contract {
+            owner_id: state.owner_id,
+            //the map handle only stores its prefix and length, so it can be reinterpreted with the new value type
+            sales: UnorderedMap::try_from_slice(&state.sales.try_to_vec().unwrap()).unwrap(),
+            by_owner_id: state.by_owner_id,
+            by_nft_contract_id: state.by_nft_contract_id,
+            storage_deposits: state.storage_deposits,
+            pending_owner_id: state.pending_owner_id,
+            paused_features: LookupSet::new(StorageKey::PausedFeatures),
+            pausers: LookupSet::new(StorageKey::Pausers),
+            ft_token_ids: UnorderedSet::new(StorageKey::FTTokenIds),
+            ft_claimable: LookupMap::new(StorageKey::FtClaimable),
+            market_fee_bps: 0,
+            collected_market_fees: 0,
+            collected_ft_market_fees: LookupMap::new(StorageKey::CollectedFtMarketFees),
+        }
+    }
+}
+
+//rewrite at most `limit` sales from the passed in index in place with the current layout. Returns the index to continue from
+fn migrate_sales(
+    sales: &mut UnorderedMap<ContractAndTokenId, SaleV1>,
+    from_index: u64,
+    limit: u64,
+) -> u64 {
+    let end = (from_index + limit).min(sales.len());
+    let page: Vec<(ContractAndTokenId, SaleV1)> = (from_index..end)
+        .map(|index| {
+            (
+                sales.keys_as_vector().get(index).unwrap(),
+                sales.values_as_vector().get(index).unwrap(),
+            )
+        })
+        .collect();
+    for (contract_and_token_id, sale) in page {
+        sales.insert_raw(
+            &contract_and_token_id.try_to_vec().unwrap(),
+            &Sale::from(sale).try_to_vec().unwrap(),
+        );
+    }
+    end
+}
+
+//every state layout this contract has been deployed with. The last variant is the current layout
+pub enum VersionedContract {
+    V1(ContractV1),
+    V2(ContractV2),
+    V3(Contract),
+}
+
+impl VersionedContract {
+    //read the stored state using the layout of its stored version
+    pub(crate) fn read() -> Self {
+        let version = env::storage_read(STATE_VERSION_KEY)
+            .map(|version| version[0])
+            .unwrap_or(1);
+        match version {
+            1 => Self::V1(env::state_read().expect("Failed to read old state")),
+            2 => Self::V2(env::state_read().expect("Failed to read old state")),
+            3 => Self::V3(env::state_read().expect("Failed to read old state")),
+            _ => env::panic_str("Unknown state version"),
+        }
+    }
+
+    //the contract owner stored in the state
+    pub(crate) fn owner_id(&self) -> &AccountId {
+        match self {
+            Self::V1(state) => &state.owner_id,
+            Self::V2(state) => &state.owner_id,
+            Self::V3(state) => &state.owner_id,
+        }
+    }
+
+    //convert at most `limit` stored sales from the passed in index into the current layout and return the index to
+    //continue from. Only the top-level state is left to convert once it returns the number of sales
+    fn migrate_entries(&mut self, from_index: u64, limit: u64) -> u64 {
+        match self {
+            Self::V1(state) => migrate_sales(&mut state.sales, from_index, limit),
+            Self::V2(state) => migrate_sales(&mut state.sales, from_index, limit),
+            Self::V3(_) => 0,
+        }
+    }
+
+    //the number of stored sales that have to be converted
+    fn entries_len(&self) -> u64 {
+        match self {
+            Self::V1(state) => state.sales.len(),
+            Self::V2(state) => state.sales.len(),
+            Self::V3(_) => 0,
+        }
+    }
+
+    //convert the top-level state into the current layout, one version at a time. Every sale must have been converted
+    fn into_current(self) -> Contract {
+        match self {
+            Self::V1(state) => Self::V2(state.into()).into_current(),
+            Self::V2(state) => state.into(),
+            Self::V3(state) => state,
+        }
+    }
+}
+
+//record that the stored state uses the current layout
+pub(crate) fn write_state_version() {
+    env::storage_write(STATE_VERSION_KEY, &[CURRENT_STATE_VERSION]);
+}
+
+#[near_bindgen]
+impl Contract {
+    /*
+        migrates the stored state to the current layout, converting at most `limit` sales per call so that any
+        number of sales fits in the GAS limit. With a limit, call it again until it returns true. Without one, each
+        call converts a full batch and then calls migrate on this contract for the next one, so a single call (like
+        the one update_contract makes) converts every sale. If that chain runs out of GAS, calling migrate again
+        resumes it. The progress is stored under its own key and the state keeps its old layout until every sale was
+        converted, so every other method fails until then. Migrating a state that already has the current layout
+        changes nothing.
+        Can only be called by the contract owner or by the contract itself (see update_contract).
+    */
+    pub fn migrate(limit: Option<u64>) -> PromiseOrValue<bool> {
+        //read the old state. If it can't be read, panic
+        let mut state = VersionedContract::read();
+
+        let predecessor_id = env::predecessor_account_id();
+        assert!(
+            &predecessor_id == state.owner_id() || predecessor_id == env::current_account_id(),
+            "only contract owner"
+        );
+
+        //convert the next batch of sales, continuing where the previous call stopped
+        let from_index = env::storage_read(MIGRATION_CURSOR_KEY)
+            .map(|cursor| u64::try_from_slice(&cursor).unwrap())
+            .unwrap_or(0);
+        let batch_size = limit.unwrap_or(MAX_MIGRATION_BATCH_SIZE).min(MAX_MIGRATION_BATCH_SIZE);
+        let next_index = state.migrate_entries(from_index, batch_size);
+        if next_index < state.entries_len() {
+            env::storage_write(MIGRATION_CURSOR_KEY, &next_index.try_to_vec().unwrap());
+            env::log_str(&format!("Migrated {} of {} sales", next_index, state.entries_len()));
+            //without a limit the next batch is converted by another call, so the whole migration finishes in one go
+            if limit.is_none() {
+                return PromiseOrValue::Promise(call_migrate(Promise::new(env::current_account_id())));
+            }
+            return PromiseOrValue::Value(false);
+        }
+
+        //every sale is converted, so the top-level state can be switched to the current layout
+        env::storage_remove(MIGRATION_CURSOR_KEY);
+        env::state_write(&state.into_current());
+        write_state_version();
+        PromiseOrValue::Value(true)
+    }
+
+    /*
+        deploys the wasm passed as the raw input of this call to the contract account and runs migrate without a
+        limit in the same batch, which keeps calling itself until every sale is converted. If the first call fails
+        the whole batch, including the deployment, is reverted. Attach enough GAS for every batch, or call migrate
+        again if it runs out.
+    */
+    pub fn update_contract(&self) -> Promise {
+        self.assert_contract_owner();
+
+        //the new code is the raw input of the call
+        let code = env::input().expect("No code was attached");
+
+        call_migrate(Promise::new(env::current_account_id()).deploy_contract(code)).as_return()
+    }
+}
+
+//adds a migrate call without a limit to the passed in promise, forwarding all the unused GAS to it
+fn call_migrate(promise: Promise) -> Promise {
+    promise.function_call_weight(
+        "migrate".to_string(),
+        b"{}".to_vec(),
+        0,
+        GAS_FOR_MIGRATE,
+        GasWeight(1),
+    )
+}
@@ -2,11 +2,31 @@ use crate::*;
 
 /// approval callbacks from NFT Contracts
 
-//struct for keeping track of the sale conditions for a Sale
+//the sale conditions passed in the msg of nft_approve. The variant is picked from the fields that are passed in
 #[derive(Serialize, Deserialize)]
 #[serde(crate = "near_sdk::serde")]
-pub struct SaleArgs {
-    pub sale_conditions: SalePriceInYoctoNear,
+#[serde(untagged)]
+pub enum SaleArgs {
+    //an English auction, e.g. {"auction": {"reserve_price": "1000", "min_increment": "100", "ends_at": 1700000000000}}
+    Auction { auction: AuctionArgs, ft_token_id: Option<FungibleTokenId> },
+    //a Dutch auction, e.g. {"dutch_auction": {"start_price": "1000", "floor_price": "100", "starts_at": 1700000000000, "duration": 86400000}}
+    DutchAuction { dutch_auction: DutchAuction, ft_token_id: Option<FungibleTokenId> },
+    //a fixed price sale, e.g. {"sale_conditions": "1000"}
+    FixedPrice { sale_conditions: SalePriceInYoctoNear, ft_token_id: Option<FungibleTokenId> },
+}
+//any variant can be priced in a whitelisted fungible token instead of NEAR by adding e.g. "ft_token_id": "usdc.near".
+//auctions can only be priced in NEAR
+
+//struct for keeping track of the conditions of an auction
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AuctionArgs {
+    //lowest amount the first bid can be
+    pub reserve_price: U128,
+    //how much every bid must add to the current highest bid
+    pub min_increment: U128,
+    //when the auction ends, as a Unix epoch in milliseconds
+    pub ends_at: u64,
 }
 
 /*
@@ -14,7 +34,7 @@ pub struct SaleArgs {
     called, it will fire a cross contract call to this marketplace and this is the function
     that is invoked. 
 */
-trait NonFungibleTokenApprovalsReceiver {
+pub trait NonFungibleTokenApprovalsReceiver {
     fn nft_on_approve(
         &mut self,
         token_id: TokenId,
@@ -36,6 +56,9 @@ impl NonFungibleTokenApprovalsReceiver for Contract {
         approval_id: u64,
         msg: String,
     ) {
+        //make sure listing isn't paused
+        self.assert_not_paused(PauseFeature::Listing);
+
         // get the contract ID which is the predecessor
         let nft_contract_id = env::predecessor_account_id();
         //get the signer which is the person who initiated the transaction
@@ -55,6 +78,21 @@ impl NonFungibleTokenApprovalsReceiver for Contract {
             "owner_id should be signer_id"
         );
 
+        //relisting would drop the escrowed bid of an auction, so an auction with bids can only be settled
+        let contract_and_token_id = format!("{}{}{}", nft_contract_id, DELIMETER, token_id);
+        let existing_sale = self.sales.get(&contract_and_token_id);
+        assert!(
+            existing_sale
+                .as_ref()
+                .and_then(|sale| sale.auction.as_ref())
+                .is_none_or(|auction| auction.highest_bid.is_none()),
+            "Cannot relist an auction with bids"
+        );
+        //an existing sale of the token is replaced, so it's removed first along with its entries in the view collections
+        if existing_sale.is_some() {
+            self.internal_remove_sale(nft_contract_id.clone(), token_id.clone());
+        }
+
         //we need to enforce that the user has enough storage for 1 EXTRA sale.  
 
         //get the storage for a sale. dot 0 converts from U128 to u128
@@ -72,25 +110,72 @@ impl NonFungibleTokenApprovalsReceiver for Contract {
         );
 
         //if all these checks pass we can create the sale conditions object.
-        let SaleArgs { sale_conditions } =
+        let sale_args: SaleArgs =
             //the sale conditions come from the msg field. The market assumes that the user passed
             //in a proper msg. If they didn't, it panics. 
             near_sdk::serde_json::from_str(&msg).expect("Not valid SaleArgs");
 
-        //create the unique sale ID which is the contract + DELIMITER + token ID
-        let contract_and_token_id = format!("{}{}{}", nft_contract_id, DELIMETER, token_id);
+        //an auction is listed at its reserve price until it gets bids
+        let (sale_conditions, auction, dutch_auction, ft_token_id) = match sale_args {
+            SaleArgs::FixedPrice { sale_conditions, ft_token_id } => (sale_conditions, None, None, ft_token_id),
+            //a Dutch auction is listed at its start price. The views show the live price
+            SaleArgs::DutchAuction { dutch_auction, ft_token_id } => {
+                assert!(
+                    dutch_auction.start_price.0 >= dutch_auction.floor_price.0,
+                    "Start price must be greater than or equal to the floor price"
+                );
+                assert!(dutch_auction.duration > 0, "Duration must be greater than 0");
+                (dutch_auction.start_price, None, Some(dutch_auction), ft_token_id)
+            }
+            SaleArgs::Auction { auction, ft_token_id } => {
+                assert!(ft_token_id.is_none(), "Auctions can only be priced in NEAR");
+                assert!(auction.min_increment.0 > 0, "Minimum increment must be greater than 0");
+                assert!(
+                    auction.ends_at > env::block_timestamp_ms(),
+                    "Auction must end in the future"
+                );
+                (
+                    auction.reserve_price,
+                    Some(Auction {
+                        reserve_price: auction.reserve_price,
+                        min_increment: auction.min_increment,
+                        ends_at: auction.ends_at,
+                        highest_bid: None,
+                    }),
+                    None,
+                    None,
+                )
+            }
+        };
+        //a sale priced in a fungible token is bought with ft_transfer_call, so the token must be whitelisted
+        if let Some(ft_token_id) = &ft_token_id {
+            assert!(
+                self.ft_token_ids.contains(ft_token_id),
+                "Fungible token {} is not supported",
+                ft_token_id
+            );
+        }
+
         
+        //create the sale object
+        let sale = Sale {
+            owner_id: owner_id.clone(), //owner of the sale / token
+            approval_id, //approval ID for that token that was given to the market
+            nft_contract_id: nft_contract_id.to_string(), //NFT contract the token was minted on
+            token_id: token_id.clone(), //the actual token ID
+            sale_conditions, //the sale conditions 
+            auction, //the auction, if the token is auctioned
+            dutch_auction, //the Dutch auction, if the price declines over time
+            ft_token_id, //the fungible token the sale is priced in, if it isn't priced in NEAR
+        };
         //insert the key value pair into the sales map. Key is the unique ID. value is the sale object
-        self.sales.insert(
-            &contract_and_token_id,
-            &Sale {
-                owner_id: owner_id.clone(), //owner of the sale / token
-                approval_id, //approval ID for that token that was given to the market
-                nft_contract_id: nft_contract_id.to_string(), //NFT contract the token was minted on
-                token_id: token_id.clone(), //the actual token ID
-                sale_conditions, //the sale conditions 
-           },
-        );
+        self.sales.insert(&contract_and_token_id, &sale);
+        //relisting a token updates its sale
+        if existing_sale.is_some() {
+            EventLog::emit(EventLogVariant::SaleUpdated(vec![sale.to_log()]));
+        } else {
+            EventLog::emit(EventLogVariant::SaleCreated(vec![sale.to_log()]));
+        }
 
         //Extra functionality that populates collections necessary for the view calls 
 
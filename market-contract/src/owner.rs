@@ -0,0 +1,66 @@
+use crate::*;
+
+#[near_bindgen]
+impl Contract {
+    /// Propose a new contract owner. The transfer only happens once the proposed account calls `accept_ownership`.
+    /// A new proposal replaces any pending one.
+    #[payable]
+    pub fn propose_owner(&mut self, new_owner_id: AccountId) {
+        //assert that the user attached exactly 1 yoctoNEAR for security reasons
+        assert_one_yocto();
+        self.assert_contract_owner();
+        assert_ne!(new_owner_id, self.owner_id, "Account is already the contract owner");
+
+        self.pending_owner_id = Some(new_owner_id.clone());
+        EventLog::emit(EventLogVariant::OwnershipTransferPropose(vec![OwnershipTransferLog {
+            old_owner_id: self.owner_id.to_string(),
+            new_owner_id: new_owner_id.to_string(),
+            memo: None,
+        }]));
+    }
+
+    /// Accept a pending ownership transfer. Must be called by the proposed owner.
+    #[payable]
+    pub fn accept_ownership(&mut self) {
+        //assert that the user attached exactly 1 yoctoNEAR for security reasons
+        assert_one_yocto();
+        let new_owner_id = self.pending_owner_id.take().expect("No pending ownership transfer");
+        assert_eq!(
+            env::predecessor_account_id(),
+            new_owner_id,
+            "only pending owner"
+        );
+
+        let old_owner_id = std::mem::replace(&mut self.owner_id, new_owner_id.clone());
+        EventLog::emit(EventLogVariant::OwnershipTransferAccept(vec![OwnershipTransferLog {
+            old_owner_id: old_owner_id.to_string(),
+            new_owner_id: new_owner_id.to_string(),
+            memo: None,
+        }]));
+    }
+
+    /// Cancel a pending ownership transfer
+    #[payable]
+    pub fn cancel_ownership_transfer(&mut self) {
+        //assert that the user attached exactly 1 yoctoNEAR for security reasons
+        assert_one_yocto();
+        self.assert_contract_owner();
+        let new_owner_id = self.pending_owner_id.take().expect("No pending ownership transfer");
+
+        EventLog::emit(EventLogVariant::OwnershipTransferCancel(vec![OwnershipTransferLog {
+            old_owner_id: self.owner_id.to_string(),
+            new_owner_id: new_owner_id.to_string(),
+            memo: None,
+        }]));
+    }
+
+    /// Get the contract owner
+    pub fn get_owner(&self) -> AccountId {
+        self.owner_id.clone()
+    }
+
+    /// Get the account the ownership transfer was proposed to, if any
+    pub fn get_pending_owner(&self) -> Option<AccountId> {
+        self.pending_owner_id.clone()
+    }
+}
@@ -0,0 +1,79 @@
+use crate::*;
+
+//features of the market that can be paused independently
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "snake_case")]
+pub enum PauseFeature {
+    //nft_on_approve
+    Listing,
+    //offer, place_bid and ft_on_transfer
+    Purchasing,
+}
+
+#[near_bindgen]
+impl Contract {
+    //pause a feature. Can be called by the contract owner or a pauser. View methods keep working while paused
+    pub fn pause(&mut self, feature: PauseFeature) {
+        self.assert_owner_or_pauser();
+        //only log when the feature wasn't paused yet
+        if self.paused_features.insert(&feature) {
+            EventLog::emit(EventLogVariant::Pause(vec![PauseLog {
+                feature,
+                account_id: env::predecessor_account_id().to_string(),
+                memo: None,
+            }]));
+        }
+    }
+
+    //unpause a feature. Can be called by the contract owner or a pauser
+    pub fn unpause(&mut self, feature: PauseFeature) {
+        self.assert_owner_or_pauser();
+        //only log when the feature was paused
+        if self.paused_features.remove(&feature) {
+            EventLog::emit(EventLogVariant::Unpause(vec![PauseLog {
+                feature,
+                account_id: env::predecessor_account_id().to_string(),
+                memo: None,
+            }]));
+        }
+    }
+
+    //check if a feature is paused
+    pub fn is_paused(&self, feature: PauseFeature) -> bool {
+        self.paused_features.contains(&feature)
+    }
+
+    //give a specified account the right to pause and unpause
+    pub fn add_pauser(&mut self, account_id: AccountId) {
+        self.assert_contract_owner();
+        self.pausers.insert(&account_id);
+    }
+
+    //take the right to pause and unpause away from a specified account
+    pub fn remove_pauser(&mut self, account_id: AccountId) {
+        self.assert_contract_owner();
+        self.pausers.remove(&account_id);
+    }
+
+    //check if a specified account can pause and unpause
+    pub fn is_pauser(&self, account_id: AccountId) -> bool {
+        self.pausers.contains(&account_id)
+    }
+}
+
+impl Contract {
+    //make sure that the feature isn't paused
+    pub(crate) fn assert_not_paused(&self, feature: PauseFeature) {
+        assert!(!self.paused_features.contains(&feature), "{:?} is paused", feature);
+    }
+
+    //make sure that the caller is the owner of the contract or a pauser
+    fn assert_owner_or_pauser(&self) {
+        let predecessor_id = env::predecessor_account_id();
+        assert!(
+            predecessor_id == self.owner_id || self.pausers.contains(&predecessor_id),
+            "only contract owner or pauser"
+        );
+    }
+}
@@ -1,5 +1,7 @@
 use crate::*;
+use crate::ft_callbacks::transfer_ft;
 use near_sdk::promise_result_as_success;
+use std::collections::BTreeMap;
 
 //struct that holds important information about each sale on the market
 #[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
@@ -13,8 +15,42 @@ pub struct Sale {
     pub nft_contract_id: String,
     //actual token ID for sale
     pub token_id: String,
-    //sale price in yoctoNEAR that the token is listed for
+    //sale price in yoctoNEAR that the token is listed for. For an auction, this is the reserve price
     pub sale_conditions: SalePriceInYoctoNear,
+    //the auction state (current highest bid and end time) if the token is auctioned instead of sold at a fixed price
+    pub auction: Option<Auction>,
+    //the declining price if the token is sold in a Dutch auction
+    pub dutch_auction: Option<DutchAuction>,
+    //the fungible token the sale is priced in. If there is none, the sale is priced in NEAR
+    pub ft_token_id: Option<FungibleTokenId>,
+}
+
+impl Sale {
+    //the price the token can be bought for right now. A Dutch auction declines from its start price, otherwise it's the listed price
+    pub(crate) fn current_price(&self) -> u128 {
+        match &self.dutch_auction {
+            Some(dutch_auction) => dutch_auction.price_at(env::block_timestamp_ms()),
+            None => self.sale_conditions.0,
+        }
+    }
+
+    //the log of the sale for the sale_created, sale_updated and sale_removed events
+    pub(crate) fn to_log(&self) -> SaleLog {
+        SaleLog {
+            nft_contract_id: self.nft_contract_id.clone(),
+            token_id: self.token_id.clone(),
+            owner_id: self.owner_id.to_string(),
+            price: self.sale_conditions,
+            ft_token_id: self.ft_token_id.as_ref().map(|ft_token_id| ft_token_id.to_string()),
+            memo: None,
+        }
+    }
+
+    //the sale as returned by the views, with the live price as the sale conditions
+    pub(crate) fn with_live_price(mut self) -> Self {
+        self.sale_conditions = U128(self.current_price());
+        self
+    }
 }
 
 #[near_bindgen]
@@ -27,10 +63,17 @@ impl Contract {
         assert_one_yocto();
         //get the sale object as the return value from removing the sale internally
         let sale = self.internal_remove_sale(nft_contract_id.into(), token_id);
+        //an auction with bids can only be settled, since the bidder's funds are escrowed
+        assert!(
+            sale.auction.as_ref().is_none_or(|auction| auction.highest_bid.is_none()),
+            "Cannot remove an auction with bids"
+        );
         //get the predecessor of the call and make sure they're the owner of the sale
         let owner_id = env::predecessor_account_id();
         //if this fails, the remove sale will revert
         assert_eq!(owner_id, sale.owner_id, "Must be sale owner");
+
+        EventLog::emit(EventLogVariant::SaleRemoved(vec![sale.to_log()]));
     }
 
     //updates the price for a sale on the market
@@ -57,16 +100,25 @@ impl Contract {
             sale.owner_id,
             "Must be sale owner"
         );
+        assert!(
+            sale.auction.is_none() && sale.dutch_auction.is_none(),
+            "Cannot update the price of an auction"
+        );
         
         //set the sale conditions equal to the passed in price
         sale.sale_conditions = price;
         //insert the sale back into the map for the unique sale ID
         self.sales.insert(&contract_and_token_id, &sale);
+
+        EventLog::emit(EventLogVariant::SaleUpdated(vec![sale.to_log()]));
     }
 
     //place an offer on a specific sale. The sale will go through as long as your deposit is greater than or equal to the list price
     #[payable]
     pub fn offer(&mut self, nft_contract_id: AccountId, token_id: String) {
+        //make sure purchasing isn't paused
+        self.assert_not_paused(PauseFeature::Purchasing);
+
         //get the attached deposit and make sure it's greater than 0
         let deposit = env::attached_deposit();
         assert!(deposit > 0, "Attached deposit must be greater than 0");
@@ -82,18 +134,35 @@ impl Contract {
         //get the buyer ID which is the person who called the function and make sure they're not the owner of the sale
         let buyer_id = env::predecessor_account_id();
         assert_ne!(sale.owner_id, buyer_id, "Cannot bid on your own sale.");
+        //auctions are bought through place_bid and settle_auction
+        assert!(sale.auction.is_none(), "Sale is an auction, use place_bid");
+        //sales priced in a fungible token are bought through ft_transfer_call
+        assert!(sale.ft_token_id.is_none(), "Sale is priced in a fungible token, use ft_transfer_call");
         
-        //get the u128 price of the token (dot 0 converts from U128 to u128)
-        let price = sale.sale_conditions.0;
+        //get the u128 price of the token. For a Dutch auction, this is the price at the current block
+        let price = sale.current_price();
 
         //make sure the deposit is greater than the price
         assert!(deposit >= price, "Attached deposit must be greater than or equal to the current price: {:?}", price);
 
+        //a Dutch auction is paid at the current price and the rest of the deposit is refunded
+        let mut paid = deposit;
+        if let Some(dutch_auction) = &sale.dutch_auction {
+            assert!(
+                env::block_timestamp_ms() >= dutch_auction.starts_at,
+                "Dutch auction has not started yet"
+            );
+            paid = price;
+            if deposit > price {
+                Promise::new(buyer_id.clone()).transfer(deposit - price);
+            }
+        }
+
         //process the purchase (which will remove the sale, transfer and get the payout from the nft contract, and then distribute royalties) 
         self.process_purchase(
             contract_id,
             token_id,
-            U128(deposit),
+            U128(paid),
             buyer_id,
         );
     }
@@ -110,6 +179,8 @@ impl Contract {
     ) -> Promise {
         //get the sale object by removing the sale
         let sale = self.internal_remove_sale(nft_contract_id.clone(), token_id.clone());
+        //the market fee is taken out of the price before the nft contract splits the rest into the payout
+        let market_fee = self.market_fee_for(price.0);
 
         //initiate a cross contract call to the nft contract. This will transfer the token to the buyer and return
         //a payout object used for the market to distribute funds to the appropriate accounts.
@@ -123,10 +194,10 @@ impl Contract {
                 sale.approval_id, //market contract's approval ID in order to transfer the token on behalf of the owner
             "payout from market".to_string(), //memo (to include some context)
             /*
-                the price that the token was purchased for. This will be used in conjunction with the royalty percentages
+                the price that the token was purchased for, without the market fee. This will be used in conjunction with the royalty percentages
                 for the token in order to determine how much money should go to which account. 
             */
-            price,
+            U128(price.0 - market_fee),
             10, //the maximum amount of accounts the market can payout at once (this is limited by GAS)
             )
         //after the transfer payout has been initiated, we resolve the promise by calling our own resolve_purchase function. 
@@ -138,6 +209,8 @@ impl Contract {
             .resolve_purchase(
                 buyer_id, //the buyer and price are passed in incase something goes wrong and we need to refund the buyer
                 price,
+                U128(market_fee), //the market fee is only collected once the purchase went through
+                sale, //the sale is passed in for the fungible token the price was paid in and for the purchase event
             )
         )
     }
@@ -145,13 +218,15 @@ impl Contract {
     /*
         private method used to resolve the promise when calling nft_transfer_payout. This will take the payout object and 
         check to see if it's authentic and there's no problems. If everything is fine, it will pay the accounts. If there's a problem,
-        it will refund the buyer for the price. 
+        it will refund the buyer for the price. Sales priced in a fungible token are paid and refunded with ft_transfer.
     */
     #[private]
     pub fn resolve_purchase(
         &mut self,
         buyer_id: AccountId,
         price: U128,
+        market_fee: U128,
+        sale: Sale,
     ) -> U128 {
         // checking for payout information returned from the nft_transfer_payout method
         let payout_option = promise_result_as_success().and_then(|value| {
@@ -168,8 +243,8 @@ impl Contract {
                     
                     //if the payout object is the correct length, we move forward
                     } else {
-                        //we'll keep track of how much the nft contract wants us to payout. Starting at the full price payed by the buyer
-                        let mut remainder = price.0;
+                        //we'll keep track of how much the nft contract wants us to payout. Starting at the price payed by the buyer without the market fee
+                        let mut remainder = price.0 - market_fee.0;
                         
                         //loop through the payout and subtract the values from the remainder. 
                         for &value in payout_object.payout.values() {
@@ -195,15 +270,40 @@ impl Contract {
             payout_option
         //if the payout option was None, we refund the buyer for the price they payed and return
         } else {
-            Promise::new(buyer_id).transfer(u128::from(price));
+            transfer_payment(&sale.ft_token_id, buyer_id.clone(), price);
+            EventLog::emit(EventLogVariant::PurchaseFailed(vec![PurchaseFailedLog {
+                nft_contract_id: sale.nft_contract_id,
+                token_id: sale.token_id,
+                owner_id: sale.owner_id.to_string(),
+                buyer_id: buyer_id.to_string(),
+                price,
+                ft_token_id: sale.ft_token_id.map(|ft_token_id| ft_token_id.to_string()),
+                memo: None,
+            }]));
             // leave function and return the price that was refunded
             return price;
         };
 
-        // NEAR payouts
+        // NEAR or fungible token payouts
+        let mut payout_log = BTreeMap::new();
         for (receiver_id, amount) in payout {
-            Promise::new(receiver_id).transfer(amount.0);
+            payout_log.insert(receiver_id.to_string(), amount);
+            transfer_payment(&sale.ft_token_id, receiver_id, amount);
         }
+        //the market keeps the fee
+        self.internal_collect_market_fee(&sale.ft_token_id, market_fee.0);
+
+        EventLog::emit(EventLogVariant::SalePurchased(vec![SalePurchasedLog {
+            nft_contract_id: sale.nft_contract_id,
+            token_id: sale.token_id,
+            owner_id: sale.owner_id.to_string(),
+            buyer_id: buyer_id.to_string(),
+            price,
+            market_fee,
+            payout: payout_log,
+            ft_token_id: sale.ft_token_id.map(|ft_token_id| ft_token_id.to_string()),
+            memo: None,
+        }]));
 
         //return the price payout out
         price
@@ -222,5 +322,21 @@ trait ExtSelf {
         &mut self,
         buyer_id: AccountId,
         price: U128,
+        market_fee: U128,
+        sale: Sale,
     ) -> Promise;
 }
+
+//pay an account in NEAR, or in the fungible token the sale is priced in. ft_transfer requires exactly 1 yoctoNEAR,
+//which is paid by the market (see FT_TRANSFER_DEPOSIT). If the receiver isn't registered with the FT contract, the amount
+//can be claimed with claim_ft
+pub(crate) fn transfer_payment(ft_token_id: &Option<FungibleTokenId>, receiver_id: AccountId, amount: U128) {
+    match ft_token_id {
+        Some(ft_token_id) => {
+            transfer_ft(ft_token_id.clone(), receiver_id, amount);
+        }
+        None => {
+            Promise::new(receiver_id).transfer(amount.0);
+        }
+    }
+}
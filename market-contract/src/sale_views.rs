@@ -1,4 +1,37 @@
 use crate::*;
+use near_sdk::collections::Vector;
+
+/// Number of sales returned in a page when no limit is passed in
+pub const DEFAULT_PAGE_LIMIT: u64 = 50;
+/// Maximum number of sales returned in a page, to stay within the gas limit of view calls
+pub const MAX_PAGE_LIMIT: u64 = 100;
+
+/// A page of sales along with the cursor to fetch the next page
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SalePage {
+    pub sales: Vec<Sale>,
+    // Value to pass as from_index to get the next page. None on the last page
+    pub next_cursor: Option<U128>,
+}
+
+//returns the items of one page of the vector, along with the index the next page starts from if there is one.
+//pages index directly into the vector so their cost only depends on the limit.
+pub(crate) fn paginate<T: BorshSerialize + BorshDeserialize>(
+    items: &Vector<T>,
+    from_index: Option<U128>,
+    limit: Option<u64>,
+) -> (Vec<T>, Option<U128>) {
+    //where to start pagination - if we have a from_index, we'll use that - otherwise start from 0 index
+    let start = u128::from(from_index.unwrap_or(U128(0))).min(items.len() as u128) as u64;
+    //take "limit" elements, at most MAX_PAGE_LIMIT. If we didn't specify a limit, use 50
+    let limit = limit.unwrap_or(DEFAULT_PAGE_LIMIT).min(MAX_PAGE_LIMIT);
+    let end = (start + limit).min(items.len());
+
+    let page = (start..end).map(|index| items.get(index).unwrap()).collect();
+    let next_cursor = if end < items.len() { Some(U128(end as u128)) } else { None };
+    (page, next_cursor)
+}
 
 #[near_bindgen]
 impl Contract {
@@ -35,31 +68,30 @@ impl Contract {
         from_index: Option<U128>,
         limit: Option<u64>,
     ) -> Vec<Sale> {
-        //get the set of token IDs for sale for the given account ID
-        let by_owner_id = self.by_owner_id.get(&account_id);
-        //if there was some set, we set the sales variable equal to that set. If there wasn't, sales is set to an empty vector
-        let sales = if let Some(by_owner_id) = by_owner_id {
-            by_owner_id
-        } else {
-            return vec![];
+        self.get_sales_by_owner_id_page(account_id, from_index, limit).sales
+    }
+
+    //returns paginated sale objects for a given account like get_sales_by_owner_id, along with the cursor of the next page
+    pub fn get_sales_by_owner_id_page(
+        &self,
+        account_id: AccountId,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> SalePage {
+        //get the set of unique sale IDs for the given account ID. If there is none, we return an empty page
+        let (sale_ids, next_cursor) = match self.by_owner_id.get(&account_id) {
+            Some(by_owner_id) => paginate(by_owner_id.as_vector(), from_index, limit),
+            None => (vec![], None),
         };
-        
-        //we'll convert the UnorderedSet into a vector of strings
-        let keys = sales.as_vector();
 
-        //where to start pagination - if we have a from_index, we'll use that - otherwise start from 0 index
-        let start = u128::from(from_index.unwrap_or(U128(0)));
-        
-        //iterate through the keys vector
-        keys.iter()
-            //skip to the index we specified in the start variable
-            .skip(start as usize) 
-            //take the first "limit" elements in the vector. If we didn't specify a limit, use 0
-            .take(limit.unwrap_or(0) as usize) 
-            //we'll map the token IDs which are strings into Sale objects
-            .map(|token_id| self.sales.get(&token_id).unwrap())
-            //since we turned the keys into an iterator, we need to turn it back into a vector to return
-            .collect()
+        SalePage {
+            //we'll map the unique sale IDs into Sale objects
+            sales: sale_ids
+                .iter()
+                .map(|contract_and_token_id| self.sales.get(contract_and_token_id).unwrap().with_live_price())
+                .collect(),
+            next_cursor,
+        }
     }
 
     //get the number of sales for an nft contract. (returns a string)
@@ -85,38 +117,43 @@ impl Contract {
         from_index: Option<U128>,
         limit: Option<u64>,
     ) -> Vec<Sale> {
-        //get the set of token IDs for sale for the given contract ID
-        let by_nft_contract_id = self.by_nft_contract_id.get(&nft_contract_id);
-        
-        //if there was some set, we set the sales variable equal to that set. If there wasn't, sales is set to an empty vector
-        let sales = if let Some(by_nft_contract_id) = by_nft_contract_id {
-            by_nft_contract_id
-        } else {
-            return vec![];
-        };
+        self.get_sales_by_nft_contract_id_page(nft_contract_id, from_index, limit).sales
+    }
 
-        //we'll convert the UnorderedSet into a vector of strings
-        let keys = sales.as_vector();
+    //returns paginated sale objects associated with a given nft contract like get_sales_by_nft_contract_id,
+    //along with the cursor of the next page
+    pub fn get_sales_by_nft_contract_id_page(
+        &self,
+        nft_contract_id: AccountId,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> SalePage {
+        //get the set of token IDs for sale for the given contract ID. If there is none, we return an empty page
+        let (token_ids, next_cursor) = match self.by_nft_contract_id.get(&nft_contract_id) {
+            Some(by_nft_contract_id) => paginate(by_nft_contract_id.as_vector(), from_index, limit),
+            None => (vec![], None),
+        };
 
-        //where to start pagination - if we have a from_index, we'll use that - otherwise start from 0 index
-        let start = u128::from(from_index.unwrap_or(U128(0)));
-        
-        //iterate through the keys vector
-        keys.iter()
-            //skip to the index we specified in the start variable
-            .skip(start as usize) 
-            //take the first "limit" elements in the vector. If we didn't specify a limit, use 0
-            .take(limit.unwrap_or(0) as usize) 
-            //we'll map the token IDs which are strings into Sale objects by passing in the unique sale ID (contract + DELIMITER + token ID)
-            .map(|token_id| self.sales.get(&format!("{}{}{}", nft_contract_id, DELIMETER, token_id)).unwrap())
-            //since we turned the keys into an iterator, we need to turn it back into a vector to return
-            .collect()
+        SalePage {
+            //we'll map the token IDs into Sale objects by passing in the unique sale ID (contract + DELIMITER + token ID)
+            sales: token_ids
+                .iter()
+                .map(|token_id| {
+                    self.sales
+                        .get(&format!("{}{}{}", nft_contract_id, DELIMETER, token_id))
+                        .unwrap()
+                        .with_live_price()
+                })
+                .collect(),
+            next_cursor,
+        }
     }
 
-    //get a sale information for a given unique sale ID (contract + DELIMITER + token ID)
+    //get a sale information for a given unique sale ID (contract + DELIMITER + token ID).
+    //the sale conditions of a Dutch auction are the price at the current block
     pub fn get_sale(&self, nft_contract_token: ContractAndTokenId) -> Option<Sale> {
         //try and get the sale object for the given unique sale ID. Will return an option since
         //we're not guaranteed that the unique sale ID passed in will be valid.
-        self.sales.get(&nft_contract_token)
+        self.sales.get(&nft_contract_token).map(Sale::with_live_price)
     }
 }
@@ -0,0 +1,50 @@
+use crate::*;
+
+/// A standard implemented by the contract along with its version
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Standard {
+    // Name of the standard, e.g. "nep297"
+    pub standard: String,
+    // Implemented version of the standard, e.g. "1.0.0"
+    pub version: String,
+}
+
+/// NEP-330 contract source metadata
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ContractSourceMetadata {
+    // Crate version at build time
+    pub version: Option<String>,
+    // Link to the source code repository
+    pub link: Option<String>,
+    // Implemented standards
+    pub standards: Vec<Standard>,
+}
+
+/// Link to the source the wasm was built from. build.sh passes the commit in GIT_COMMIT, otherwise it's the repository
+fn source_link(repository: &str) -> String {
+    match option_env!("GIT_COMMIT").filter(|commit| !commit.is_empty()) {
+        Some(commit) => format!("{}/tree/{}", repository, commit),
+        None => repository.to_string(),
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Get the version, source code link and supported standards of the deployed contract. Filled in at build time.
+    /// The market only trades tokens, so the NFT standards it relies on are implemented by the NFT contracts
+    pub fn contract_source_metadata(&self) -> ContractSourceMetadata {
+        ContractSourceMetadata {
+            version: Some(env!("CARGO_PKG_VERSION").to_string()),
+            link: option_env!("CARGO_PKG_REPOSITORY").map(source_link),
+            standards: [("nep297", "1.0.0"), ("nep330", "1.1.0")]
+                .iter()
+                .map(|(standard, version)| Standard {
+                    standard: standard.to_string(),
+                    version: version.to_string(),
+                })
+                .collect(),
+        }
+    }
+}
@@ -2,12 +2,17 @@
 use crate::sale::Sale;
 #[cfg(test)]
 use crate::Contract;
+use crate::migrate::{ContractV1, ContractV2, SaleV1, VersionedContract, MAX_MIGRATION_BATCH_SIZE};
+use crate::ft_callbacks::FungibleTokenReceiver;
+use crate::nft_callbacks::NonFungibleTokenApprovalsReceiver;
+use crate::{PauseFeature, StorageKey};
 use near_sdk::{
-    collections::UnorderedSet,
+    collections::{LookupMap, UnorderedMap, UnorderedSet},
     env,
     json_types::{U128, U64},
-    test_utils::{accounts, VMContextBuilder},
-    testing_env, AccountId,
+    mock::VmAction,
+    test_utils::{accounts, get_created_receipts, get_logs, VMContextBuilder},
+    testing_env, AccountId, PromiseOrValue, PromiseResult,
 };
 
 const MIN_REQUIRED_APPROVAL_YOCTO: u128 = 170000000000000000000;
@@ -120,6 +125,9 @@ fn test_remove_sale() {
         nft_contract_id: env::predecessor_account_id().to_string(), //NFT contract the token was minted on
         token_id: token_id.clone(),                                 //the actual token ID
         sale_conditions: U128(100), //the sale conditions -- price in YOCTO NEAR
+        auction: None,
+        dutch_auction: None,
+        ft_token_id: None,
     };
     let nft_contract_id = env::predecessor_account_id();
     let contract_and_token_id = format!("{}{}{}", nft_contract_id, ".", token_id);
@@ -171,6 +179,9 @@ fn test_update_price() {
         nft_contract_id: env::predecessor_account_id().to_string(), //NFT contract the token was minted on
         token_id: token_id.clone(),                                 //the actual token ID
         sale_conditions: nft_bid_yocto, //the sale conditions -- price in YOCTO NEAR
+        auction: None,
+        dutch_auction: None,
+        ft_token_id: None,
     };
     let nft_contract_id = env::predecessor_account_id();
     let contract_and_token_id = format!("{}{}{}", nft_contract_id, ".", token_id);
@@ -197,4 +208,735 @@ fn test_update_price() {
     // test update price success
     let sale = contract.sales.get(&contract_and_token_id).expect("No sale");
     assert_eq!(sale.sale_conditions, new_price);
-}
\ No newline at end of file
+}
+#[test]
+fn test_ownership_transfer() {
+    let mut context = get_context(accounts(0));
+    testing_env!(context.attached_deposit(1).build());
+    let mut contract = Contract::new(accounts(0));
+
+    contract.propose_owner(accounts(1));
+    assert_eq!(contract.get_pending_owner(), Some(accounts(1)));
+
+    testing_env!(context.predecessor_account_id(accounts(1)).build());
+    contract.accept_ownership();
+    assert_eq!(contract.get_owner(), accounts(1));
+    assert_eq!(contract.get_pending_owner(), None);
+}
+
+#[test]
+#[should_panic(expected = "only contract owner")]
+fn test_propose_owner_not_owner() {
+    let mut context = get_context(accounts(0));
+    testing_env!(context.attached_deposit(1).build());
+    let mut contract = Contract::new(accounts(0));
+
+    testing_env!(context.predecessor_account_id(accounts(1)).build());
+    contract.propose_owner(accounts(1));
+}
+
+//store a state with the layout of the first deployments, with one storage deposit and two sales
+fn write_v1_state(owner_id: AccountId) {
+    let mut state = ContractV1 {
+        owner_id: owner_id.clone(),
+        sales: UnorderedMap::new(StorageKey::Sales),
+        by_owner_id: LookupMap::new(StorageKey::ByOwnerId),
+        by_nft_contract_id: LookupMap::new(StorageKey::ByNFTContractId),
+        storage_deposits: LookupMap::new(StorageKey::StorageDeposits),
+    };
+    state.storage_deposits.insert(&owner_id, &MIN_REQUIRED_STORAGE_YOCTO);
+    state.sales.insert(
+        &format!("{}.0", accounts(1)),
+        &SaleV1 {
+            owner_id: owner_id.clone(),
+            approval_id: 0,
+            nft_contract_id: accounts(1).to_string(),
+            token_id: "0".to_string(),
+            sale_conditions: U128(100),
+        },
+    );
+    state.sales.insert(
+        &format!("{}.1", accounts(1)),
+        &SaleV1 {
+            owner_id: owner_id.clone(),
+            approval_id: 1,
+            nft_contract_id: accounts(1).to_string(),
+            token_id: "1".to_string(),
+            sale_conditions: U128(200),
+        },
+    );
+    env::state_write(&state);
+}
+
+#[test]
+fn test_migrate_from_v1() {
+    let context = get_context(accounts(0));
+    testing_env!(context.build());
+    write_v1_state(accounts(0));
+
+    //the first call only converts one sale and the state keeps its old layout
+    assert!(matches!(Contract::migrate(Some(1)), PromiseOrValue::Value(false)));
+    assert!(matches!(VersionedContract::read(), VersionedContract::V1(_)));
+
+    //the second call picks up where the first one stopped and finishes the migration
+    assert!(matches!(Contract::migrate(None), PromiseOrValue::Value(true)));
+    let contract: Contract = env::state_read().unwrap();
+    assert_eq!(contract.get_owner(), accounts(0));
+    assert_eq!(contract.get_pending_owner(), None);
+    assert_eq!(contract.storage_balance_of(accounts(0)), U128(MIN_REQUIRED_STORAGE_YOCTO));
+    //sales listed before auctions become fixed price sales
+    let sale = contract.get_sale(format!("{}.0", accounts(1))).unwrap();
+    assert_eq!(sale.sale_conditions, U128(100));
+    assert!(sale.auction.is_none());
+    assert!(sale.dutch_auction.is_none());
+    assert_eq!(contract.get_sale(format!("{}.1", accounts(1))).unwrap().sale_conditions, U128(200));
+
+    //migrating a state that already has the current layout changes nothing
+    assert!(matches!(Contract::migrate(None), PromiseOrValue::Value(true)));
+    let contract: Contract = env::state_read().unwrap();
+    assert_eq!(contract.storage_balance_of(accounts(0)), U128(MIN_REQUIRED_STORAGE_YOCTO));
+}
+
+#[test]
+fn test_migrate_chains_batches() {
+    let context = get_context(accounts(0));
+    testing_env!(context.build());
+    write_v1_state(accounts(0));
+    //one sale more than a single call converts
+    let mut state = env::state_read::<ContractV1>().unwrap();
+    for token_id in 2..=MAX_MIGRATION_BATCH_SIZE {
+        state.sales.insert(
+            &format!("{}.{}", accounts(1), token_id),
+            &SaleV1 {
+                owner_id: accounts(0),
+                approval_id: token_id,
+                nft_contract_id: accounts(1).to_string(),
+                token_id: token_id.to_string(),
+                sale_conditions: U128(100),
+            },
+        );
+    }
+    env::state_write(&state);
+
+    //without a limit a full batch is converted and migrate is called again for the rest
+    assert!(matches!(Contract::migrate(None), PromiseOrValue::Promise(_)));
+    assert!(matches!(VersionedContract::read(), VersionedContract::V1(_)));
+    let receipts = get_created_receipts();
+    assert_eq!(receipts.len(), 1);
+    assert_eq!(receipts[0].receiver_id, env::current_account_id());
+    assert!(matches!(
+        &receipts[0].actions[0],
+        VmAction::FunctionCall { function_name, .. } if function_name == "migrate"
+    ));
+
+    //the chained call finishes the migration
+    assert!(matches!(Contract::migrate(None), PromiseOrValue::Value(true)));
+    let contract: Contract = env::state_read().unwrap();
+    assert_eq!(contract.sales.len(), MAX_MIGRATION_BATCH_SIZE + 1);
+}
+
+#[test]
+fn test_migrate_from_v2() {
+    let context = get_context(accounts(0));
+    testing_env!(context.build());
+    write_v1_state(accounts(0));
+
+    //a V2 state keeps its pending owner
+    let mut state = ContractV2::from(env::state_read::<ContractV1>().unwrap());
+    state.pending_owner_id = Some(accounts(2));
+    env::state_write(&state);
+    env::storage_write(b"STATE_VERSION", &[2]);
+
+    assert!(matches!(Contract::migrate(None), PromiseOrValue::Value(true)));
+    let contract: Contract = env::state_read().unwrap();
+    assert_eq!(contract.get_pending_owner(), Some(accounts(2)));
+    assert!(contract.get_sale(format!("{}.0", accounts(1))).unwrap().ft_token_id.is_none());
+    assert!(matches!(VersionedContract::read(), VersionedContract::V3(_)));
+}
+
+#[test]
+#[should_panic(expected = "only contract owner")]
+fn test_migrate_not_owner() {
+    let context = get_context(accounts(1));
+    testing_env!(context.build());
+    write_v1_state(accounts(0));
+
+    Contract::migrate(None);
+}
+
+#[test]
+fn test_pause() {
+    let mut context = get_context(accounts(0));
+    testing_env!(context.build());
+    let mut contract = Contract::new(accounts(0));
+    contract.add_pauser(accounts(1));
+
+    //a pauser pauses listing
+    testing_env!(context.predecessor_account_id(accounts(1)).build());
+    contract.pause(PauseFeature::Listing);
+    assert!(contract.is_paused(PauseFeature::Listing));
+    assert!(!contract.is_paused(PauseFeature::Purchasing));
+
+    //views keep working while paused
+    assert_eq!(contract.get_supply_sales(), U64(0));
+
+    contract.unpause(PauseFeature::Listing);
+    assert!(!contract.is_paused(PauseFeature::Listing));
+}
+
+#[test]
+#[should_panic(expected = "Purchasing is paused")]
+fn test_offer_paused() {
+    let mut context = get_context(accounts(0));
+    testing_env!(context.build());
+    let mut contract = Contract::new(accounts(0));
+    contract.pause(PauseFeature::Purchasing);
+
+    testing_env!(context
+        .attached_deposit(100)
+        .predecessor_account_id(accounts(1))
+        .build());
+    contract.offer(accounts(2), "0".to_string());
+}
+
+#[test]
+fn test_get_sales_page() {
+    let context = get_context(accounts(0));
+    testing_env!(context.build());
+    let mut contract = Contract::new(accounts(0));
+
+    // list three tokens of the same owner and nft contract
+    let nft_contract_id = accounts(1);
+    let mut owner_token_set = UnorderedSet::new(b"o".to_vec());
+    let mut nft_token_set = UnorderedSet::new(b"n".to_vec());
+    for i in 0..3 {
+        let token_id = i.to_string();
+        let contract_and_token_id = format!("{}{}{}", nft_contract_id, ".", token_id);
+        contract.sales.insert(
+            &contract_and_token_id,
+            &Sale {
+                owner_id: accounts(0),
+                approval_id: 0,
+                nft_contract_id: nft_contract_id.to_string(),
+                token_id: token_id.clone(),
+                sale_conditions: U128(100),
+                auction: None,
+                dutch_auction: None,
+                ft_token_id: None,
+            },
+        );
+        owner_token_set.insert(&contract_and_token_id);
+        nft_token_set.insert(&token_id);
+    }
+    contract.by_owner_id.insert(&accounts(0), &owner_token_set);
+    contract.by_nft_contract_id.insert(&nft_contract_id, &nft_token_set);
+
+    // walk through the sales two at a time using the returned cursor
+    let first = contract.get_sales_by_owner_id_page(accounts(0), None, Some(2));
+    assert_eq!(first.sales.len(), 2);
+    assert_eq!(first.next_cursor, Some(U128(2)));
+    let last = contract.get_sales_by_nft_contract_id_page(nft_contract_id.clone(), first.next_cursor, Some(2));
+    assert_eq!(last.sales.len(), 1);
+    assert_eq!(last.sales[0].token_id, "2".to_string());
+    assert_eq!(last.next_cursor, None);
+
+    // without a limit, the default page size is used
+    assert_eq!(contract.get_sales_by_nft_contract_id(nft_contract_id, None, None).len(), 3);
+    assert!(contract.get_sales_by_owner_id(accounts(2), None, None).is_empty());
+}
+
+#[test]
+fn test_contract_source_metadata() {
+    let context = get_context(accounts(0));
+    testing_env!(context.build());
+    let contract = Contract::new(accounts(0));
+
+    let source_metadata = contract.contract_source_metadata();
+    assert_eq!(source_metadata.version, Some(env!("CARGO_PKG_VERSION").to_string()));
+    assert!(source_metadata.link.is_some());
+    let standards: Vec<String> = source_metadata.standards.into_iter().map(|s| s.standard).collect();
+    assert_eq!(standards, vec!["nep297".to_string(), "nep330".to_string()]);
+}
+
+//list token "0" of the nft contract accounts(1) for accounts(0) with the passed in msg, at a block time of 0.
+//the fungible token accounts(3) is whitelisted
+fn list_sale(context: &mut VMContextBuilder, msg: String) -> Contract {
+    testing_env!(context.build());
+    let mut contract = Contract::new(accounts(0));
+    contract.add_ft_token_ids(vec![accounts(3)]);
+    testing_env!(context
+        .attached_deposit(MIN_REQUIRED_STORAGE_YOCTO)
+        .predecessor_account_id(accounts(0))
+        .build());
+    contract.storage_deposit(Some(accounts(0)));
+
+    testing_env!(context
+        .attached_deposit(0)
+        .predecessor_account_id(accounts(1))
+        .signer_account_id(accounts(0))
+        .build());
+    contract.nft_on_approve("0".to_string(), accounts(0), 1, msg);
+    contract
+}
+
+//list token "0" as an auction ending at `ends_at` (ms)
+fn list_auction(context: &mut VMContextBuilder, ends_at: u64) -> Contract {
+    let msg = format!(
+        r#"{{"auction": {{"reserve_price": "100", "min_increment": "10", "ends_at": {}}}}}"#,
+        ends_at
+    );
+    list_sale(context, msg)
+}
+
+#[test]
+fn test_place_bid() {
+    let mut context = get_context(accounts(0));
+    let mut contract = list_auction(&mut context, 3_600_000);
+    let sale = contract.get_sale(format!("{}.0", accounts(1))).unwrap();
+    assert_eq!(sale.sale_conditions, U128(100));
+    assert!(sale.auction.unwrap().highest_bid.is_none());
+
+    testing_env!(context
+        .attached_deposit(100)
+        .predecessor_account_id(accounts(2))
+        .build());
+    contract.place_bid(accounts(1), "0".to_string());
+
+    //outbid by at least the minimum increment. The first bidder gets refunded
+    testing_env!(context
+        .attached_deposit(110)
+        .predecessor_account_id(accounts(3))
+        .build());
+    contract.place_bid(accounts(1), "0".to_string());
+
+    let auction = contract.get_sale(format!("{}.0", accounts(1))).unwrap().auction.unwrap();
+    let highest_bid = auction.highest_bid.unwrap();
+    assert_eq!(highest_bid.bidder_id, accounts(3));
+    assert_eq!(highest_bid.amount, U128(110));
+    assert_eq!(auction.ends_at, 3_600_000);
+    let logs = get_logs();
+    assert!(logs[0].contains(r#""event":"bid_placed""#));
+    assert!(logs[0].contains(r#""amount":"110""#));
+}
+
+#[test]
+fn test_place_bid_extends_auction() {
+    let mut context = get_context(accounts(0));
+    let mut contract = list_auction(&mut context, 3_600_000);
+
+    //a bid one minute before the end pushes the end back to five minutes after the bid
+    testing_env!(context
+        .block_timestamp(3_540_000 * 1_000_000)
+        .attached_deposit(100)
+        .predecessor_account_id(accounts(2))
+        .build());
+    contract.place_bid(accounts(1), "0".to_string());
+
+    let auction = contract.get_sale(format!("{}.0", accounts(1))).unwrap().auction.unwrap();
+    assert_eq!(auction.ends_at, 3_540_000 + 5 * 60 * 1000);
+}
+
+#[test]
+#[should_panic(expected = "Bid must be at least 110")]
+fn test_place_bid_below_increment() {
+    let mut context = get_context(accounts(0));
+    let mut contract = list_auction(&mut context, 3_600_000);
+
+    testing_env!(context
+        .attached_deposit(100)
+        .predecessor_account_id(accounts(2))
+        .build());
+    contract.place_bid(accounts(1), "0".to_string());
+
+    testing_env!(context.attached_deposit(105).build());
+    contract.place_bid(accounts(1), "0".to_string());
+}
+
+#[test]
+#[should_panic(expected = "Sale is an auction, use place_bid")]
+fn test_offer_on_auction() {
+    let mut context = get_context(accounts(0));
+    let mut contract = list_auction(&mut context, 3_600_000);
+
+    testing_env!(context
+        .attached_deposit(1000)
+        .predecessor_account_id(accounts(2))
+        .build());
+    contract.offer(accounts(1), "0".to_string());
+}
+
+#[test]
+#[should_panic(expected = "Auction has not ended yet")]
+fn test_settle_auction_before_end() {
+    let mut context = get_context(accounts(0));
+    let mut contract = list_auction(&mut context, 3_600_000);
+
+    testing_env!(context.predecessor_account_id(accounts(2)).build());
+    contract.settle_auction(accounts(1), "0".to_string());
+}
+
+#[test]
+fn test_settle_auction() {
+    let mut context = get_context(accounts(0));
+    let mut contract = list_auction(&mut context, 3_600_000);
+
+    //an auction without bids is removed when it's settled
+    testing_env!(context
+        .block_timestamp(3_600_000 * 1_000_000)
+        .predecessor_account_id(accounts(2))
+        .build());
+    contract.settle_auction(accounts(1), "0".to_string());
+    assert!(contract.get_sale(format!("{}.0", accounts(1))).is_none());
+    assert_eq!(contract.get_supply_by_owner_id(accounts(0)), U64(0));
+}
+
+//list token "0" as a Dutch auction declining from 1000 to 100 between 1s and 10s
+fn list_dutch_auction(context: &mut VMContextBuilder) -> Contract {
+    let msg = r#"{"dutch_auction": {"start_price": "1000", "floor_price": "100", "starts_at": 1000, "duration": 9000}}"#;
+    list_sale(context, msg.to_string())
+}
+
+#[test]
+fn test_dutch_auction_price() {
+    let mut context = get_context(accounts(0));
+    let contract = list_dutch_auction(&mut context);
+    let sale_id = format!("{}.0", accounts(1));
+    assert_eq!(contract.get_sale(sale_id.clone()).unwrap().sale_conditions, U128(1000));
+
+    //halfway through the decay
+    testing_env!(context.block_timestamp(5_500 * 1_000_000).build());
+    assert_eq!(contract.get_sale(sale_id.clone()).unwrap().sale_conditions, U128(550));
+    assert_eq!(contract.get_sales_by_owner_id(accounts(0), None, None)[0].sale_conditions, U128(550));
+
+    //the price stays at the floor after the decay
+    testing_env!(context.block_timestamp(20_000 * 1_000_000).build());
+    assert_eq!(contract.get_sale(sale_id).unwrap().sale_conditions, U128(100));
+}
+
+#[test]
+fn test_offer_dutch_auction() {
+    let mut context = get_context(accounts(0));
+    let mut contract = list_dutch_auction(&mut context);
+
+    //anything over the current price is refunded, and the sale is bought
+    testing_env!(context
+        .block_timestamp(5_500 * 1_000_000)
+        .attached_deposit(1000)
+        .predecessor_account_id(accounts(2))
+        .build());
+    contract.offer(accounts(1), "0".to_string());
+    assert!(contract.get_sale(format!("{}.0", accounts(1))).is_none());
+}
+
+#[test]
+#[should_panic(expected = "Attached deposit must be greater than or equal to the current price: 550")]
+fn test_offer_dutch_auction_below_price() {
+    let mut context = get_context(accounts(0));
+    let mut contract = list_dutch_auction(&mut context);
+
+    testing_env!(context
+        .block_timestamp(5_500 * 1_000_000)
+        .attached_deposit(500)
+        .predecessor_account_id(accounts(2))
+        .build());
+    contract.offer(accounts(1), "0".to_string());
+}
+
+#[test]
+#[should_panic(expected = "Dutch auction has not started yet")]
+fn test_offer_dutch_auction_before_start() {
+    let mut context = get_context(accounts(0));
+    let mut contract = list_dutch_auction(&mut context);
+
+    testing_env!(context
+        .attached_deposit(1000)
+        .predecessor_account_id(accounts(2))
+        .build());
+    contract.offer(accounts(1), "0".to_string());
+}
+
+#[test]
+fn test_ft_token_ids() {
+    let context = get_context(accounts(0));
+    testing_env!(context.build());
+    let mut contract = Contract::new(accounts(0));
+
+    assert_eq!(contract.add_ft_token_ids(vec![accounts(3), accounts(3)]), vec![true, false]);
+    assert_eq!(contract.supported_ft_token_ids(), vec![accounts(3)]);
+    assert_eq!(contract.remove_ft_token_ids(vec![accounts(3)]), vec![true]);
+    assert!(contract.supported_ft_token_ids().is_empty());
+}
+
+#[test]
+#[should_panic(expected = "Fungible token eugene is not supported")]
+fn test_list_sale_unsupported_ft() {
+    let mut context = get_context(accounts(0));
+    list_sale(&mut context, format!(r#"{{"sale_conditions": "100", "ft_token_id": "{}"}}"#, accounts(4)));
+}
+
+#[test]
+fn test_ft_on_transfer() {
+    let mut context = get_context(accounts(0));
+    let mut contract = list_sale(&mut context, format!(r#"{{"sale_conditions": "100", "ft_token_id": "{}"}}"#, accounts(3)));
+    let sale_id = format!("{}.0", accounts(1));
+    assert_eq!(contract.get_sale(sale_id.clone()).unwrap().ft_token_id, Some(accounts(3)));
+
+    //the FT contract calls the market after transferring the tokens of the buyer. The amount over the price is returned as unused
+    testing_env!(context
+        .predecessor_account_id(accounts(3))
+        .signer_account_id(accounts(2))
+        .build());
+    let msg = format!(r#"{{"nft_contract_id": "{}", "token_id": "0"}}"#, accounts(1));
+    match contract.ft_on_transfer(accounts(2), U128(150), msg) {
+        PromiseOrValue::Value(unused) => assert_eq!(unused, U128(50)),
+        PromiseOrValue::Promise(_) => panic!("Expected the unused amount"),
+    }
+    assert!(contract.get_sale(sale_id).is_none());
+}
+
+#[test]
+#[should_panic(expected = "Sale is not priced in danny")]
+fn test_ft_on_transfer_near_sale() {
+    let mut context = get_context(accounts(0));
+    let mut contract = list_sale(&mut context, r#"{"sale_conditions": "100"}"#.to_string());
+
+    testing_env!(context.predecessor_account_id(accounts(3)).build());
+    let msg = format!(r#"{{"nft_contract_id": "{}", "token_id": "0"}}"#, accounts(1));
+    contract.ft_on_transfer(accounts(2), U128(100), msg);
+}
+
+#[test]
+#[should_panic(expected = "Cannot buy your own sale.")]
+fn test_ft_on_transfer_own_sale() {
+    let mut context = get_context(accounts(0));
+    let mut contract = list_sale(&mut context, format!(r#"{{"sale_conditions": "100", "ft_token_id": "{}"}}"#, accounts(3)));
+
+    testing_env!(context.predecessor_account_id(accounts(3)).build());
+    let msg = format!(r#"{{"nft_contract_id": "{}", "token_id": "0"}}"#, accounts(1));
+    contract.ft_on_transfer(accounts(0), U128(100), msg);
+}
+
+#[test]
+#[should_panic(expected = "Sale is priced in a fungible token, use ft_transfer_call")]
+fn test_offer_ft_sale() {
+    let mut context = get_context(accounts(0));
+    let mut contract = list_sale(&mut context, format!(r#"{{"sale_conditions": "100", "ft_token_id": "{}"}}"#, accounts(3)));
+
+    testing_env!(context
+        .attached_deposit(100)
+        .predecessor_account_id(accounts(2))
+        .build());
+    contract.offer(accounts(1), "0".to_string());
+}
+
+#[test]
+fn test_set_market_fee() {
+    let context = get_context(accounts(0));
+    testing_env!(context.build());
+    let mut contract = Contract::new(accounts(0));
+    assert_eq!(contract.get_market_fee(), 0);
+
+    contract.set_market_fee(250);
+    assert_eq!(contract.get_market_fee(), 250);
+}
+
+#[test]
+fn test_market_fee_for_large_price() {
+    let context = get_context(accounts(0));
+    testing_env!(context.build());
+    let mut contract = Contract::new(accounts(0));
+    contract.set_market_fee(250);
+
+    //FT prices can be close to u128::MAX without the fee overflowing
+    assert_eq!(contract.market_fee_for(10_001), 250);
+    assert_eq!(contract.market_fee_for(u128::MAX), 8_507_059_173_023_461_586_584_365_185_794_205_286);
+}
+
+#[test]
+#[should_panic(expected = "Market fee can't be more than 1000 basis points")]
+fn test_set_market_fee_over_cap() {
+    let context = get_context(accounts(0));
+    testing_env!(context.build());
+    let mut contract = Contract::new(accounts(0));
+    contract.set_market_fee(1001);
+}
+
+#[test]
+fn test_resolve_purchase_collects_market_fee() {
+    let mut context = get_context(accounts(0));
+    testing_env!(context.build());
+    let mut contract = Contract::new(accounts(0));
+    contract.set_market_fee(250);
+    let sale = Sale {
+        owner_id: accounts(0),
+        approval_id: 0,
+        nft_contract_id: accounts(1).to_string(),
+        token_id: "0".to_string(),
+        sale_conditions: U128(1000),
+        auction: None,
+        dutch_auction: None,
+        ft_token_id: None,
+    };
+
+    //the nft contract split the price without the fee of 2.5% into the payout
+    let payout = near_sdk::serde_json::json!({ "payout": { accounts(0).to_string(): "975" } });
+    testing_env!(
+        context.predecessor_account_id(accounts(0)).build(),
+        near_sdk::VMConfig::test(),
+        near_sdk::RuntimeFeesConfig::test(),
+        Default::default(),
+        vec![PromiseResult::Successful(payout.to_string().into_bytes())]
+    );
+    contract.resolve_purchase(accounts(2), U128(1000), U128(25), sale);
+    assert_eq!(contract.get_collected_market_fees(None), U128(25));
+    let logs = get_logs();
+    assert!(logs[0].contains(r#""event":"sale_purchased""#));
+    assert!(logs[0].contains(r#""payout":{"alice":"975"}"#));
+
+    //the owner withdraws the collected fees
+    testing_env!(context.attached_deposit(1).build());
+    assert_eq!(contract.withdraw_market_fees(None), U128(25));
+    assert_eq!(contract.get_collected_market_fees(None), U128(0));
+}
+
+#[test]
+fn test_sale_events() {
+    let mut context = get_context(accounts(0));
+    let mut contract = list_sale(&mut context, r#"{"sale_conditions": "100"}"#.to_string());
+    assert!(get_logs().last().unwrap().contains(r#""event":"sale_created""#));
+
+    testing_env!(context
+        .attached_deposit(1)
+        .predecessor_account_id(accounts(0))
+        .build());
+    contract.update_price(accounts(1), "0".to_string(), U128(200));
+    let logs = get_logs();
+    assert!(logs[0].contains(r#""event":"sale_updated""#));
+    assert!(logs[0].contains(r#""price":"200""#));
+
+    contract.remove_sale(accounts(1), "0".to_string());
+    assert!(get_logs().last().unwrap().contains(r#""event":"sale_removed""#));
+}
+
+#[test]
+fn test_resolve_purchase_failed() {
+    let mut context = get_context(accounts(0));
+    testing_env!(context.build());
+    let mut contract = Contract::new(accounts(0));
+    contract.set_market_fee(250);
+    let sale = Sale {
+        owner_id: accounts(0),
+        approval_id: 0,
+        nft_contract_id: accounts(1).to_string(),
+        token_id: "0".to_string(),
+        sale_conditions: U128(1000),
+        auction: None,
+        dutch_auction: None,
+        ft_token_id: None,
+    };
+
+    //the transfer failed, so the buyer is refunded and no fee is collected
+    testing_env!(
+        context.predecessor_account_id(accounts(0)).build(),
+        near_sdk::VMConfig::test(),
+        near_sdk::RuntimeFeesConfig::test(),
+        Default::default(),
+        vec![PromiseResult::Failed]
+    );
+    assert_eq!(contract.resolve_purchase(accounts(2), U128(1000), U128(25), sale), U128(1000));
+    assert_eq!(contract.get_collected_market_fees(None), U128(0));
+    let logs = get_logs();
+    assert!(logs[0].contains(r#""event":"purchase_failed""#));
+    assert!(logs[0].contains(r#""buyer_id":"charlie""#));
+}
+
+#[test]
+#[should_panic(expected = "Cannot relist an auction with bids")]
+fn test_relist_auction_with_bids() {
+    let mut context = get_context(accounts(0));
+    let mut contract = list_auction(&mut context, 3_600_000);
+
+    testing_env!(context
+        .attached_deposit(100)
+        .predecessor_account_id(accounts(2))
+        .build());
+    contract.place_bid(accounts(1), "0".to_string());
+
+    //the seller approves the market again for the token
+    testing_env!(context
+        .attached_deposit(0)
+        .predecessor_account_id(accounts(1))
+        .signer_account_id(accounts(0))
+        .build());
+    contract.nft_on_approve("0".to_string(), accounts(0), 2, r#"{"sale_conditions": "100"}"#.to_string());
+}
+
+#[test]
+fn test_resolve_ft_transfer_failed() {
+    let mut context = get_context(accounts(0));
+    testing_env!(context.build());
+    let mut contract = Contract::new(accounts(0));
+
+    //the receiver wasn't registered with the FT contract, so the payout is credited to them
+    testing_env!(
+        context.build(),
+        near_sdk::VMConfig::test(),
+        near_sdk::RuntimeFeesConfig::test(),
+        Default::default(),
+        vec![PromiseResult::Failed]
+    );
+    contract.resolve_ft_transfer(accounts(3), accounts(2), U128(100));
+    contract.resolve_ft_transfer(accounts(3), accounts(2), U128(50));
+    assert_eq!(contract.get_claimable_ft(accounts(2), accounts(3)), U128(150));
+
+    //a successful transfer credits nothing
+    testing_env!(
+        context.build(),
+        near_sdk::VMConfig::test(),
+        near_sdk::RuntimeFeesConfig::test(),
+        Default::default(),
+        vec![PromiseResult::Successful(vec![])]
+    );
+    contract.resolve_ft_transfer(accounts(3), accounts(4), U128(100));
+    assert_eq!(contract.get_claimable_ft(accounts(4), accounts(3)), U128(0));
+
+    //the receiver claims the tokens once registered
+    testing_env!(context
+        .attached_deposit(1)
+        .predecessor_account_id(accounts(2))
+        .build());
+    assert_eq!(contract.claim_ft(accounts(3)), U128(150));
+    assert_eq!(contract.get_claimable_ft(accounts(2), accounts(3)), U128(0));
+}
+
+#[test]
+fn test_resolve_withdraw_market_fees_failed() {
+    let context = get_context(accounts(0));
+    testing_env!(context.build());
+    let mut contract = Contract::new(accounts(0));
+
+    //the owner wasn't registered with the FT contract, so the fees are collected again
+    testing_env!(
+        context.build(),
+        near_sdk::VMConfig::test(),
+        near_sdk::RuntimeFeesConfig::test(),
+        Default::default(),
+        vec![PromiseResult::Failed]
+    );
+    assert_eq!(contract.resolve_withdraw_market_fees(accounts(3), U128(25)), U128(0));
+    assert_eq!(contract.get_collected_market_fees(Some(accounts(3))), U128(25));
+}
+
+#[test]
+fn test_relist_sale_event() {
+    let mut context = get_context(accounts(0));
+    let mut contract = list_auction(&mut context, 3_600_000);
+
+    //an auction without bids can be relisted, which updates the sale
+    contract.nft_on_approve("0".to_string(), accounts(0), 2, r#"{"sale_conditions": "300"}"#.to_string());
+    assert!(get_logs().last().unwrap().contains(r#""event":"sale_updated""#));
+    let sale = contract.get_sale(format!("{}.0", accounts(1))).unwrap();
+    assert_eq!(sale.sale_conditions, U128(300));
+    assert!(sale.auction.is_none());
+    assert_eq!(contract.get_supply_by_owner_id(accounts(0)), U64(1));
+}
@@ -2,12 +2,19 @@
 //이를 통해 사람들은 자신을 대신하여 NFT를 전송하도록 다른 계정을 승인할 수 있습니다.
 //이 파일에는 표준의 승인 관리 확장을 준수하는 논리가 포함되어 있습니다. 다음은 메소드와 해당 기능에 대한 분석입니다.
 use crate::*;
+use near_sdk::json_types::U64;
 use near_sdk::{ext_contract};
 
 pub trait NonFungibleTokenCore {
     //approve an account ID to transfer a token on your behalf
-    //당신을 대신하여 토큰을 전송할 계정 ID 승인
-    fn nft_approve(&mut self, token_id: TokenId, account_id: AccountId, msg: Option<String>);
+    //당신을 대신하여 토큰을 전송할 계정 ID 승인. expires_at(block timestamp)이 지나면 승인은 없는 것으로 취급됩니다.
+    fn nft_approve(
+        &mut self,
+        token_id: TokenId,
+        account_id: AccountId,
+        msg: Option<String>,
+        expires_at: Option<U64>,
+    );
 
     //check if the passed in account has access to approve the token ID
     //전달된 계정이 토큰 ID를 승인할 수 있는지 확인
@@ -47,18 +54,25 @@ impl NonFungibleTokenCore for Contract {
     //당신을 대신하여 토큰을 승인할 특정 계정 ID 허용
     /**
      * near call YOUR_CONTRACT_ID nft_approve 
-     * '{"token_id": "YOUR_TOKEN_ID", "account_id": "ACCOUNT_TO_APPROVE", "msg": "OPTIONAL_MESSAGE"}' 
+     * '{"token_id": "YOUR_TOKEN_ID", "account_id": "ACCOUNT_TO_APPROVE", "msg": "OPTIONAL_MESSAGE", "expires_at": "OPTIONAL_EXPIRY"}' 
      * --accountId YOUR_ACCOUNT_ID --amount ATTACHED_AMOUNT
      * 
         YOUR_CONTRACT_ID는 스마트 컨트랙트가 배포된 계정의 ID입니다.
         YOUR_TOKEN_ID는 승인하려는 토큰의 ID입니다.
         ACCOUNT_TO_APPROVE는 승인하려는 계정의 ID입니다.
         OPTIONAL_MESSAGE는 선택적 메시지입니다 (필요한 경우에만).
+        OPTIONAL_EXPIRY는 승인이 만료되는 block timestamp(나노초)입니다 (필요한 경우에만).
         YOUR_ACCOUNT_ID는 함수를 호출하는 계정의 ID입니다. 이 계정은 토큰의 소유자여야 합니다.
         ATTACHED_AMOUNT는 첨부할 NEAR 토큰의 양입니다. 이는 특히 저장 비용을 위해 필요할 수 있습니다.
      */
     #[payable]
-    fn nft_approve(&mut self, token_id: TokenId, account_id: AccountId, msg: Option<String>) {
+    fn nft_approve(
+        &mut self,
+        token_id: TokenId,
+        account_id: AccountId,
+        msg: Option<String>,
+        expires_at: Option<U64>,
+    ) {
         /*
             assert at least one yocto for security reasons - this will cause a redirect to the NEAR wallet.
             The user needs to attach enough to pay for storage on the contract
@@ -79,20 +93,24 @@ impl NonFungibleTokenCore for Contract {
             "Predecessor must be the token owner."
         );
 
+        //soulbound tokens can't be approved since nobody could ever transfer them
+        //soulbound 토큰은 아무도 전송할 수 없으므로 승인할 수 없습니다
+        self.assert_not_soulbound(&token_id);
+
         //get the next approval ID if we need a new approval
         //새로운 승인이 필요하면 다음 승인 ID 가져오기
         let approval_id: u64 = token.next_approval_id;
 
         //check if the account has been approved already for this token
          //해당 계정이 이 토큰에 대해 이미 승인되었는지 확인
-        let is_new_approval = token
+        let replaced_approval_id = token
             .approved_account_ids
             //insert returns none if the key was not present.  
             //키가 없으면 insert는 none을 반환합니다.
-            .insert(account_id.clone(), approval_id)
-            //if the key was not present, .is_none() will return true so it is a new approval.
-            //키가 없으면, .is_none()은 true를 반환하므로 새로운 승인입니다.
-            .is_none();
+            .insert(account_id.clone(), approval_id);
+        //if the key was not present, it is a new approval.
+        //키가 없으면 새로운 승인입니다.
+        let is_new_approval = replaced_approval_id.is_none();
 
         //if it was a new approval, we need to calculate how much storage is being used to add the account.
         //새로운 승인이었으면 계정을 추가하여 사용되는 저장 공간을 계산합니다.
@@ -111,9 +129,37 @@ impl NonFungibleTokenCore for Contract {
         //tokens_by_id 컬렉션에 토큰 다시 삽입
         self.tokens_by_id.insert(&token_id, &token);
 
-        //refund any excess storage attached by the user. If the user didn't attach enough, panic. 
-        //사용자가 첨부한 불필요한 저장을 환불합니다. 사용자가 충분히 첨부하지 않았다면 panic. 
-        refund_deposit(storage_used);
+        //if an expiry was passed in, store it for the new approval ID and charge the storage it uses.
+        //only the expiry of the replaced approval ID (if any) is dropped. Other expiries are kept even if their approval
+        //is gone, since a receiver approving during nft_on_transfer must not drop the expiries that nft_resolve_transfer
+        //restores along with the previous approvals. They're pruned once the transfer resolves.
+        //만료 시각이 전달되었으면 새 승인 ID에 대해 저장하고 사용된 저장 공간을 청구합니다.
+        //대체된 승인 ID의 만료 시각만(있다면) 제거됩니다. 다른 만료 시각은 승인이 없어도 유지되는데, nft_on_transfer 중에
+        //수신자가 승인하더라도 nft_resolve_transfer가 이전 승인과 함께 복원하는 만료 시각이 제거되면 안 되기 때문입니다.
+        //이들은 전송이 해결되면 정리됩니다.
+        let initial_storage_usage = env::storage_usage();
+        let mut expiries = self.approval_expires_at.get(&token_id).unwrap_or_default();
+        if let Some(replaced_approval_id) = replaced_approval_id {
+            expiries.remove(&replaced_approval_id);
+        }
+        if let Some(expires_at) = expires_at {
+            assert!(
+                expires_at.0 > env::block_timestamp(),
+                "Approval expiry must be in the future"
+            );
+            expiries.insert(approval_id, expires_at.0);
+        }
+        if expiries.is_empty() {
+            self.approval_expires_at.remove(&token_id);
+        } else {
+            self.approval_expires_at.insert(&token_id, &expiries);
+        }
+        let expiry_storage_used = env::storage_usage().saturating_sub(initial_storage_usage);
+
+        //refund any excess storage attached by the user. If the user didn't attach enough, draw the rest from their
+        //storage balance and panic if that isn't enough either.
+        //사용자가 첨부한 불필요한 저장을 환불합니다. 충분히 첨부하지 않았다면 나머지를 저장소 잔액에서 차감하고, 그래도 부족하면 panic.
+        self.charge_storage(storage_used + expiry_storage_used);
 
         //if some message was passed into the function, we initiate a cross contract call on the
         //account we're giving access to. 
@@ -160,6 +206,10 @@ impl NonFungibleTokenCore for Contract {
 
         //계정 ID에 대한 승인 ID가 있으면
         if let Some(approval) = approval {
+            //만료된 승인은 없는 것으로 취급합니다
+            if self.is_approval_expired(&token_id, *approval) {
+                return false;
+            }
             //함수에 특정 approval_id가 전달되면
 			if let Some(approval_id) = approval_id {
                 //전달된 승인 ID가 계정의 실제 승인 ID와 일치하는지 반환
@@ -197,16 +247,15 @@ impl NonFungibleTokenCore for Contract {
         assert_eq!(&predecessor_account_id, &token.owner_id);
 
         //계정 ID가 토큰의 승인에 있으면 제거하고 if 문 로직 실행
-        if token
-            .approved_account_ids
-            .remove(&account_id)
-            .is_some()
-        {
+        if let Some(approval_id) = token.approved_account_ids.remove(&account_id) {
             //approved_account_id를 제거함으로써 해제된 자금을 함수의 호출자에게 환불
-            refund_approved_account_ids_iter(predecessor_account_id, [account_id].iter());
+            refund_approved_account_ids_iter(predecessor_account_id.clone(), [account_id].iter());
 
             //승인 목록에서 account_id를 제거하여 tokens_by_id 컬렉션에 토큰 다시 삽입
             self.tokens_by_id.insert(&token_id, &token);
+
+            //제거된 승인의 만료 시각도 제거하고 환불
+            self.internal_remove_approval_expiries(&token_id, predecessor_account_id, |id| *id == approval_id);
         }
     }
 
@@ -232,11 +281,93 @@ impl NonFungibleTokenCore for Contract {
         //토큰의 승인된 계정 ID가 비어 있지 않으면 승인 취소
         if !token.approved_account_ids.is_empty() {
             //승인된 계정 ID를 함수 호출자에게 환불
-            refund_approved_account_ids(predecessor_account_id, &token.approved_account_ids);
+            refund_approved_account_ids(predecessor_account_id.clone(), &token.approved_account_ids);
             //승인된 계정 ID 지우기
-            token.approved_account_ids.clear();
+            let approval_ids: Vec<u64> = token.approved_account_ids.drain().map(|(_, approval_id)| approval_id).collect();
             //승인된 계정 ID가 지워진 상태로 tokens_by_id 컬렉션에 토큰 다시 삽입
             self.tokens_by_id.insert(&token_id, &token);
+            //모든 승인의 만료 시각도 제거하고 환불
+            self.internal_remove_approval_expiries(&token_id, predecessor_account_id, |id| approval_ids.contains(id));
+        }
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    //remove every expired approval of a token. Anyone can call this, and the freed storage is refunded to the token owner
+    //토큰의 만료된 승인을 모두 제거합니다. 누구나 호출할 수 있으며 해제된 저장 공간은 토큰 소유자에게 환불됩니다
+    //near call CONTRACT_ID nft_prune_expired_approvals '{"token_id": "YOUR_TOKEN_ID"}' --accountId ANY_ACCOUNT_ID
+    pub fn nft_prune_expired_approvals(&mut self, token_id: TokenId) {
+        //전달된 토큰 ID에서 토큰 객체 가져오기
+        let mut token = self.tokens_by_id.get(&token_id).expect("No token");
+
+        //만료된 승인의 계정 ID와 승인 ID 모으기
+        let (expired_account_ids, expired_approval_ids): (Vec<AccountId>, Vec<u64>) = token
+            .approved_account_ids
+            .iter()
+            .filter(|(_, approval_id)| self.is_approval_expired(&token_id, **approval_id))
+            .map(|(account_id, approval_id)| (account_id.clone(), *approval_id))
+            .unzip();
+        if expired_account_ids.is_empty() {
+            return;
+        }
+
+        //만료된 승인을 제거하고 토큰을 다시 삽입
+        for account_id in expired_account_ids.iter() {
+            token.approved_account_ids.remove(account_id);
         }
+        self.tokens_by_id.insert(&token_id, &token);
+
+        //만료된 승인과 만료 시각이 사용하던 저장 공간을 토큰 소유자에게 환불
+        refund_approved_account_ids_iter(token.owner_id.clone(), expired_account_ids.iter());
+        self.internal_remove_approval_expiries(&token_id, token.owner_id, |id| expired_approval_ids.contains(id));
     }
-}
\ No newline at end of file
+}
+
+impl Contract {
+    //check if the approval with the passed in ID has an expiry that has been reached
+    //전달된 ID의 승인이 만료 시각에 도달했는지 확인
+    pub(crate) fn is_approval_expired(&self, token_id: &TokenId, approval_id: u64) -> bool {
+        self.approval_expires_at
+            .get(token_id)
+            .and_then(|expiries| expiries.get(&approval_id).copied())
+            .map(|expires_at| expires_at <= env::block_timestamp())
+            .unwrap_or(false)
+    }
+
+    //remove the expiries of the approval IDs the passed in predicate matches and refund the freed storage. Callers pick the
+    //IDs, so that while a transfer is pending the expiries nft_resolve_transfer may still restore aren't dropped
+    //전달된 조건에 맞는 승인 ID의 만료 시각을 제거하고 해제된 저장 공간을 환불합니다. 제거할 ID는 호출자가 정하므로,
+    //전송이 진행 중인 동안 nft_resolve_transfer가 복원할 수 있는 만료 시각이 제거되지 않습니다
+    pub(crate) fn internal_remove_approval_expiries(
+        &mut self,
+        token_id: &TokenId,
+        refund_id: AccountId,
+        is_removed: impl Fn(&u64) -> bool,
+    ) {
+        let mut expiries = if let Some(expiries) = self.approval_expires_at.get(token_id) {
+            expiries
+        } else {
+            return;
+        };
+
+        let initial_len = expiries.len();
+        expiries.retain(|approval_id, _| !is_removed(approval_id));
+        if expiries.len() == initial_len {
+            return;
+        }
+
+        let initial_storage_usage = env::storage_usage();
+        if expiries.is_empty() {
+            self.approval_expires_at.remove(token_id);
+        } else {
+            self.approval_expires_at.insert(token_id, &expiries);
+        }
+
+        let freed_storage_in_bytes = initial_storage_usage.saturating_sub(env::storage_usage());
+        if freed_storage_in_bytes > 0 {
+            Promise::new(refund_id)
+                .transfer(Balance::from(freed_storage_in_bytes) * env::storage_byte_cost());
+        }
+    }
+}
@@ -0,0 +1,167 @@
+//토큰 소각 논리가 포함되어 있습니다.
+use crate::*;
+
+#[near_bindgen]
+impl Contract {
+    //burns the token, removing it from every collection and refunding the freed storage to the token owner.
+    //the caller must be the owner of the token or an approved account.
+    // 토큰을 소각하여 모든 컬렉션에서 제거하고 해제된 저장소를 토큰 소유자에게 환불합니다.
+    // 호출자는 토큰의 소유자이거나 승인된 계정이어야 합니다.
+    /**
+     * near call CONTRACT_ID nft_burn '{"token_id": "YOUR_TOKEN_ID", "approval_id": OPTIONAL_APPROVAL_ID, "memo": "OPTIONAL_MEMO"}'
+     * --accountId YOUR_ACCOUNT_ID --depositYocto 1
+     */
+    #[payable]
+    pub fn nft_burn(&mut self, token_id: TokenId, approval_id: Option<u64>, memo: Option<String>) {
+        //assert that the user attached exactly 1 yoctoNEAR for security reasons
+        // 보안을 위해 사용자가 정확히 1 yoctoNEAR를 첨부했는지 확인합니다.
+        assert_one_yocto();
+
+        //get the sender and the token object
+        // 발신자와 토큰 객체를 가져옵니다.
+        let sender_id = env::predecessor_account_id();
+        let token = self.tokens_by_id.get(&token_id).expect("No token");
+
+        //make sure the sender is the owner or an approved account for the token
+        // 발신자가 토큰의 소유자이거나 승인된 계정인지 확인합니다.
+        self.internal_assert_authorized(&sender_id, &token_id, &token, approval_id);
+
+        //the token owner gets the freed storage back
+        // 해제된 저장소는 토큰 소유자에게 환불됩니다.
+        let refund_id = token.owner_id.clone();
+        self.internal_burn(&sender_id, &token_id, token, refund_id, memo);
+    }
+
+    //revokes a soulbound token, burning it on behalf of its owner. Only the account that minted the token or the
+    //contract owner can revoke it, and the freed storage is refunded to them since the issuer paid for it.
+    // soulbound 토큰을 취소하여 소유자를 대신해 소각합니다. 토큰을 발행한 계정이나 계약 소유자만 취소할 수 있으며,
+    // 발행자가 저장소 비용을 지불했으므로 해제된 저장소는 발행자에게 환불됩니다.
+    /**
+     * near call CONTRACT_ID nft_revoke_soulbound '{"token_id": "YOUR_TOKEN_ID", "memo": "OPTIONAL_MEMO"}'
+     * --accountId ISSUER_ACCOUNT_ID --depositYocto 1
+     */
+    #[payable]
+    pub fn nft_revoke_soulbound(&mut self, token_id: TokenId, memo: Option<String>) {
+        //assert that the user attached exactly 1 yoctoNEAR for security reasons
+        // 보안을 위해 사용자가 정확히 1 yoctoNEAR를 첨부했는지 확인합니다.
+        assert_one_yocto();
+
+        let sender_id = env::predecessor_account_id();
+        let token = self.tokens_by_id.get(&token_id).expect("No token");
+        assert!(
+            self.soulbound_tokens.contains(&token_id),
+            "Only soulbound tokens can be revoked"
+        );
+
+        //make sure the sender issued the token or is the contract owner
+        // 발신자가 토큰의 발행자이거나 계약 소유자인지 확인합니다.
+        assert!(
+            sender_id == self.owner_id
+                || self.minter_by_token_id.get(&token_id).as_ref() == Some(&sender_id),
+            "Only the issuer can revoke the token"
+        );
+
+        self.internal_burn(&sender_id, &token_id, token, sender_id.clone(), memo);
+    }
+
+    //check if the token is soulbound (can't be transferred or approved)
+    // 토큰이 soulbound(전송하거나 승인할 수 없음)인지 확인합니다.
+    pub fn nft_is_soulbound(&self, token_id: TokenId) -> bool {
+        self.soulbound_tokens.contains(&token_id)
+    }
+}
+
+impl Contract {
+    //removes the token from every collection, logs the burn and refunds the freed storage to the passed in account,
+    //or to the sponsor pool if the pool paid for the token. The storage used by approvals is always refunded to the
+    //token owner, who paid for it.
+    // 모든 컬렉션에서 토큰을 제거하고 소각을 로그에 기록한 뒤 해제된 저장소를 전달된 계정에 환불하며,
+    // 후원 풀이 토큰의 비용을 지불했다면 풀에 환불합니다. 승인에 사용된 저장소는 항상 비용을 지불한 토큰 소유자에게 환불됩니다.
+    pub(crate) fn internal_burn(
+        &mut self,
+        sender_id: &AccountId,
+        token_id: &TokenId,
+        token: Token,
+        refund_id: AccountId,
+        memo: Option<String>,
+    ) {
+        //measure the initial storage being used on the contract
+        // 계약에서 사용하는 초기 저장소를 측정합니다.
+        let initial_storage_usage = env::storage_usage();
+
+        //remove the token from every collection
+        // 모든 컬렉션에서 토큰을 제거합니다.
+        self.internal_remove_token_from_owner(&token.owner_id, token_id);
+        self.tokens_by_id.remove(token_id);
+        self.token_metadata_by_id.remove(token_id);
+        self.minter_by_token_id.remove(token_id);
+        self.metadata_updater_by_token_id.remove(token_id);
+        self.soulbound_tokens.remove(token_id);
+        self.approval_expires_at.remove(token_id);
+        let sponsored = self.sponsored_tokens.remove(token_id);
+        if let Some(token_type) = self.token_type_by_id.remove(token_id) {
+            self.internal_remove_token_from_type(&token_type, token_id);
+        }
+
+        //if the sender isn't the owner of the token, we set the authorized ID equal to the sender.
+        // 발신자가 토큰의 소유자가 아닌 경우 authorized_id를 발신자로 설정합니다.
+        let authorized_id = if sender_id != &token.owner_id {
+            Some(sender_id.to_string())
+        } else {
+            None
+        };
+
+        // Construct the burn log as per the events standard.
+        // 이벤트 표준에 따라 소각 로그를 구성합니다.
+        let nft_burn_log: EventLog = EventLog {
+            // Standard name ("nep171").
+            standard: NFT_STANDARD_NAME.to_string(),
+            // Version of the standard ("nft-1.0.0").
+            version: NFT_METADATA_SPEC.to_string(),
+            // The data related with the event stored in a vector.
+            event: EventLogVariant::NftBurn(vec![NftBurnLog {
+                // The owner of the burned token.
+                owner_id: token.owner_id.to_string(),
+                // The optional authorized account ID to burn the token on behalf of the owner.
+                authorized_id,
+                // A vector containing the token IDs as strings.
+                token_ids: vec![token_id.to_string()],
+                // An optional memo to include.
+                memo,
+            }]),
+        };
+
+        // Log the serialized json.
+        env::log_str(&nft_burn_log.to_string());
+
+        //the freed storage includes the approved account IDs, which are refunded separately below
+        // 해제된 저장소에는 승인된 계정 ID가 포함되어 있으며, 이는 아래에서 따로 환불됩니다.
+        let approvals_storage: u64 = token
+            .approved_account_ids
+            .keys()
+            .map(bytes_for_approved_account_id)
+            .sum();
+        let freed_storage_in_bytes = initial_storage_usage
+            .saturating_sub(env::storage_usage())
+            .saturating_sub(approvals_storage);
+
+        //the migration paid for the unparsed timestamps, so they're removed after measuring and their storage stays with the contract
+        // 변환되지 않은 시각은 상태 이전이 비용을 지불했으므로 측정 후에 제거하며, 해제된 저장소는 계약에 남습니다.
+        self.unparsed_timestamps_by_id.remove(token_id);
+
+        //refund the storage released by the token and its metadata. A sponsored token goes back to the pool that paid for it
+        // 토큰과 메타데이터가 해제한 저장소를 환불합니다. 후원받은 토큰은 비용을 지불한 풀로 돌아갑니다.
+        let refund = Balance::from(freed_storage_in_bytes) * env::storage_byte_cost();
+        if sponsored {
+            self.sponsor_pool.balance += refund;
+        } else if refund > 0 {
+            Promise::new(refund_id).transfer(refund);
+        }
+
+        //we refund the owner for releasing the storage used up by the approved account IDs
+        // 승인된 계정 ID에 사용된 저장소를 해제함으로써 소유자에게 환불을 합니다.
+        if !token.approved_account_ids.is_empty() {
+            refund_approved_account_ids(token.owner_id, &token.approved_account_ids);
+        }
+    }
+}
@@ -1,5 +1,44 @@
 //NFT 토큰과 그 소유자를 나열하는 방법이 포함되어 있습니다.
+//페이지는 기본 벡터의 인덱스로 직접 접근하므로 from_index와 상관없이 비용이 limit에 비례합니다.
 use crate::*;
+use near_sdk::collections::Vector;
+
+/// limit을 지정하지 않았을 때 한 페이지에서 반환하는 항목 수입니다.
+pub const DEFAULT_PAGE_LIMIT: u64 = 50;
+/// 한 페이지에서 반환할 수 있는 최대 항목 수입니다. 뷰 호출의 GAS 한도 안에서 처리할 수 있도록 제한합니다.
+pub const MAX_PAGE_LIMIT: u64 = 100;
+
+// 토큰 한 페이지와 다음 페이지를 가져올 커서입니다.
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct JsonTokenPage {
+    // 이 페이지의 토큰
+    pub tokens: Vec<JsonToken>,
+    // 다음 페이지의 from_index로 전달할 값입니다. 마지막 페이지라면 None입니다.
+    pub next_cursor: Option<U128>,
+}
+
+//returns the items of one page of the vector, along with the index the next page starts from if there is one
+//벡터의 한 페이지에 해당하는 항목과, 다음 페이지가 있다면 그 시작 인덱스를 반환합니다.
+pub(crate) fn paginate<T: BorshSerialize + BorshDeserialize>(
+    items: &Vector<T>,
+    from_index: Option<U128>,
+    limit: Option<u64>,
+) -> (Vec<T>, Option<U128>) {
+    //where to start pagination - if we have a from_index, we'll use that - otherwise start from 0 index
+    //페이징을 시작할 위치 - from_index가 있으면 그것을 사용하고, 그렇지 않으면 0 인덱스에서 시작합니다.
+    let start = u128::from(from_index.unwrap_or(U128(0))).min(items.len() as u128) as u64;
+    //take "limit" elements, at most MAX_PAGE_LIMIT. If we didn't specify a limit, use 50
+    //"limit"개의 요소를 가져오되 MAX_PAGE_LIMIT을 넘지 않습니다. limit을 지정하지 않았다면 50을 사용합니다.
+    let limit = limit.unwrap_or(DEFAULT_PAGE_LIMIT).min(MAX_PAGE_LIMIT);
+    let end = (start + limit).min(items.len());
+
+    //index directly into the vector instead of iterating from index zero
+    //인덱스 0부터 순회하지 않고 벡터의 인덱스로 직접 접근합니다.
+    let page = (start..end).map(|index| items.get(index).unwrap()).collect();
+    let next_cursor = if end < items.len() { Some(U128(end as u128)) } else { None };
+    (page, next_cursor)
+}
 
 #[near_bindgen]
 impl Contract {
@@ -16,25 +55,16 @@ impl Contract {
     //소유자와 상관없이 페이징을 사용하여 계약의 nft 토큰을 쿼리합니다.
     //near view $ID nft_tokens '{}'
     pub fn nft_tokens(&self, from_index: Option<U128>, limit: Option<u64>) -> Vec<JsonToken> {
-        //where to start pagination - if we have a from_index, we'll use that - otherwise start from 0 index
-        //페이징을 시작할 위치 - from_index가 있으면 그것을 사용하고, 그렇지 않으면 0 인덱스에서 시작합니다.
-        let start = u128::from(from_index.unwrap_or(U128(0)));
-
-        //iterate through each token using an iterator
-        //반복자를 사용하여 각 토큰을 순회합니다.
-        self.token_metadata_by_id.keys()
-            //skip to the index we specified in the start variable
-            //start 변수에 지정한 인덱스로 건너뜁니다.
-            .skip(start as usize) 
-            //take the first "limit" elements in the vector. If we didn't specify a limit, use 50
-            //벡터에서 첫 번째 "limit" 요소를 가져옵니다. limit을 지정하지 않았다면 50을 사용합니다.
-            .take(limit.unwrap_or(50) as usize) 
-            //we'll map the token IDs which are strings into Json Tokens
-            //문자열인 토큰 ID를 Json 토큰으로 매핑합니다.
-            .map(|token_id| self.nft_token(token_id.clone()).unwrap())
-            //since we turned the keys into an iterator, we need to turn it back into a vector to return
-            //키를 반복자로 변환했으므로 반환하기 위해 벡터로 다시 변환해야 합니다.
-            .collect()
+        self.nft_tokens_page(from_index, limit).tokens
+    }
+
+    //Query for nft tokens like nft_tokens, along with the cursor of the next page
+    //nft_tokens처럼 토큰을 쿼리하고 다음 페이지의 커서도 함께 반환합니다.
+    //near view $ID nft_tokens_page '{"from_index": "0", "limit": 50}'
+    pub fn nft_tokens_page(&self, from_index: Option<U128>, limit: Option<u64>) -> JsonTokenPage {
+        let (token_ids, next_cursor) =
+            paginate(self.token_metadata_by_id.keys_as_vector(), from_index, limit);
+        self.json_token_page(token_ids, next_cursor)
     }
 
     //get the total supply of NFTs for a given owner
@@ -77,37 +107,106 @@ impl Contract {
         from_index: Option<U128>,
         limit: Option<u64>,
     ) -> Vec<JsonToken> {
-        //get the set of tokens for the passed in owner
-        //전달된 소유자에 대한 토큰 세트를 가져옵니다.
-        let tokens_for_owner_set = self.tokens_per_owner.get(&account_id);
-        //if there is some set of tokens, we'll set the tokens variable equal to that set
-        //토큰의 일부 세트가 있다면 tokens 변수를 해당 세트와 동일하게 설정합니다.
-        let tokens = if let Some(tokens_for_owner_set) = tokens_for_owner_set {
-            tokens_for_owner_set
-        } else {
-            //if there is no set of tokens, we'll simply return an empty vector. 
-            //토큰 세트가 없다면 그냥 빈 벡터를 반환합니다.
-            return vec![];
+        self.nft_tokens_for_owner_page(account_id, from_index, limit).tokens
+    }
+
+    //Query for the tokens of an owner like nft_tokens_for_owner, along with the cursor of the next page
+    //nft_tokens_for_owner처럼 소유자의 토큰을 쿼리하고 다음 페이지의 커서도 함께 반환합니다.
+    //near view $ID nft_tokens_for_owner_page '{"account_id": "alice.near", "from_index": "0", "limit": 50}'
+    pub fn nft_tokens_for_owner_page(
+        &self,
+        account_id: AccountId,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> JsonTokenPage {
+        //get the set of tokens for the passed in owner. If there is none, we return an empty page
+        //전달된 소유자에 대한 토큰 세트를 가져옵니다. 없다면 빈 페이지를 반환합니다.
+        let (token_ids, next_cursor) = match self.tokens_per_owner.get(&account_id) {
+            Some(tokens_for_owner_set) => {
+                paginate(tokens_for_owner_set.as_vector(), from_index, limit)
+            }
+            None => (vec![], None),
         };
+        self.json_token_page(token_ids, next_cursor)
+    }
+
+    //get the total supply of NFTs of a given type
+    //주어진 유형의 NFT 전체 공급량을 가져옵니다.
+    //near view $ID nft_supply_for_type '{"token_type":"badge"}'
+    pub fn nft_supply_for_type(&self, token_type: TokenType) -> U128 {
+        self.tokens_per_type
+            .get(&token_type)
+            .map(|tokens_for_type_set| U128(tokens_for_type_set.len() as u128))
+            .unwrap_or(U128(0))
+    }
 
-        //where to start pagination - if we have a from_index, we'll use that - otherwise start from 0 index
-        //페이징을 시작할 위치 - from_index가 있으면 그것을 사용하고, 그렇지 않으면 0 인덱스에서 시작합니다.
-        let start = u128::from(from_index.unwrap_or(U128(0)));
-
-        //iterate through the keys vector
-        //키 벡터를 순회합니다.
-        tokens.iter()
-            //skip to the index we specified in the start variable
-            //start 변수에 지정한 인덱스로 건너뜁니다.
-            .skip(start as usize) 
-            //take the first "limit" elements in the vector. If we didn't specify a limit, use 50
-            //벡터에서 첫 번째 "limit" 요소를 가져옵니다. limit을 지정하지 않았다면 50을 사용합니다.
-            .take(limit.unwrap_or(50) as usize) 
-            //we'll map the token IDs which are strings into Json Tokens
-            //문자열인 토큰 ID를 Json 토큰으로 매핑합니다.
-            .map(|token_id| self.nft_token(token_id.clone()).unwrap())
-            //since we turned the keys into an iterator, we need to turn it back into a vector to return
-            //키를 반복자로 변환했으므로 반환하기 위해 벡터로 다시 변환해야 합니다.
-            .collect()
+    //Query for all the tokens of a type
+    //유형의 모든 토큰을 쿼리합니다.
+    //near view $ID nft_tokens_for_type '{"token_type": "badge", "from_index": "0", "limit": 10}'
+    pub fn nft_tokens_for_type(
+        &self,
+        token_type: TokenType,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> Vec<JsonToken> {
+        self.nft_tokens_for_type_page(token_type, from_index, limit).tokens
+    }
+
+    //Query for the tokens of a type like nft_tokens_for_type, along with the cursor of the next page
+    //nft_tokens_for_type처럼 유형의 토큰을 쿼리하고 다음 페이지의 커서도 함께 반환합니다.
+    pub fn nft_tokens_for_type_page(
+        &self,
+        token_type: TokenType,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> JsonTokenPage {
+        //get the set of tokens for the passed in type. If there is none, we return an empty page
+        //전달된 유형에 대한 토큰 세트를 가져옵니다. 없다면 빈 페이지를 반환합니다.
+        let (token_ids, next_cursor) = match self.tokens_per_type.get(&token_type) {
+            Some(tokens_for_type_set) => {
+                paginate(tokens_for_type_set.as_vector(), from_index, limit)
+            }
+            None => (vec![], None),
+        };
+        self.json_token_page(token_ids, next_cursor)
+    }
+
+    //Query for the tokens that expire before a time (Unix epoch milliseconds), e.g. to clean up event tickets.
+    //each call scans one page of all tokens, so a page can hold fewer tokens than the limit. Keep following next_cursor until it's None
+    //주어진 시각(Unix epoch 밀리초) 전에 만료되는 토큰을 쿼리합니다. 예를 들어 행사 티켓을 정리할 때 사용할 수 있습니다.
+    //호출마다 전체 토큰의 한 페이지를 살펴보므로 페이지에 limit보다 적은 토큰이 있을 수 있습니다. next_cursor가 None이 될 때까지 계속 조회합니다.
+    //near view $ID nft_tokens_expiring_before '{"ts": 1700000000000, "from_index": "0", "limit": 50}'
+    pub fn nft_tokens_expiring_before(
+        &self,
+        ts: u64,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> JsonTokenPage {
+        let (token_ids, next_cursor) =
+            paginate(self.token_metadata_by_id.keys_as_vector(), from_index, limit);
+        let token_ids = token_ids
+            .into_iter()
+            .filter(|token_id| {
+                self.token_metadata_by_id
+                    .get(token_id)
+                    .and_then(|metadata| metadata.expires_at)
+                    .is_some_and(|expires_at| expires_at.0 < ts)
+            })
+            .collect();
+        self.json_token_page(token_ids, next_cursor)
+    }
+}
+
+impl Contract {
+    //map a page of token IDs into Json Tokens
+    //토큰 ID 페이지를 Json 토큰으로 매핑합니다.
+    fn json_token_page(&self, token_ids: Vec<TokenId>, next_cursor: Option<U128>) -> JsonTokenPage {
+        JsonTokenPage {
+            tokens: token_ids
+                .into_iter()
+                .map(|token_id| self.nft_token(token_id).unwrap())
+                .collect(),
+            next_cursor,
+        }
     }
 }
@@ -2,10 +2,16 @@ use std::fmt;
 
 use near_sdk::serde::{Deserialize, Serialize};
 
+use crate::PauseFeature;
+
 /// Enum that represents the data type of the EventLog.
-/// The enum can either be an NftMint or an NftTransfer.
+/// The enum can either be one of the NEP-171 events (NftMint, NftTransfer, NftBurn, ContractMetadataUpdate,
+/// NftMetadataUpdate) or one of this contract's own extension events (MinterAdd, MinterRemove, OwnershipTransferPropose,
+/// OwnershipTransferAccept, OwnershipTransferCancel, Pause, Unpause, OperatorApprove, OperatorRevoke, SponsorPoolDraw).
 /// 이벤트 로그의 데이터 유형을 나타내는 Enum입니다.
-/// Enum은 NftMint 또는 NftTransfer 중 하나일 수 있습니다
+/// Enum은 NEP-171 이벤트(NftMint, NftTransfer, NftBurn, ContractMetadataUpdate, NftMetadataUpdate)
+/// 또는 이 계약 고유의 확장 이벤트(MinterAdd, MinterRemove, OwnershipTransferPropose,
+/// OwnershipTransferAccept, OwnershipTransferCancel, Pause, Unpause, OperatorApprove, OperatorRevoke, SponsorPoolDraw) 중 하나일 수 있습니다
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(tag = "event", content = "data")]
 #[serde(rename_all = "snake_case")]
@@ -14,6 +20,19 @@ use near_sdk::serde::{Deserialize, Serialize};
 pub enum EventLogVariant {
     NftMint(Vec<NftMintLog>),
     NftTransfer(Vec<NftTransferLog>),
+    NftBurn(Vec<NftBurnLog>),
+    ContractMetadataUpdate(Vec<ContractMetadataUpdateLog>),
+    NftMetadataUpdate(Vec<NftMetadataUpdateLog>),
+    MinterAdd(Vec<MinterUpdateLog>),
+    MinterRemove(Vec<MinterUpdateLog>),
+    OwnershipTransferPropose(Vec<OwnershipTransferLog>),
+    OwnershipTransferAccept(Vec<OwnershipTransferLog>),
+    OwnershipTransferCancel(Vec<OwnershipTransferLog>),
+    Pause(Vec<PauseLog>),
+    Unpause(Vec<PauseLog>),
+    OperatorApprove(Vec<OperatorLog>),
+    OperatorRevoke(Vec<OperatorLog>),
+    SponsorPoolDraw(Vec<SponsorPoolDrawLog>),
 }
 
 /// Interface to capture data about an event
@@ -100,6 +119,181 @@ pub struct NftTransferLog {
     pub memo: Option<String>,
 }
 
+/// An event log to capture token burning
+///
+/// Arguments
+/// * `owner_id`: owner of the burned tokens
+/// * `authorized_id`: approved account to burn
+/// * `token_ids`: ["1", "12345abc"]
+/// * `memo`: optional message
+/// 토큰 소각을 캡쳐하는 이벤트 로그입니다.
+///
+/// 인자:
+/// * `owner_id`: 소각된 토큰의 소유자
+/// * `authorized_id`: 소각을 승인한 계정
+/// * `token_ids`: ["1", "12345abc"]
+/// * `memo`: 선택적 메시지
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct NftBurnLog {
+    pub owner_id: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub authorized_id: Option<String>,
+
+    pub token_ids: Vec<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+/// An event log to capture contract metadata updates
+///
+/// Arguments
+/// * `memo`: optional message
+/// 계약 메타데이터 업데이트를 캡쳐하는 이벤트 로그입니다.
+///
+/// 인자:
+/// * `memo`: 선택적 메시지
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ContractMetadataUpdateLog {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+/// An event log to capture token metadata updates
+///
+/// Arguments
+/// * `token_ids`: ["1", "12345abc"]
+/// * `memo`: optional message
+/// 토큰 메타데이터 업데이트를 캡쳐하는 이벤트 로그입니다.
+///
+/// 인자:
+/// * `token_ids`: ["1", "12345abc"]
+/// * `memo`: 선택적 메시지
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct NftMetadataUpdateLog {
+    pub token_ids: Vec<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+/// An event log to capture changes to the set of approved minters
+///
+/// Arguments
+/// * `account_ids`: ["minter.near"]
+/// * `memo`: optional message
+/// 승인된 발행자 세트의 변경을 캡쳐하는 이벤트 로그입니다.
+///
+/// 인자:
+/// * `account_ids`: ["minter.near"]
+/// * `memo`: 선택적 메시지
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MinterUpdateLog {
+    pub account_ids: Vec<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+/// An event log to capture each step of a contract ownership transfer
+///
+/// Arguments
+/// * `old_owner_id`: "owner.near"
+/// * `new_owner_id`: "multisig.near"
+/// * `memo`: optional message
+/// 계약 소유권 이전의 각 단계를 캡쳐하는 이벤트 로그입니다.
+///
+/// 인자:
+/// * `old_owner_id`: "owner.near"
+/// * `new_owner_id`: "multisig.near"
+/// * `memo`: 선택적 메시지
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct OwnershipTransferLog {
+    pub old_owner_id: String,
+    pub new_owner_id: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+/// An event log to capture a feature being paused or unpaused
+///
+/// Arguments
+/// * `feature`: "mint" or "transfer"
+/// * `account_id`: the account that paused or unpaused the feature
+/// * `memo`: optional message
+/// 기능의 일시 정지 또는 해제를 캡쳐하는 이벤트 로그입니다.
+///
+/// 인자:
+/// * `feature`: "mint" 또는 "transfer"
+/// * `account_id`: 기능을 일시 정지하거나 해제한 계정
+/// * `memo`: 선택적 메시지
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PauseLog {
+    pub feature: PauseFeature,
+    pub account_id: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+/// An event log to capture an operator being approved or revoked for all of an owner's tokens
+///
+/// Arguments
+/// * `owner_id`: "owner.near"
+/// * `operator_id`: "market.near"
+/// * `memo`: optional message
+/// 소유자의 모든 토큰에 대한 운영자 승인 또는 취소를 캡쳐하는 이벤트 로그입니다.
+///
+/// 인자:
+/// * `owner_id`: "owner.near"
+/// * `operator_id`: "market.near"
+/// * `memo`: 선택적 메시지
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct OperatorLog {
+    pub owner_id: String,
+    pub operator_id: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+/// An event log to capture the sponsor pool paying for the storage of a mint
+///
+/// Arguments
+/// * `relayer_id`: whitelisted relayer that minted, "relayer.near"
+/// * `receiver_id`: receiver of the minted tokens, "user.near"
+/// * `amount`: yoctoNEAR drawn from the pool, "6000000000000000000000"
+/// * `remaining_balance`: yoctoNEAR left in the pool
+/// * `memo`: optional message
+/// 후원 풀이 발행의 저장소 비용을 지불한 것을 캡쳐하는 이벤트 로그입니다.
+///
+/// 인자:
+/// * `relayer_id`: 발행한 화이트리스트 릴레이어, "relayer.near"
+/// * `receiver_id`: 발행된 토큰의 수신자, "user.near"
+/// * `amount`: 풀에서 차감된 yoctoNEAR, "6000000000000000000000"
+/// * `remaining_balance`: 풀에 남은 yoctoNEAR
+/// * `memo`: 선택적 메시지
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SponsorPoolDrawLog {
+    pub relayer_id: String,
+    pub receiver_id: String,
+    pub amount: String,
+    pub remaining_balance: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -157,4 +351,76 @@ mod tests {
         };
         assert_eq!(expected, log.to_string());
     }
-} 
\ No newline at end of file
+
+    #[test]
+    fn nep_format_burn() {
+        let expected = r#"EVENT_JSON:{"standard":"nep171","version":"1.0.0","event":"nft_burn","data":[{"owner_id":"user1.near","authorized_id":"market.near","token_ids":["token"]}]}"#;
+        let log = EventLog {
+            standard: "nep171".to_string(),
+            version: "1.0.0".to_string(),
+            event: EventLogVariant::NftBurn(vec![NftBurnLog {
+                owner_id: "user1.near".to_string(),
+                authorized_id: Some("market.near".to_string()),
+                token_ids: vec!["token".to_string()],
+                memo: None,
+            }]),
+        };
+        assert_eq!(expected, log.to_string());
+    }
+
+    #[test]
+    fn nep_format_contract_metadata_update() {
+        let expected = r#"EVENT_JSON:{"standard":"nep171","version":"1.1.0","event":"contract_metadata_update","data":[{"memo":"new icon"}]}"#;
+        let log = EventLog {
+            standard: "nep171".to_string(),
+            version: "1.1.0".to_string(),
+            event: EventLogVariant::ContractMetadataUpdate(vec![ContractMetadataUpdateLog {
+                memo: Some("new icon".to_owned()),
+            }]),
+        };
+        assert_eq!(expected, log.to_string());
+    }
+
+    #[test]
+    fn nep_format_nft_metadata_update() {
+        let expected = r#"EVENT_JSON:{"standard":"nep171","version":"1.2.0","event":"nft_metadata_update","data":[{"token_ids":["token"]}]}"#;
+        let log = EventLog {
+            standard: "nep171".to_string(),
+            version: "1.2.0".to_string(),
+            event: EventLogVariant::NftMetadataUpdate(vec![NftMetadataUpdateLog {
+                token_ids: vec!["token".to_string()],
+                memo: None,
+            }]),
+        };
+        assert_eq!(expected, log.to_string());
+    }
+
+    #[test]
+    fn extension_format_minter_add() {
+        let expected = r#"EVENT_JSON:{"standard":"nft_extension","version":"1.0.0","event":"minter_add","data":[{"account_ids":["minter.near"]}]}"#;
+        let log = EventLog {
+            standard: "nft_extension".to_string(),
+            version: "1.0.0".to_string(),
+            event: EventLogVariant::MinterAdd(vec![MinterUpdateLog {
+                account_ids: vec!["minter.near".to_string()],
+                memo: None,
+            }]),
+        };
+        assert_eq!(expected, log.to_string());
+    }
+
+    #[test]
+    fn extension_format_ownership_transfer_propose() {
+        let expected = r#"EVENT_JSON:{"standard":"nft_extension","version":"1.0.0","event":"ownership_transfer_propose","data":[{"old_owner_id":"owner.near","new_owner_id":"multisig.near"}]}"#;
+        let log = EventLog {
+            standard: "nft_extension".to_string(),
+            version: "1.0.0".to_string(),
+            event: EventLogVariant::OwnershipTransferPropose(vec![OwnershipTransferLog {
+                old_owner_id: "owner.near".to_string(),
+                new_owner_id: "multisig.near".to_string(),
+                memo: None,
+            }]),
+        };
+        assert_eq!(expected, log.to_string());
+    }
+}
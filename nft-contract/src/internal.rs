@@ -57,6 +57,14 @@ pub(crate) fn hash_account_id(account_id: &AccountId) -> CryptoHash {
     hash
 }
 
+//used to generate a unique prefix for the set of tokens of a type
+// 유형의 토큰 세트에 사용할 고유한 접두사를 생성합니다.
+pub(crate) fn hash_token_type(token_type: &TokenType) -> CryptoHash {
+    let mut hash = CryptoHash::default();
+    hash.copy_from_slice(&env::sha256(token_type.as_bytes()));
+    hash
+}
+
 //used to make sure the user attached exactly 1 yoctoNEAR
 // 사용자가 정확히 1 yoctoNEAR를 첨부했는지 확인하는 데 사용됩니다.
 pub(crate) fn assert_one_yocto() {
@@ -101,6 +109,206 @@ pub(crate) fn refund_deposit(storage_used: u64) {
 }
 
 impl Contract {
+    //make sure that the caller is the owner of the contract
+    // 호출자가 계약의 소유자인지 확인합니다.
+    pub(crate) fn assert_contract_owner(&self) {
+        assert_eq!(
+            env::predecessor_account_id(),
+            self.owner_id,
+            "only contract owner"
+        )
+    }
+
+    //make sure that the perpetual royalties can be paid out: at most 6 accounts, no zero amounts and a total within the royalty cap.
+    //otherwise `10000 - total_perpetual` in the payout could underflow and every sale would fail.
+    // 영구 로열티를 지급할 수 있는지 확인합니다: 최대 6개 계정, 0이 아닌 금액, 로열티 상한 이내의 합계.
+    // 그렇지 않으면 지급 시 `10000 - total_perpetual`이 언더플로되어 모든 판매가 실패할 수 있습니다.
+    pub(crate) fn assert_valid_royalty(&self, royalty: &HashMap<AccountId, u32>) {
+        //make sure that the length of the perpetual royalties is below 7 since we won't have enough GAS to pay out that many people
+        // 영구 로열티의 길이가 7 미만인지 확인합니다. 그렇지 않으면 많은 사람들에게 GAS를 지급할 수 없습니다.
+        assert!(royalty.len() < 7, "Cannot add more than 6 perpetual royalty amounts");
+
+        let mut total: u64 = 0;
+        for (account_id, amount) in royalty {
+            assert!(*amount > 0, "Royalty for {} must be greater than zero", account_id);
+            total += u64::from(*amount);
+        }
+        assert!(
+            total <= u64::from(self.royalty_cap),
+            "Total royalty of {} exceeds the cap of {}",
+            total,
+            self.royalty_cap
+        );
+    }
+
+    //make sure that the token isn't soulbound, since soulbound tokens can't be transferred or approved
+    // 토큰이 soulbound가 아닌지 확인합니다. soulbound 토큰은 전송하거나 승인할 수 없습니다.
+    pub(crate) fn assert_not_soulbound(&self, token_id: &TokenId) {
+        assert!(
+            !self.soulbound_tokens.contains(token_id),
+            "Token is soulbound and cannot be transferred"
+        );
+    }
+
+    //make sure that the type of the token isn't locked
+    // 토큰의 유형이 잠겨 있지 않은지 확인합니다.
+    pub(crate) fn assert_token_type_not_locked(&self, token_id: &TokenId) {
+        if let Some(token_type) = self.token_type_by_id.get(token_id) {
+            assert!(
+                !self.token_types_locked.contains(&token_type),
+                "Token type {} is locked",
+                token_type
+            );
+        }
+    }
+
+    //make sure that the token is valid right now: it has started and hasn't expired
+    // 토큰이 지금 유효한지 확인합니다: 유효 기간이 시작되었고 만료되지 않아야 합니다.
+    pub(crate) fn assert_token_valid(&self, token_id: &TokenId) {
+        let metadata = self.token_metadata_by_id.get(token_id).expect("No token metadata");
+        let now = env::block_timestamp_ms();
+        assert!(
+            metadata.has_started(now),
+            "Token {} is not valid until {}",
+            token_id,
+            metadata.starts_at.map_or(0, |starts_at| starts_at.0)
+        );
+        assert!(!metadata.is_expired(now), "Token {} has expired", token_id);
+    }
+
+    //make sure that the caller is allowed to mint: anyone can when public minting is on, otherwise only approved minters
+    // 호출자가 토큰을 발행할 수 있는지 확인합니다: 공개 발행이면 누구나, 아니면 승인된 발행자만 가능합니다.
+    pub(crate) fn assert_can_mint(&self) {
+        assert!(
+            self.public_minting || self.approved_minters.contains(&env::predecessor_account_id()),
+            "Not approved minter"
+        )
+    }
+
+    //creates the token and stores it along with its metadata (internal method and can't be called directly via CLI).
+    //logging and storage refunds are left to the caller so batch mints can do them once.
+    // 토큰을 생성하고 메타데이터와 함께 저장합니다 (내부 메서드이며 CLI에서 직접 호출할 수 없습니다).
+    // 일괄 발행에서 한 번만 처리할 수 있도록 로그 기록과 저장소 환불은 호출자에게 맡깁니다.
+    pub(crate) fn internal_mint(&mut self, args: MintArgs) {
+        let MintArgs {
+            token_id,
+            mut metadata,
+            receiver_id,
+            perpetual_royalties,
+            soulbound,
+            token_type,
+        } = args;
+
+        //create a royalty map to store in the token
+        // 토큰에 저장할 로열티 맵을 생성합니다.
+        let mut royalty = HashMap::new();
+
+        //if perpetual royalties were passed into the function:
+        // 영구 로열티가 함수에 전달되면:
+        if let Some(perpetual_royalties) = perpetual_royalties {
+            //make sure the perpetual royalties can be paid out
+            // 영구 로열티를 지급할 수 있는지 확인합니다.
+            self.assert_valid_royalty(&perpetual_royalties);
+            //iterate through the perpetual royalties and insert the account and amount in the royalty map
+            // 영구 로열티를 반복하고 로열티 맵에 계정과 금액을 삽입합니다.
+            for (account, amount) in perpetual_royalties {
+                royalty.insert(account, amount);
+            }
+        }
+
+        //specify the token struct that contains the owner ID
+        // 소유자 ID를 포함하는 토큰 구조를 지정합니다.
+        let token = Token {
+            //set the owner ID equal to the receiver ID passed into the function
+            // 함수에 전달된 수신자 ID와 동일한 소유자 ID를 설정합니다.
+            owner_id: receiver_id,
+            //we set the approved account IDs to the default value (an empty map)
+            // 승인된 계정 ID를 기본값(빈 맵)으로 설정합니다.
+            approved_account_ids: Default::default(),
+            //the next approval ID is set to 0
+            // 다음 승인 ID를 0으로 설정합니다.
+            next_approval_id: 0,
+            //the map of perpetual royalties for the token (The owner will get 100% - total perpetual royalties)
+            // 토큰의 영구 로열티 맵 (소유자는 100% - 총 영구 로열티를 받게 됩니다.)
+            royalty,
+        };
+
+        //insert the token ID and token struct and make sure that the token doesn't exist
+        // 토큰 ID와 토큰 구조를 삽입하고 토큰이 이미 존재하지 않는지 확인합니다.
+        assert!(
+            self.tokens_by_id.insert(&token_id, &token).is_none(),
+            "Token already exists"
+        );
+
+        //the token is issued now and hasn't been updated yet, whatever the minter passed in
+        // 발행자가 전달한 값과 상관없이 토큰은 지금 발행되었고 아직 업데이트되지 않았습니다.
+        metadata.issued_at = Some(U64(env::block_timestamp_ms()));
+        metadata.updated_at = None;
+
+        //insert the token ID and metadata
+        // 토큰 ID와 메타데이터를 삽입합니다.
+        self.token_metadata_by_id.insert(&token_id, &metadata);
+
+        //keep track of who minted the token so they can be given the right to update its metadata
+        // 메타데이터를 업데이트할 권한을 줄 수 있도록 토큰을 발행한 계정을 기록합니다.
+        self.minter_by_token_id.insert(&token_id, &env::predecessor_account_id());
+
+        //soulbound tokens can never be transferred or approved
+        // soulbound 토큰은 전송하거나 승인할 수 없습니다.
+        if soulbound.unwrap_or(false) {
+            self.soulbound_tokens.insert(&token_id);
+        }
+
+        //if a token type was passed in, keep track of it and add the token to the type's index
+        // 토큰 유형이 전달되었다면 기록하고 해당 유형의 인덱스에 토큰을 추가합니다.
+        if let Some(token_type) = token_type {
+            self.internal_add_token_to_type(&token_type, &token_id);
+            self.token_type_by_id.insert(&token_id, &token_type);
+        }
+
+        //call the internal method for adding the token to the owner
+        // 소유자에게 토큰을 추가하기 위한 내부 메서드를 호출합니다.
+        self.internal_add_token_to_owner(&token.owner_id, &token_id);
+    }
+
+    //add a token to the set of tokens of a type
+    // 유형의 토큰 세트에 토큰을 추가합니다.
+    pub(crate) fn internal_add_token_to_type(&mut self, token_type: &TokenType, token_id: &TokenId) {
+        //get the set of tokens for the given type. If there isn't one yet, we create a new unordered set
+        // 주어진 유형의 토큰 세트를 가져옵니다. 아직 없다면 새로운 UnorderedSet을 생성합니다.
+        let mut tokens_set = self.tokens_per_type.get(token_type).unwrap_or_else(|| {
+            UnorderedSet::new(
+                StorageKey::TokensPerTypeInner {
+                    //we get a new unique prefix for the collection
+                    token_type_hash: hash_token_type(token_type),
+                }
+                .try_to_vec()
+                .unwrap(),
+            )
+        });
+
+        tokens_set.insert(token_id);
+        self.tokens_per_type.insert(token_type, &tokens_set);
+    }
+
+    //remove a token from the set of tokens of a type
+    // 유형의 토큰 세트에서 토큰을 제거합니다.
+    pub(crate) fn internal_remove_token_from_type(&mut self, token_type: &TokenType, token_id: &TokenId) {
+        let mut tokens_set = self
+            .tokens_per_type
+            .get(token_type)
+            .expect("Token should be in its type's set");
+        tokens_set.remove(token_id);
+
+        //if the set is now empty, we remove the type from the map. Otherwise, we insert the set back
+        // 세트가 비었다면 맵에서 유형을 제거하고, 그렇지 않다면 세트를 다시 삽입합니다.
+        if tokens_set.is_empty() {
+            self.tokens_per_type.remove(token_type);
+        } else {
+            self.tokens_per_type.insert(token_type, &tokens_set);
+        }
+    }
+
     //add a token to the set of tokens an owner has
     pub(crate) fn internal_add_token_to_owner(
         &mut self,
@@ -152,66 +360,66 @@ impl Contract {
         }
     }
 
-    //transfers the NFT to the receiver_id (internal method and can't be called directly via CLI).
-    pub(crate) fn internal_transfer(
-        &mut self,
+    //make sure that the sender is allowed to act on the token: either the owner or an approved account
+    //(internal method and can't be called directly via CLI).
+    // 발신자가 토큰을 다룰 수 있는지 확인합니다: 소유자이거나 승인된 계정이어야 합니다.
+    pub(crate) fn internal_assert_authorized(
+        &self,
         sender_id: &AccountId,
-        receiver_id: &AccountId,
         token_id: &TokenId,
-        //we introduce an approval ID so that people with that approval ID can transfer the token
+        token: &Token,
+        //we introduce an approval ID so that people with that approval ID can act on the token
         approval_id: Option<u64>,
-        memo: Option<String>,
-    ) -> Token {
-        //get the token object by passing in the token_id
-        let token = self.tokens_by_id.get(token_id).expect("No token");
-
+    ) {
         //if the sender doesn't equal the owner, we check if the sender is in the approval list
-		if sender_id != &token.owner_id {
-			//if the token's approved account IDs doesn't contain the sender, we panic
-			if !token.approved_account_ids.contains_key(sender_id) {
-				env::panic_str("Unauthorized");
-			}
-
-			// If they included an approval_id, check if the sender's actual approval_id is the same as the one included
-			if let Some(enforced_approval_id) = approval_id {
+        // 발신자가 소유자가 아니라면 승인 목록에 있는지 확인합니다.
+        if sender_id != &token.owner_id {
+            //if the token's approved account IDs doesn't contain the sender, we panic
+            if !token.approved_account_ids.contains_key(sender_id) {
+                env::panic_str("Unauthorized");
+            }
+
+            //expired approvals are treated as if they were never given
+            // 만료된 승인은 주어지지 않은 것으로 취급합니다.
+            let sender_approval_id = token.approved_account_ids[sender_id];
+            assert!(
+                !self.is_approval_expired(token_id, sender_approval_id),
+                "Approval for {} has expired",
+                sender_id
+            );
+
+            // If they included an approval_id, check if the sender's actual approval_id is the same as the one included
+            if let Some(enforced_approval_id) = approval_id {
                 //get the actual approval ID
-				let actual_approval_id = token
-					.approved_account_ids
-					.get(sender_id)
+                let actual_approval_id = token
+                    .approved_account_ids
+                    .get(sender_id)
                     //if the sender isn't in the map, we panic
-					.expect("Sender is not approved account");
+                    .expect("Sender is not approved account");
 
                 //make sure that the actual approval ID is the same as the one provided
                 assert_eq!(
-					actual_approval_id, &enforced_approval_id,
-					"The actual approval_id {} is different from the given approval_id {}",
-					actual_approval_id, enforced_approval_id,
-				);
-			}
-		}
-
-        //we make sure that the sender isn't sending the token to themselves
-        assert_ne!(
-            &token.owner_id, receiver_id,
-            "The token owner and the receiver should be different"
-        );
-
-        //we remove the token from it's current owner's set
-        self.internal_remove_token_from_owner(&token.owner_id, token_id);
-        //we then add the token to the receiver_id's set
-        self.internal_add_token_to_owner(receiver_id, token_id);
+                    actual_approval_id, &enforced_approval_id,
+                    "The actual approval_id {} is different from the given approval_id {}",
+                    actual_approval_id, enforced_approval_id,
+                );
+            }
+        }
+    }
 
-        //we create a new token struct 
-        let new_token = Token {
-            owner_id: receiver_id.clone(),
-            //reset the approval account IDs
-            approved_account_ids: Default::default(),
-            next_approval_id: token.next_approval_id,
-            //we copy over the royalties from the previous token
-            royalty: token.royalty.clone(),
-        };
-        //insert that new token into the tokens_by_id, replacing the old entry 
-        self.tokens_by_id.insert(token_id, &new_token);
+    //transfers the NFT to the receiver_id (internal method and can't be called directly via CLI).
+    pub(crate) fn internal_transfer(
+        &mut self,
+        sender_id: &AccountId,
+        receiver_id: &AccountId,
+        token_id: &TokenId,
+        //we introduce an approval ID so that people with that approval ID can transfer the token
+        approval_id: Option<u64>,
+        memo: Option<String>,
+    ) -> Token {
+        //move the token to the receiver and get the previous token object
+        // 토큰을 수신자에게 옮기고 이전 토큰 객체를 가져옵니다.
+        let token = self.internal_move_token(sender_id, receiver_id, token_id, approval_id);
 
         //if there was some memo attached, we log it. 
         if let Some(memo) = memo.as_ref() {
@@ -220,8 +428,9 @@ impl Contract {
 
         // Default the authorized ID to be None for the logs.
         let mut authorized_id = None;
-        //if the approval ID was provided, set the authorized ID equal to the sender
-        if approval_id.is_some() {
+        //if the approval ID was provided or an operator transferred the token, set the authorized ID equal to the sender
+        // 승인 ID가 제공되었거나 운영자가 토큰을 전송한 경우 authorized_id를 발신자로 설정합니다.
+        if approval_id.is_some() || sender_id != &token.owner_id {
             authorized_id = Some(sender_id.to_string());
         }
 
@@ -252,4 +461,64 @@ impl Contract {
         //return the previous token object that was transferred.
         token
     }
+
+    //checks that the token can be transferred by the sender and moves it to the receiver without logging anything.
+    //returns the previous token object.
+    // 발신자가 토큰을 전송할 수 있는지 확인하고 아무것도 로그에 기록하지 않고 수신자에게 옮깁니다.
+    // 이전 토큰 객체를 반환합니다.
+    pub(crate) fn internal_move_token(
+        &mut self,
+        sender_id: &AccountId,
+        receiver_id: &AccountId,
+        token_id: &TokenId,
+        approval_id: Option<u64>,
+    ) -> Token {
+        //get the token object by passing in the token_id
+        let token = self.tokens_by_id.get(token_id).expect("No token");
+
+        //make sure the token can be transferred at all
+        // 토큰을 전송할 수 있는지 확인합니다.
+        self.assert_not_paused(PauseFeature::Transfer);
+        self.assert_not_soulbound(token_id);
+        self.assert_token_type_not_locked(token_id);
+        self.assert_token_valid(token_id);
+
+        //make sure the sender is the owner, an approved account for the token or an operator of the owner.
+        //operators can only transfer, so they're checked here instead of in internal_assert_authorized, which burning uses as well.
+        //an operator doesn't act through a per-token approval, so the approval ID it passes (nft_transfer_payout always passes one) is ignored
+        // 발신자가 소유자, 토큰에 대해 승인된 계정 또는 소유자의 운영자인지 확인합니다.
+        // 운영자는 전송만 할 수 있으므로 소각에서도 사용하는 internal_assert_authorized 대신 여기에서 확인합니다.
+        // 운영자는 토큰별 승인을 통해 동작하지 않으므로 전달한 승인 ID(nft_transfer_payout은 항상 전달합니다)는 무시됩니다.
+        let is_operator_transfer = sender_id != &token.owner_id
+            && !token.approved_account_ids.contains_key(sender_id)
+            && self.is_operator(&token.owner_id, sender_id);
+        if !is_operator_transfer {
+            self.internal_assert_authorized(sender_id, token_id, &token, approval_id);
+        }
+
+        //we make sure that the sender isn't sending the token to themselves
+        assert_ne!(
+            &token.owner_id, receiver_id,
+            "The token owner and the receiver should be different"
+        );
+
+        //we remove the token from it's current owner's set
+        self.internal_remove_token_from_owner(&token.owner_id, token_id);
+        //we then add the token to the receiver_id's set
+        self.internal_add_token_to_owner(receiver_id, token_id);
+
+        //we create a new token struct 
+        let new_token = Token {
+            owner_id: receiver_id.clone(),
+            //reset the approval account IDs
+            approved_account_ids: Default::default(),
+            next_approval_id: token.next_approval_id,
+            //we copy over the royalties from the previous token
+            royalty: token.royalty.clone(),
+        };
+        //insert that new token into the tokens_by_id, replacing the old entry 
+        self.tokens_by_id.insert(token_id, &new_token);
+
+        token
+    }
 } 
\ No newline at end of file
@@ -1,33 +1,54 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
-use near_sdk::collections::{LazyOption, LookupMap, UnorderedMap, UnorderedSet};
-use near_sdk::json_types::{Base64VecU8, U128};
+use near_sdk::collections::{LazyOption, LookupMap, LookupSet, UnorderedMap, UnorderedSet};
+use near_sdk::json_types::{Base64VecU8, U128, U64};
 use near_sdk::serde::{Deserialize, Serialize};
 use near_sdk::{
     env, near_bindgen, AccountId, Balance, CryptoHash, PanicOnDefault, Promise, PromiseOrValue,
 };
 
 use crate::internal::*;
+use crate::migrate::*;
 pub use crate::metadata::*;
 pub use crate::mint::*;
 pub use crate::nft_core::*;
 pub use crate::approval::*;
 pub use crate::royalty::*;
 pub use crate::events::*;
+pub use crate::pause::*;
+pub use crate::sponsor::*;
 
 mod internal;
 mod approval; 
 mod enumeration; 
 mod metadata; 
 mod mint; 
+mod burn; 
 mod nft_core; 
 mod royalty; 
 mod events;
+mod owner;
+mod pause;
+mod operator;
+mod storage;
+mod sponsor;
+mod source_metadata;
+mod migrate;
 
 /// NFT 표준의 버전으로 사용됩니다.
 pub const NFT_METADATA_SPEC: &str = "1.0.0";
 /// 우리가 사용하는 NFT 표준의 이름입니다.
 pub const NFT_STANDARD_NAME: &str = "nep171";
+/// contract_metadata_update 이벤트가 추가된 NFT 표준의 버전입니다.
+pub const NFT_CONTRACT_METADATA_UPDATE_SPEC: &str = "1.1.0";
+/// nft_metadata_update 이벤트가 추가된 NFT 표준의 버전입니다.
+pub const NFT_METADATA_UPDATE_SPEC: &str = "1.2.0";
+/// NFT 표준에 없는 이 계약 고유의 이벤트에 사용하는 표준 이름입니다.
+pub const NFT_EXTENSION_STANDARD_NAME: &str = "nft_extension";
+/// 이 계약 고유의 이벤트 표준의 버전입니다.
+pub const NFT_EXTENSION_SPEC: &str = "1.0.0";
+/// 영구 로열티 합계의 기본 상한입니다 (10000 = 100%이므로 5000 = 50%).
+pub const DEFAULT_ROYALTY_CAP: u32 = 5000;
 
 #[near_bindgen]
 #[derive(BorshDeserialize, BorshSerialize, PanicOnDefault)]
@@ -46,6 +67,66 @@ pub struct Contract {
 
     // 계약의 메타데이터를 추적합니다.
     pub metadata: LazyOption<NFTContractMetadata>,
+
+    // 주어진 토큰 ID를 처음 발행한 계정을 추적합니다.
+    pub minter_by_token_id: LookupMap<TokenId, AccountId>,
+
+    // 주어진 토큰 ID의 메타데이터를 누가 업데이트할 수 있는지 추적합니다. 없으면 계약 소유자만 가능합니다.
+    pub metadata_updater_by_token_id: LookupMap<TokenId, MetadataUpdater>,
+
+    // 토큰을 발행할 수 있도록 승인된 계정을 추적합니다.
+    pub approved_minters: UnorderedSet<AccountId>,
+
+    // true이면 누구나 토큰을 발행할 수 있고, false이면 승인된 계정만 발행할 수 있습니다.
+    pub public_minting: bool,
+
+    // 소유권 이전이 제안된 계정입니다. 이 계정이 accept_ownership을 호출해야 이전이 완료됩니다.
+    pub pending_owner_id: Option<AccountId>,
+
+    // 토큰 하나의 영구 로열티 합계의 상한입니다 (10000 = 100%).
+    pub royalty_cap: u32,
+
+    // 양도할 수 없는(soulbound) 토큰 ID를 추적합니다. 발행 시에 정해집니다.
+    pub soulbound_tokens: LookupSet<TokenId>,
+
+    // 현재 일시 정지된 기능을 추적합니다.
+    pub paused_features: LookupSet<PauseFeature>,
+
+    // 계약 소유자 외에 기능을 일시 정지하고 해제할 수 있는 계정을 추적합니다.
+    pub pausers: LookupSet<AccountId>,
+
+    // 주어진 토큰 ID의 유형을 추적합니다. 유형 없이 발행된 토큰은 포함되지 않습니다.
+    pub token_type_by_id: LookupMap<TokenId, TokenType>,
+
+    // 주어진 유형의 모든 토큰 ID를 추적합니다.
+    pub tokens_per_type: LookupMap<TokenType, UnorderedSet<TokenId>>,
+
+    // 전송이 잠긴 토큰 유형을 추적합니다.
+    pub token_types_locked: LookupSet<TokenType>,
+
+    // 소유자의 모든 토큰을 대신 전송할 수 있도록 승인된 (소유자, 운영자) 쌍을 추적합니다.
+    pub operator_approvals: LookupSet<(AccountId, AccountId)>,
+
+    // 주어진 토큰 ID의 승인 ID별 만료 시각(block timestamp, 나노초)을 추적합니다. 만료 없이 주어진 승인은 포함되지 않습니다.
+    pub approval_expires_at: LookupMap<TokenId, HashMap<u64, u64>>,
+
+    // 계정이 NEP-145 storage_deposit으로 미리 예치한 저장소 잔액을 추적합니다 (최소 잔액 포함).
+    pub storage_deposits: LookupMap<AccountId, Balance>,
+
+    // 화이트리스트 릴레이어의 발행 저장소 비용을 지불하는 후원 풀의 잔액과 상한입니다.
+    pub sponsor_pool: SponsorPool,
+
+    // 후원 풀로 발행 비용을 지불받을 수 있는 릴레이어 계정을 추적합니다.
+    pub sponsor_relayers: LookupSet<AccountId>,
+
+    // 수신자별로 후원 풀이 지금까지 지불한 금액을 추적합니다.
+    pub sponsored_per_receiver: LookupMap<AccountId, Balance>,
+
+    // 저장소 비용을 후원 풀이 지불한 토큰을 추적합니다. 이 토큰을 소각하면 해제된 저장소가 풀에 환불됩니다.
+    pub sponsored_tokens: LookupSet<TokenId>,
+
+    // 상태 이전 때 Unix epoch 밀리초로 변환할 수 없었던 토큰의 시각을 원래 문자열 그대로 보관합니다.
+    pub unparsed_timestamps_by_id: LookupMap<TokenId, UnparsedTimestamps>,
 }
 
 /// 영구 컬렉션의 키를 위한 도우미 구조체입니다.
@@ -59,6 +140,20 @@ pub enum StorageKey {
     TokensPerType,
     TokensPerTypeInner { token_type_hash: CryptoHash },
     TokenTypesLocked,
+    MinterByTokenId,
+    MetadataUpdaterByTokenId,
+    ApprovedMinters,
+    SoulboundTokens,
+    PausedFeatures,
+    Pausers,
+    TokenTypeById,
+    OperatorApprovals,
+    ApprovalExpiresAt,
+    StorageDeposits,
+    SponsorRelayers,
+    SponsoredPerReceiver,
+    SponsoredTokens,
+    UnparsedTimestampsById,
 }
 
 #[near_bindgen]
@@ -91,6 +186,11 @@ impl Contract {
     */
     #[init]
     pub fn new(owner_id: AccountId, metadata: NFTContractMetadata) -> Self {
+        // 승인된 발행자 세트를 생성하고 소유자를 추가합니다.
+        let mut approved_minters =
+            UnorderedSet::new(StorageKey::ApprovedMinters.try_to_vec().unwrap());
+        approved_minters.insert(&owner_id);
+
         // 모든 필드를 초기화한 Self 타입의 변수를 생성합니다.
         let this = Self {
             // 저장소 키는 컬렉션의 접두사입니다. 이는 데이터 충돌을 방지하는데 도움이 됩니다.
@@ -105,8 +205,41 @@ impl Contract {
                 StorageKey::NFTContractMetadata.try_to_vec().unwrap(),
                 Some(&metadata),
             ),
+            minter_by_token_id: LookupMap::new(StorageKey::MinterByTokenId.try_to_vec().unwrap()),
+            metadata_updater_by_token_id: LookupMap::new(
+                StorageKey::MetadataUpdaterByTokenId.try_to_vec().unwrap(),
+            ),
+            approved_minters,
+            // 기본적으로 승인된 계정만 토큰을 발행할 수 있습니다.
+            public_minting: false,
+            // 진행 중인 소유권 이전이 없습니다.
+            pending_owner_id: None,
+            // 영구 로열티 합계는 기본적으로 50%를 넘을 수 없습니다.
+            royalty_cap: DEFAULT_ROYALTY_CAP,
+            soulbound_tokens: LookupSet::new(StorageKey::SoulboundTokens.try_to_vec().unwrap()),
+            paused_features: LookupSet::new(StorageKey::PausedFeatures.try_to_vec().unwrap()),
+            pausers: LookupSet::new(StorageKey::Pausers.try_to_vec().unwrap()),
+            token_type_by_id: LookupMap::new(StorageKey::TokenTypeById.try_to_vec().unwrap()),
+            tokens_per_type: LookupMap::new(StorageKey::TokensPerType.try_to_vec().unwrap()),
+            token_types_locked: LookupSet::new(StorageKey::TokenTypesLocked.try_to_vec().unwrap()),
+            operator_approvals: LookupSet::new(StorageKey::OperatorApprovals.try_to_vec().unwrap()),
+            approval_expires_at: LookupMap::new(StorageKey::ApprovalExpiresAt.try_to_vec().unwrap()),
+            storage_deposits: LookupMap::new(StorageKey::StorageDeposits.try_to_vec().unwrap()),
+            // 후원 풀은 비어 있고 상한이 0이므로 소유자가 설정하기 전까지는 사용할 수 없습니다.
+            sponsor_pool: SponsorPool::default(),
+            sponsor_relayers: LookupSet::new(StorageKey::SponsorRelayers.try_to_vec().unwrap()),
+            sponsored_per_receiver: LookupMap::new(
+                StorageKey::SponsoredPerReceiver.try_to_vec().unwrap(),
+            ),
+            sponsored_tokens: LookupSet::new(StorageKey::SponsoredTokens.try_to_vec().unwrap()),
+            unparsed_timestamps_by_id: LookupMap::new(
+                StorageKey::UnparsedTimestampsById.try_to_vec().unwrap(),
+            ),
         };
 
+        // 저장된 상태가 현재 구조를 사용한다고 기록합니다.
+        write_state_version();
+
         // Contract 객체를 반환합니다.
         this
     }
@@ -1,6 +1,9 @@
 //토큰 및 메타데이터 구조를 정의합니다.
 use crate::*;
+use near_sdk::serde::Deserializer;
 pub type TokenId = String;
+// 토큰 유형입니다 (예: "ticket", "badge"). 같은 유형의 토큰을 한꺼번에 조회하거나 전송을 잠글 수 있습니다.
+pub type TokenType = String;
 
 // defines the payout type we'll be returning as a part of the royalty standards.
 // 로열티 표준의 일부로 반환될 지급 유형을 정의합니다.
@@ -30,7 +33,33 @@ pub struct NFTContractMetadata {
     pub reference_hash: Option<Base64VecU8>,
 }
 
+// 계약 메타데이터의 부분 업데이트입니다. 전달되지 않은 필드는 변경되지 않습니다.
+// 선택 필드에 null을 전달하면 해당 필드를 지웁니다.
+#[derive(Serialize, Deserialize, Default)]
+#[serde(crate = "near_sdk::serde")]
+pub struct NFTContractMetadataUpdate {
+    pub spec: Option<String>,
+    pub name: Option<String>,
+    pub symbol: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_some", skip_serializing_if = "Option::is_none")]
+    pub icon: Option<Option<String>>,
+    #[serde(default, deserialize_with = "deserialize_some", skip_serializing_if = "Option::is_none")]
+    pub base_uri: Option<Option<String>>,
+    // `reference`를 변경하거나 지울 때는 `reference_hash`도 함께 전달해야 합니다.
+    #[serde(default, deserialize_with = "deserialize_some", skip_serializing_if = "Option::is_none")]
+    pub reference: Option<Option<String>>,
+    #[serde(default, deserialize_with = "deserialize_some", skip_serializing_if = "Option::is_none")]
+    pub reference_hash: Option<Option<Base64VecU8>>,
+}
 
+// 전달된 필드를 null이더라도 Some으로 읽어, 전달되지 않은 필드(None)와 지우려는 필드(Some(None))를 구분합니다.
+fn deserialize_some<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    T::deserialize(deserializer).map(Some)
+}
 
 // 토큰의 메타데이터 구조를 정의합니다.
 #[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
@@ -46,14 +75,14 @@ pub struct TokenMetadata {
     pub media_hash: Option<Base64VecU8>,
     /// 해당 토큰의 복사본 수입니다.
     pub copies: Option<u64>,
-    /// 토큰 발행일입니다.
-    pub issued_at: Option<String>,
-    /// 토큰의 만료일입니다.
-    pub expires_at: Option<String>,
-    /// 토큰이 유효한 시작일입니다.
-    pub starts_at: Option<String>,
-    /// 토큰의 마지막 업데이트 일입니다.
-    pub updated_at: Option<String>,
+    /// 토큰 발행 시각입니다 (Unix epoch, 밀리초). 발행 시 블록 시간으로 설정됩니다.
+    pub issued_at: Option<U64>,
+    /// 토큰의 만료 시각입니다 (Unix epoch, 밀리초).
+    pub expires_at: Option<U64>,
+    /// 토큰이 유효해지는 시각입니다 (Unix epoch, 밀리초).
+    pub starts_at: Option<U64>,
+    /// 토큰의 마지막 업데이트 시각입니다 (Unix epoch, 밀리초). 메타데이터가 바뀔 때 블록 시간으로 설정됩니다.
+    pub updated_at: Option<U64>,
     /// NFT가 체인 상에 저장하려는 추가 정보입니다. 문자열화된 JSON일 수 있습니다.
     pub extra: Option<String>,
     /// 토큰에 관련된 참조 또는 문서의 URL입니다.
@@ -62,6 +91,19 @@ pub struct TokenMetadata {
     pub reference_hash: Option<Base64VecU8>,
 }
 
+// 계약 소유자 외에 토큰의 메타데이터를 업데이트할 수 있는 계정을 정의합니다.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "snake_case")]
+pub enum MetadataUpdater {
+    // 현재 토큰 소유자
+    TokenOwner,
+    // 토큰을 처음 발행한 계정
+    Minter,
+    // 계약 소유자만
+    ContractOwner,
+}
+
 // 토큰 구조를 정의합니다.
 #[derive(BorshDeserialize, BorshSerialize)]
 pub struct Token {
@@ -82,6 +124,12 @@ pub struct JsonToken {
     pub metadata: TokenMetadata,
     pub approved_account_ids: HashMap<AccountId, u64>,
     pub royalty: HashMap<AccountId, u32>,
+    // 토큰이 양도할 수 없는(soulbound) 토큰인지 여부
+    pub soulbound: bool,
+    // 발행 시 지정된 토큰의 유형
+    pub token_type: Option<TokenType>,
+    // 토큰이 만료되었는지(expires_at이 지났는지) 여부. 만료된 토큰은 전송할 수 없습니다.
+    pub expired: bool,
 }
 
 pub trait NonFungibleTokenMetadata {
@@ -95,3 +143,282 @@ impl NonFungibleTokenMetadata for Contract {
         self.metadata.get().unwrap()
     }
 }
+
+#[near_bindgen]
+impl Contract {
+    /**
+     * 계약 메타데이터를 업데이트합니다. 계약 소유자만 호출할 수 있으며, 전달된 필드만 변경되고 null을 전달한 선택 필드는 지워집니다.
+     * 변경된 필드만 검증하므로, 기존 값이 유효하지 않더라도 다른 필드는 업데이트할 수 있습니다.
+     *
+     * near call CONTRACT_ID nft_update_contract_metadata '{
+        "metadata": { "icon": "NEW_ICON", "base_uri": "NEW_BASE_URI" },
+        "memo": "OPTIONAL_MEMO"
+    }' --accountId OWNER_ACCOUNT_ID --depositYocto 1
+     */
+    #[payable]
+    pub fn nft_update_contract_metadata(
+        &mut self,
+        metadata: NFTContractMetadataUpdate,
+        memo: Option<String>,
+    ) {
+        // 보안을 위해 최소 1 yoctoNEAR가 첨부되었는지, 호출자가 계약 소유자인지 확인합니다.
+        assert_at_least_one_yocto();
+        self.assert_contract_owner();
+
+        // 계약에서 사용하는 초기 저장소를 측정합니다.
+        let initial_storage_usage = env::storage_usage();
+
+        // `reference`가 바뀌면 이전 해시는 더 이상 유효하지 않으므로 새 해시가 필요합니다.
+        assert!(
+            metadata.reference.is_none() || metadata.reference_hash.is_some(),
+            "reference_hash must be provided when updating reference"
+        );
+        let reference_updated = metadata.reference.is_some() || metadata.reference_hash.is_some();
+
+        // 현재 메타데이터에 전달된 필드를 병합합니다.
+        let mut contract_metadata = self.metadata.get().unwrap();
+        if let Some(spec) = metadata.spec {
+            contract_metadata.spec = spec;
+        }
+        if let Some(name) = metadata.name {
+            contract_metadata.name = name;
+        }
+        if let Some(symbol) = metadata.symbol {
+            contract_metadata.symbol = symbol;
+        }
+        if let Some(icon) = metadata.icon {
+            contract_metadata.icon = icon;
+        }
+        if let Some(base_uri) = metadata.base_uri {
+            contract_metadata.base_uri = base_uri;
+        }
+        if let Some(reference) = metadata.reference {
+            contract_metadata.reference = reference;
+        }
+        if let Some(reference_hash) = metadata.reference_hash {
+            contract_metadata.reference_hash = reference_hash;
+        }
+
+        // `reference`나 `reference_hash`가 바뀌었다면 병합된 두 값이 일관되는지 확인합니다.
+        if reference_updated {
+            contract_metadata.assert_valid();
+        }
+
+        // 업데이트된 메타데이터를 저장합니다.
+        self.metadata.set(&contract_metadata);
+
+        // 이벤트 표준(NEP-171 v1.1.0)에 따라 계약 메타데이터 업데이트 로그를 구성합니다.
+        let contract_metadata_update_log: EventLog = EventLog {
+            // 표준 이름 ("nep171").
+            standard: NFT_STANDARD_NAME.to_string(),
+            // contract_metadata_update 이벤트가 추가된 표준의 버전 ("1.1.0").
+            version: NFT_CONTRACT_METADATA_UPDATE_SPEC.to_string(),
+            // 이벤트와 관련된 데이터는 벡터에 저장됩니다.
+            event: EventLogVariant::ContractMetadataUpdate(vec![ContractMetadataUpdateLog {
+                // 포함할 선택적 메모.
+                memo,
+            }]),
+        };
+
+        // 직렬화된 json을 로그에 기록합니다.
+        env::log_str(&contract_metadata_update_log.to_string());
+
+        // 메타데이터가 커졌다면 추가 저장소 비용을 청구하고, 나머지 첨부 금액은 환불합니다.
+        let required_storage_in_bytes = env::storage_usage().saturating_sub(initial_storage_usage);
+        refund_deposit(required_storage_in_bytes);
+    }
+}
+
+impl TokenMetadata {
+    //whether the token has expired at the passed in time (Unix epoch milliseconds)
+    // 전달된 시각(Unix epoch 밀리초)에 토큰이 만료되었는지 여부입니다.
+    pub(crate) fn is_expired(&self, now: u64) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at.0 <= now)
+    }
+
+    //whether the token has started being valid at the passed in time (Unix epoch milliseconds)
+    // 전달된 시각(Unix epoch 밀리초)에 토큰이 유효해졌는지 여부입니다.
+    pub(crate) fn has_started(&self, now: u64) -> bool {
+        self.starts_at.is_none_or(|starts_at| starts_at.0 <= now)
+    }
+}
+
+impl NFTContractMetadata {
+    // `reference`와 `reference_hash`는 함께 존재해야 하며, 해시는 32바이트 sha256 값이어야 합니다.
+    pub(crate) fn assert_valid(&self) {
+        assert_eq!(
+            self.reference.is_some(),
+            self.reference_hash.is_some(),
+            "reference and reference_hash must be set together"
+        );
+        if let Some(reference_hash) = &self.reference_hash {
+            assert_eq!(reference_hash.0.len(), 32, "reference_hash must be 32 bytes");
+        }
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /**
+     * 토큰 메타데이터를 업데이트합니다. 전달된 필드만 변경되며 `updated_at`은 현재 블록 시간으로 설정됩니다. `issued_at`은 변경할 수 없습니다.
+     * 계약 소유자는 항상 호출할 수 있으며, 토큰별 설정에 따라 토큰 소유자나 발행자도 호출할 수 있습니다.
+     *
+     * near call CONTRACT_ID nft_update_token_metadata '{
+        "token_id": "YOUR_TOKEN_ID",
+        "patch": { "media": "NEW_MEDIA_URL", "media_hash": "NEW_MEDIA_HASH" },
+        "memo": "OPTIONAL_MEMO"
+    }' --accountId YOUR_ACCOUNT_ID --depositYocto 1
+     */
+    #[payable]
+    pub fn nft_update_token_metadata(
+        &mut self,
+        token_id: TokenId,
+        patch: TokenMetadata,
+        memo: Option<String>,
+    ) {
+        // 보안을 위해 최소 1 yoctoNEAR가 첨부되었는지 확인합니다.
+        assert_at_least_one_yocto();
+
+        // 호출자가 토큰의 메타데이터를 업데이트할 수 있는지 확인합니다.
+        let token = self.tokens_by_id.get(&token_id).expect("No token");
+        let caller = env::predecessor_account_id();
+        assert!(
+            self.can_update_token_metadata(&token_id, &token, &caller),
+            "Not allowed to update the token metadata"
+        );
+
+        // 계약에서 사용하는 초기 저장소를 측정합니다.
+        let initial_storage_usage = env::storage_usage();
+
+        // 현재 메타데이터에 전달된 필드를 병합합니다.
+        let mut metadata = self.token_metadata_by_id.get(&token_id).expect("No token metadata");
+        if patch.title.is_some() {
+            metadata.title = patch.title;
+        }
+        if patch.description.is_some() {
+            metadata.description = patch.description;
+        }
+        if patch.media.is_some() {
+            metadata.media = patch.media;
+        }
+        if patch.media_hash.is_some() {
+            metadata.media_hash = patch.media_hash;
+        }
+        if patch.copies.is_some() {
+            metadata.copies = patch.copies;
+        }
+        if patch.expires_at.is_some() {
+            metadata.expires_at = patch.expires_at;
+        }
+        if patch.starts_at.is_some() {
+            metadata.starts_at = patch.starts_at;
+        }
+        if patch.extra.is_some() {
+            metadata.extra = patch.extra;
+        }
+        if patch.reference.is_some() {
+            metadata.reference = patch.reference;
+        }
+        if patch.reference_hash.is_some() {
+            metadata.reference_hash = patch.reference_hash;
+        }
+        // issued_at과 updated_at은 계약이 관리하므로 patch의 값은 무시됩니다.
+        // 마지막 업데이트 시간을 현재 블록 시간(밀리초)으로 설정합니다.
+        metadata.updated_at = Some(U64(env::block_timestamp_ms()));
+
+        // 업데이트된 메타데이터를 저장합니다.
+        self.token_metadata_by_id.insert(&token_id, &metadata);
+
+        // 이벤트 표준에 따라 메타데이터 업데이트 로그를 구성합니다.
+        let nft_metadata_update_log: EventLog = EventLog {
+            // 표준 이름 ("nep171").
+            standard: NFT_STANDARD_NAME.to_string(),
+            // nft_metadata_update 이벤트가 추가된 표준의 버전 ("1.2.0").
+            version: NFT_METADATA_UPDATE_SPEC.to_string(),
+            // 이벤트와 관련된 데이터는 벡터에 저장됩니다.
+            event: EventLogVariant::NftMetadataUpdate(vec![NftMetadataUpdateLog {
+                // 업데이트된 토큰 ID의 벡터.
+                token_ids: vec![token_id.to_string()],
+                // 포함할 선택적 메모.
+                memo,
+            }]),
+        };
+
+        // 직렬화된 json을 로그에 기록합니다.
+        env::log_str(&nft_metadata_update_log.to_string());
+
+        // 메타데이터가 커졌다면 추가 저장소 비용을 청구하고 나머지 첨부 금액은 환불합니다.
+        let final_storage_usage = env::storage_usage();
+        refund_deposit(final_storage_usage.saturating_sub(initial_storage_usage));
+
+        // 메타데이터가 작아졌다면 해제된 저장소를 토큰 소유자에게 환불합니다.
+        // 토큰의 저장소는 소유자의 몫이므로 업데이트 권한이 있는 다른 계정이 가져갈 수 없습니다.
+        let freed_storage_in_bytes = initial_storage_usage.saturating_sub(final_storage_usage);
+        if freed_storage_in_bytes > 0 {
+            Promise::new(token.owner_id).transfer(Balance::from(freed_storage_in_bytes) * env::storage_byte_cost());
+        }
+
+        // 상태 이전에서 변환하지 못한 시각 중 이번 업데이트로 대체된 값을 지웁니다. 저장소 측정이 끝난 뒤에 지우므로 해제된 저장소는 계약에 남습니다.
+        self.internal_clear_unparsed_timestamps(&token_id, patch.expires_at.is_some(), patch.starts_at.is_some());
+    }
+
+    // 계약 소유자 외에 토큰의 메타데이터를 업데이트할 수 있는 계정을 설정합니다. 계약 소유자만 호출할 수 있습니다.
+    // near call CONTRACT_ID nft_set_metadata_updater '{"token_id": "YOUR_TOKEN_ID", "updater": "token_owner"}' --accountId OWNER_ACCOUNT_ID --depositYocto 1
+    #[payable]
+    pub fn nft_set_metadata_updater(&mut self, token_id: TokenId, updater: MetadataUpdater) {
+        // 보안을 위해 최소 1 yoctoNEAR가 첨부되었는지, 호출자가 계약 소유자인지 확인합니다.
+        assert_at_least_one_yocto();
+        self.assert_contract_owner();
+        assert!(self.tokens_by_id.contains_key(&token_id), "No token");
+
+        // 계약에서 사용하는 초기 저장소를 측정합니다.
+        let initial_storage_usage = env::storage_usage();
+
+        // 기본값(계약 소유자만)은 저장하지 않습니다.
+        if updater == MetadataUpdater::ContractOwner {
+            self.metadata_updater_by_token_id.remove(&token_id);
+        } else {
+            self.metadata_updater_by_token_id.insert(&token_id, &updater);
+        }
+
+        // 필요한 저장소 비용을 청구하고 나머지 첨부 금액은 환불합니다.
+        refund_deposit(env::storage_usage().saturating_sub(initial_storage_usage));
+    }
+
+    // 계약 소유자 외에 토큰의 메타데이터를 업데이트할 수 있는 계정을 반환합니다.
+    pub fn nft_metadata_updater(&self, token_id: TokenId) -> MetadataUpdater {
+        self.metadata_updater_by_token_id
+            .get(&token_id)
+            .unwrap_or(MetadataUpdater::ContractOwner)
+    }
+
+    // 토큰이 지금 유효한지(starts_at이 지났고 expires_at이 지나지 않았는지) 확인합니다. 토큰이 없으면 false를 반환합니다.
+    //near view $ID nft_is_valid '{"token_id": "ticket-1"}'
+    pub fn nft_is_valid(&self, token_id: TokenId) -> bool {
+        let now = env::block_timestamp_ms();
+        self.token_metadata_by_id
+            .get(&token_id)
+            .is_some_and(|metadata| metadata.has_started(now) && !metadata.is_expired(now))
+    }
+}
+
+impl Contract {
+    // 주어진 계정이 토큰의 메타데이터를 업데이트할 수 있는지 반환합니다. 계약 소유자는 항상 가능합니다.
+    pub(crate) fn can_update_token_metadata(
+        &self,
+        token_id: &TokenId,
+        token: &Token,
+        account_id: &AccountId,
+    ) -> bool {
+        if account_id == &self.owner_id {
+            return true;
+        }
+        match self.nft_metadata_updater(token_id.clone()) {
+            MetadataUpdater::TokenOwner => account_id == &token.owner_id,
+            MetadataUpdater::Minter => {
+                self.minter_by_token_id.get(token_id).as_ref() == Some(account_id)
+            }
+            MetadataUpdater::ContractOwner => false,
+        }
+    }
+}
@@ -0,0 +1,431 @@
+//계약 상태의 버전 관리와 새 코드 배포 및 상태 이전 기능이 포함되어 있습니다.
+use crate::*;
+use near_sdk::{Gas, GasWeight};
+
+//the key the state version is stored under. Deployments made before state versioning don't have it and are V1
+// 상태 버전이 저장되는 키입니다. 상태 버전 관리가 도입되기 전의 배포에는 이 키가 없으며 V1입니다.
+const STATE_VERSION_KEY: &[u8] = b"STATE_VERSION";
+/// 현재 계약 상태 구조의 버전입니다. Contract나 저장된 항목의 구조가 바뀔 때마다 올리고 VersionedContract에 변환을 추가합니다.
+pub const CURRENT_STATE_VERSION: u8 = 3;
+
+//the key the index of the next entry to migrate is stored under while a migration is in progress
+// 상태 이전이 진행되는 동안 다음에 옮길 항목의 인덱스가 저장되는 키입니다.
+const MIGRATION_CURSOR_KEY: &[u8] = b"MIGRATION_CURSOR";
+/// 한 번의 migrate 호출에서 변환할 수 있는 최대 항목 수입니다.
+pub const MAX_MIGRATION_BATCH_SIZE: u64 = 100;
+
+// migrate 호출에 최소한으로 붙이는 GAS입니다. 사용되지 않은 나머지 GAS도 모두 migrate 호출에 전달됩니다.
+const GAS_FOR_MIGRATE: Gas = Gas(10_000_000_000_000);
+
+//the token metadata layout of the first deployments, which stored the timestamps as strings
+// 첫 배포의 토큰 메타데이터 구조로, 시각을 문자열로 저장했습니다.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct TokenMetadataV1 {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub media: Option<String>,
+    pub media_hash: Option<Base64VecU8>,
+    pub copies: Option<u64>,
+    pub issued_at: Option<String>,
+    pub expires_at: Option<String>,
+    pub starts_at: Option<String>,
+    pub updated_at: Option<String>,
+    pub extra: Option<String>,
+    pub reference: Option<String>,
+    pub reference_hash: Option<Base64VecU8>,
+}
+
+//the timestamps of a token that weren't Unix epoch milliseconds and couldn't be converted by the migration, as they were stored
+// 상태 이전에서 Unix epoch 밀리초가 아니어서 변환할 수 없었던 토큰의 시각으로, 저장되어 있던 그대로입니다.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Default, PartialEq, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct UnparsedTimestamps {
+    pub issued_at: Option<String>,
+    pub expires_at: Option<String>,
+    pub starts_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+impl TokenMetadataV1 {
+    //the timestamps that can't be converted to Unix epoch milliseconds, or None if every timestamp can be converted
+    // Unix epoch 밀리초로 변환할 수 없는 시각입니다. 모든 시각을 변환할 수 있다면 None입니다.
+    fn unparsed_timestamps(&self) -> Option<UnparsedTimestamps> {
+        let unparsed = |timestamp: &Option<String>| {
+            timestamp.clone().filter(|timestamp| timestamp.parse::<u64>().is_err())
+        };
+        let timestamps = UnparsedTimestamps {
+            issued_at: unparsed(&self.issued_at),
+            expires_at: unparsed(&self.expires_at),
+            starts_at: unparsed(&self.starts_at),
+            updated_at: unparsed(&self.updated_at),
+        };
+        if timestamps == UnparsedTimestamps::default() {
+            None
+        } else {
+            Some(timestamps)
+        }
+    }
+}
+
+impl From<TokenMetadataV1> for TokenMetadata {
+    //timestamps that aren't Unix epoch milliseconds can't be converted and are left unset.
+    //the migration keeps their original value in unparsed_timestamps_by_id
+    // Unix epoch 밀리초가 아닌 시각은 변환할 수 없으므로 비워 둡니다. 상태 이전은 원래 값을 unparsed_timestamps_by_id에 보관합니다.
+    fn from(metadata: TokenMetadataV1) -> Self {
+        let parse = |timestamp: Option<String>| timestamp.and_then(|timestamp| timestamp.parse().ok().map(U64));
+        TokenMetadata {
+            title: metadata.title,
+            description: metadata.description,
+            media: metadata.media,
+            media_hash: metadata.media_hash,
+            copies: metadata.copies,
+            issued_at: parse(metadata.issued_at),
+            expires_at: parse(metadata.expires_at),
+            starts_at: parse(metadata.starts_at),
+            updated_at: parse(metadata.updated_at),
+            extra: metadata.extra,
+            reference: metadata.reference,
+            reference_hash: metadata.reference_hash,
+        }
+    }
+}
+
+//the state layout of the first deployments (before approved minters were introduced)
+// 첫 배포(승인된 발행자가 도입되기 전)의 상태 구조입니다.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct ContractV1 {
+    pub owner_id: AccountId,
+    pub tokens_per_owner: LookupMap<AccountId, UnorderedSet<TokenId>>,
+    pub tokens_by_id: LookupMap<TokenId, Token>,
+    pub token_metadata_by_id: UnorderedMap<TokenId, TokenMetadataV1>,
+    pub metadata: LazyOption<NFTContractMetadata>,
+}
+
+//the state layout that introduced state versioning (approved minters, metadata updaters and ownership transfer).
+//token timestamps were still stored as strings
+// 상태 버전 관리가 도입된 상태 구조입니다(승인된 발행자, 메타데이터 업데이트 권한, 소유권 이전). 토큰 시각은 아직 문자열로 저장되었습니다.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct ContractV2 {
+    pub owner_id: AccountId,
+    pub tokens_per_owner: LookupMap<AccountId, UnorderedSet<TokenId>>,
+    pub tokens_by_id: LookupMap<TokenId, Token>,
+    pub token_metadata_by_id: UnorderedMap<TokenId, TokenMetadataV1>,
+    pub metadata: LazyOption<NFTContractMetadata>,
+    pub minter_by_token_id: LookupMap<TokenId, AccountId>,
+    pub metadata_updater_by_token_id: LookupMap<TokenId, MetadataUpdater>,
+    pub approved_minters: UnorderedSet<AccountId>,
+    pub public_minting: bool,
+    pub pending_owner_id: Option<AccountId>,
+}
+
+impl From<ContractV1> for ContractV2 {
+    fn from(state: ContractV1) -> Self {
+        //the contract owner is seeded as the first approved minter and minting stays restricted to approved minters
+        // 계약 소유자가 첫 번째 승인된 발행자로 추가되며, 발행은 승인된 발행자로 제한됩니다.
+        let mut approved_minters =
+            UnorderedSet::new(StorageKey::ApprovedMinters.try_to_vec().unwrap());
+        approved_minters.insert(&state.owner_id);
+
+        ContractV2 {
+            owner_id: state.owner_id,
+            tokens_per_owner: state.tokens_per_owner,
+            tokens_by_id: state.tokens_by_id,
+            token_metadata_by_id: state.token_metadata_by_id,
+            metadata: state.metadata,
+            minter_by_token_id: LookupMap::new(StorageKey::MinterByTokenId.try_to_vec().unwrap()),
+            metadata_updater_by_token_id: LookupMap::new(
+                StorageKey::MetadataUpdaterByTokenId.try_to_vec().unwrap(),
+            ),
+            approved_minters,
+            public_minting: false,
+            pending_owner_id: None,
+        }
+    }
+}
+
+impl From<ContractV2> for Contract {
+    //the token metadata must already have been rewritten with numeric timestamps (see migrate)
+    // 토큰 메타데이터는 이미 숫자 시각으로 다시 쓰여 있어야 합니다(migrate 참고).
+    fn from(state: ContractV2) -> Self {
+        Contract {
+            owner_id: state.owner_id,
+            tokens_per_owner: state.tokens_per_owner,
+            tokens_by_id: state.tokens_by_id,
+            token_metadata_by_id: retype(state.token_metadata_by_id),
+            metadata: state.metadata,
+            minter_by_token_id: state.minter_by_token_id,
+            metadata_updater_by_token_id: state.metadata_updater_by_token_id,
+            approved_minters: state.approved_minters,
+            public_minting: state.public_minting,
+            pending_owner_id: state.pending_owner_id,
+            royalty_cap: DEFAULT_ROYALTY_CAP,
+            soulbound_tokens: LookupSet::new(StorageKey::SoulboundTokens.try_to_vec().unwrap()),
+            paused_features: LookupSet::new(StorageKey::PausedFeatures.try_to_vec().unwrap()),
+            pausers: LookupSet::new(StorageKey::Pausers.try_to_vec().unwrap()),
+            token_type_by_id: LookupMap::new(StorageKey::TokenTypeById.try_to_vec().unwrap()),
+            tokens_per_type: LookupMap::new(StorageKey::TokensPerType.try_to_vec().unwrap()),
+            token_types_locked: LookupSet::new(StorageKey::TokenTypesLocked.try_to_vec().unwrap()),
+            operator_approvals: LookupSet::new(StorageKey::OperatorApprovals.try_to_vec().unwrap()),
+            approval_expires_at: LookupMap::new(StorageKey::ApprovalExpiresAt.try_to_vec().unwrap()),
+            storage_deposits: LookupMap::new(StorageKey::StorageDeposits.try_to_vec().unwrap()),
+            sponsor_pool: SponsorPool::default(),
+            sponsor_relayers: LookupSet::new(StorageKey::SponsorRelayers.try_to_vec().unwrap()),
+            sponsored_per_receiver: LookupMap::new(
+                StorageKey::SponsoredPerReceiver.try_to_vec().unwrap(),
+            ),
+            sponsored_tokens: LookupSet::new(StorageKey::SponsoredTokens.try_to_vec().unwrap()),
+            //filled by migrate_token_metadata while the entries were converted
+            // 항목을 변환하는 동안 migrate_token_metadata가 채웠습니다.
+            unparsed_timestamps_by_id: LookupMap::new(
+                StorageKey::UnparsedTimestampsById.try_to_vec().unwrap(),
+            ),
+        }
+    }
+}
+
+//the map handle only stores its prefix and length, so it can be reinterpreted once its values were rewritten with a new type
+// 맵 핸들은 접두사와 길이만 저장하므로, 값을 새 타입으로 다시 쓴 뒤에는 새 값 타입으로 다시 해석할 수 있습니다.
+fn retype<V, W>(map: UnorderedMap<TokenId, V>) -> UnorderedMap<TokenId, W>
+where
+    V: BorshSerialize + BorshDeserialize,
+    W: BorshSerialize + BorshDeserialize,
+{
+    UnorderedMap::try_from_slice(&map.try_to_vec().unwrap()).unwrap()
+}
+
+//rewrite the metadata of the tokens from the passed in index in place with numeric timestamps, at most `limit` of them.
+//the timestamps that can't be converted are kept in unparsed_timestamps_by_id and the IDs of their tokens are logged.
+//returns the index to continue from
+// 전달된 인덱스부터 최대 `limit`개의 토큰 메타데이터를 숫자 시각으로 제자리에서 다시 씁니다.
+// 변환할 수 없는 시각은 unparsed_timestamps_by_id에 보관하고 해당 토큰 ID를 로그에 기록합니다. 이어서 진행할 인덱스를 반환합니다.
+fn migrate_token_metadata(
+    token_metadata_by_id: &mut UnorderedMap<TokenId, TokenMetadataV1>,
+    from_index: u64,
+    limit: u64,
+) -> u64 {
+    let end = (from_index + limit).min(token_metadata_by_id.len());
+    let page: Vec<(TokenId, TokenMetadataV1)> = (from_index..end)
+        .map(|index| {
+            (
+                token_metadata_by_id.keys_as_vector().get(index).unwrap(),
+                token_metadata_by_id.values_as_vector().get(index).unwrap(),
+            )
+        })
+        .collect();
+    let mut unparsed_timestamps_by_id: LookupMap<TokenId, UnparsedTimestamps> =
+        LookupMap::new(StorageKey::UnparsedTimestampsById.try_to_vec().unwrap());
+    let mut unparsed_token_ids = Vec::new();
+    for (token_id, metadata) in page {
+        if let Some(unparsed_timestamps) = metadata.unparsed_timestamps() {
+            unparsed_timestamps_by_id.insert(&token_id, &unparsed_timestamps);
+            unparsed_token_ids.push(token_id.clone());
+        }
+        token_metadata_by_id.insert_raw(
+            &token_id.try_to_vec().unwrap(),
+            &TokenMetadata::from(metadata).try_to_vec().unwrap(),
+        );
+    }
+    if !unparsed_token_ids.is_empty() {
+        env::log_str(&format!(
+            "Could not convert the timestamps of tokens {}, see nft_unparsed_timestamps",
+            unparsed_token_ids.join(", ")
+        ));
+    }
+    end
+}
+
+//every state layout this contract has been deployed with. The last variant is the current layout
+// 이 계약이 배포되었던 모든 상태 구조입니다. 마지막 변형이 현재 구조입니다.
+pub enum VersionedContract {
+    V1(ContractV1),
+    V2(ContractV2),
+    V3(Box<Contract>),
+}
+
+impl VersionedContract {
+    //read the stored state using the layout of its stored version
+    // 저장된 버전의 구조로 저장된 상태를 읽습니다.
+    pub(crate) fn read() -> Self {
+        let version = env::storage_read(STATE_VERSION_KEY)
+            .map(|version| version[0])
+            .unwrap_or(1);
+        match version {
+            1 => Self::V1(env::state_read().expect("Failed to read old state")),
+            2 => Self::V2(env::state_read().expect("Failed to read old state")),
+            3 => Self::V3(Box::new(env::state_read().expect("Failed to read old state"))),
+            _ => env::panic_str("Unknown state version"),
+        }
+    }
+
+    //the contract owner stored in the state
+    // 상태에 저장된 계약 소유자입니다.
+    pub(crate) fn owner_id(&self) -> &AccountId {
+        match self {
+            Self::V1(state) => &state.owner_id,
+            Self::V2(state) => &state.owner_id,
+            Self::V3(state) => &state.owner_id,
+        }
+    }
+
+    //convert at most `limit` stored entries from the passed in index into the current layout and return the index to
+    //continue from. Only the top-level state is left to convert once it returns the number of entries
+    // 전달된 인덱스부터 최대 `limit`개의 저장된 항목을 현재 구조로 변환하고 이어서 진행할 인덱스를 반환합니다.
+    // 항목 수를 반환하면 최상위 상태만 변환하면 됩니다.
+    fn migrate_entries(&mut self, from_index: u64, limit: u64) -> u64 {
+        match self {
+            Self::V1(state) => migrate_token_metadata(&mut state.token_metadata_by_id, from_index, limit),
+            Self::V2(state) => migrate_token_metadata(&mut state.token_metadata_by_id, from_index, limit),
+            Self::V3(_) => 0,
+        }
+    }
+
+    //the number of stored entries that have to be converted
+    // 변환해야 하는 저장된 항목 수입니다.
+    fn entries_len(&self) -> u64 {
+        match self {
+            Self::V1(state) => state.token_metadata_by_id.len(),
+            Self::V2(state) => state.token_metadata_by_id.len(),
+            Self::V3(_) => 0,
+        }
+    }
+
+    //convert the top-level state into the current layout, one version at a time. Every entry must have been converted
+    // 최상위 상태를 한 버전씩 현재 구조로 변환합니다. 모든 항목이 변환되어 있어야 합니다.
+    fn into_current(self) -> Contract {
+        match self {
+            Self::V1(state) => Self::V2(state.into()).into_current(),
+            Self::V2(state) => state.into(),
+            Self::V3(state) => *state,
+        }
+    }
+}
+
+//record that the stored state uses the current layout
+// 저장된 상태가 현재 구조를 사용한다고 기록합니다.
+pub(crate) fn write_state_version() {
+    env::storage_write(STATE_VERSION_KEY, &[CURRENT_STATE_VERSION]);
+}
+
+#[near_bindgen]
+impl Contract {
+    //migrates the stored state to the current layout, converting at most `limit` stored entries per call so that any
+    //number of tokens fits in the GAS limit. With a limit, call it again until it returns true. Without one, each call
+    //converts a full batch and then calls migrate on this contract for the next one, so a single call (like the one
+    //update_contract makes) converts every entry. If that chain runs out of GAS, calling migrate again resumes it.
+    //the progress is stored under its own key and the state keeps its old layout until every entry was converted, so
+    //every other method fails until then. Migrating a state that already has the current layout changes nothing.
+    //can only be called by the contract owner or by the contract itself (see update_contract).
+    // 저장된 상태를 현재 구조로 옮기며, 토큰 수와 관계없이 GAS 한도 안에 들어오도록 호출마다 최대 `limit`개의 항목만 변환합니다.
+    // `limit`을 전달했다면 true를 반환할 때까지 다시 호출합니다. 전달하지 않으면 각 호출이 한 묶음을 변환한 뒤 다음 묶음을 위해
+    // 이 계약의 migrate를 호출하므로, update_contract의 호출처럼 한 번의 호출로 모든 항목이 변환됩니다. 그 사이에 GAS가
+    // 부족해지면 migrate를 다시 호출해 이어서 진행합니다. 진행 상황은 별도의 키에 저장되며 모든 항목이 변환될 때까지 상태는
+    // 이전 구조로 남아 있으므로, 그때까지 다른 모든 메서드는 실패합니다. 이미 현재 구조인 상태는 바뀌지 않습니다.
+    // 계약 소유자나 계약 자신(update_contract 참고)만 호출할 수 있습니다.
+    //near call $ID migrate '{"limit": 100}' --accountId $OWNER --gas 300000000000000
+    pub fn migrate(limit: Option<u64>) -> PromiseOrValue<bool> {
+        //read the old state. If it can't be read, panic
+        // 이전 상태를 읽습니다. 읽을 수 없으면 패닉이 발생합니다.
+        let mut state = VersionedContract::read();
+
+        let predecessor_id = env::predecessor_account_id();
+        assert!(
+            &predecessor_id == state.owner_id() || predecessor_id == env::current_account_id(),
+            "only contract owner"
+        );
+
+        //convert the next batch of entries, continuing where the previous call stopped
+        // 이전 호출이 멈춘 곳부터 다음 항목들을 변환합니다.
+        let from_index = env::storage_read(MIGRATION_CURSOR_KEY)
+            .map(|cursor| u64::try_from_slice(&cursor).unwrap())
+            .unwrap_or(0);
+        let batch_size = limit.unwrap_or(MAX_MIGRATION_BATCH_SIZE).min(MAX_MIGRATION_BATCH_SIZE);
+        let next_index = state.migrate_entries(from_index, batch_size);
+        if next_index < state.entries_len() {
+            env::storage_write(MIGRATION_CURSOR_KEY, &next_index.try_to_vec().unwrap());
+            env::log_str(&format!("Migrated {} of {} entries", next_index, state.entries_len()));
+            //without a limit the next batch is converted by another call, so the whole migration finishes in one go
+            // limit이 없으면 다음 묶음을 다른 호출에서 변환하므로 상태 이전 전체가 한 번에 끝납니다.
+            if limit.is_none() {
+                return PromiseOrValue::Promise(call_migrate(Promise::new(env::current_account_id())));
+            }
+            return PromiseOrValue::Value(false);
+        }
+
+        //every entry is converted, so the top-level state can be switched to the current layout
+        // 모든 항목이 변환되었으므로 최상위 상태를 현재 구조로 바꿀 수 있습니다.
+        env::storage_remove(MIGRATION_CURSOR_KEY);
+        env::state_write(&state.into_current());
+        write_state_version();
+        PromiseOrValue::Value(true)
+    }
+
+    //the timestamps of a token that couldn't be converted to Unix epoch milliseconds when the state was migrated,
+    //as they were stored. Returns None if every timestamp of the token was converted
+    // 상태 이전 때 Unix epoch 밀리초로 변환할 수 없었던 토큰의 시각을 저장되어 있던 그대로 반환합니다.
+    // 토큰의 모든 시각이 변환되었다면 None을 반환합니다.
+    //near view $ID nft_unparsed_timestamps '{"token_id": "ticket-1"}'
+    pub fn nft_unparsed_timestamps(&self, token_id: TokenId) -> Option<UnparsedTimestamps> {
+        self.unparsed_timestamps_by_id.get(&token_id)
+    }
+}
+
+impl Contract {
+    //forgets the unparsed timestamps that a metadata update replaced. updated_at is always replaced, expires_at and
+    //starts_at only if the update set them, and issued_at can't be updated. The migration wrote these entries at the
+    //contract's cost, so callers do this outside of their measured storage window and the freed bytes stay with the contract
+    // 메타데이터 업데이트로 대체된 변환되지 않은 시각을 지웁니다. updated_at은 항상 대체되고, expires_at과 starts_at은
+    // 업데이트에서 설정했을 때만 대체되며, issued_at은 업데이트할 수 없습니다. 이 항목은 상태 이전이 계약의 비용으로 저장했으므로
+    // 호출자는 저장소 측정 범위 밖에서 이 함수를 호출하고, 해제된 저장소는 계약에 남습니다.
+    pub(crate) fn internal_clear_unparsed_timestamps(
+        &mut self,
+        token_id: &TokenId,
+        expires_at_updated: bool,
+        starts_at_updated: bool,
+    ) {
+        let mut timestamps = match self.unparsed_timestamps_by_id.get(token_id) {
+            Some(timestamps) => timestamps,
+            None => return,
+        };
+        timestamps.updated_at = None;
+        if expires_at_updated {
+            timestamps.expires_at = None;
+        }
+        if starts_at_updated {
+            timestamps.starts_at = None;
+        }
+
+        if timestamps == UnparsedTimestamps::default() {
+            self.unparsed_timestamps_by_id.remove(token_id);
+        } else {
+            self.unparsed_timestamps_by_id.insert(token_id, &timestamps);
+        }
+    }
+
+    //deploys the wasm passed as the raw input of this call to the contract account and runs migrate without a limit in the
+    //same batch, which keeps calling itself until every entry is converted. If the first call fails the whole batch,
+    //including the deployment, is reverted. Attach enough GAS for every batch, or call migrate again if it runs out.
+    // 이 호출의 입력으로 전달된 wasm을 계약 계정에 배포하고 같은 배치에서 limit 없이 migrate를 호출하며, migrate는 모든 항목이
+    // 변환될 때까지 자신을 계속 호출합니다. 첫 번째 호출이 실패하면 배포를 포함한 배치 전체가 되돌려집니다.
+    // 모든 묶음에 충분한 GAS를 첨부하고, 부족했다면 migrate를 다시 호출합니다.
+    //near call $ID update_contract --base64 $(base64 -w0 out/main.wasm) --accountId $OWNER --gas 300000000000000
+    pub fn update_contract(&self) -> Promise {
+        self.assert_contract_owner();
+
+        // 입력으로 전달된 새 코드를 가져옵니다.
+        let code = env::input().expect("No code was attached");
+
+        call_migrate(Promise::new(env::current_account_id()).deploy_contract(code)).as_return()
+    }
+}
+
+//adds a migrate call without a limit to the passed in promise, forwarding all the unused GAS to it
+// 전달된 promise에 limit 없는 migrate 호출을 추가하며, 사용되지 않은 GAS를 모두 전달합니다.
+fn call_migrate(promise: Promise) -> Promise {
+    promise.function_call_weight(
+        "migrate".to_string(),
+        b"{}".to_vec(),
+        0,
+        GAS_FOR_MIGRATE,
+        GasWeight(1),
+    )
+}
@@ -1,10 +1,31 @@
 // 토큰 발행 논리가 포함되어 있습니다.
 use crate::*;
 
+/// 한 번의 호출로 발행할 수 있는 최대 토큰 수입니다. GAS 한도 안에서 처리할 수 있도록 제한합니다.
+pub const MAX_BATCH_MINT_SIZE: usize = 100;
+
+// 일괄 발행 시 발행할 토큰 하나의 정보입니다.
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MintArgs {
+    // 발행할 토큰 ID
+    pub token_id: TokenId,
+    // 토큰의 메타데이터
+    pub metadata: TokenMetadata,
+    // 토큰을 받을 계정
+    pub receiver_id: AccountId,
+    // 영구 로열티를 위한 선택적 매개변수
+    pub perpetual_royalties: Option<HashMap<AccountId, u32>>,
+    // true이면 토큰을 양도할 수 없습니다 (soulbound)
+    pub soulbound: Option<bool>,
+    // 토큰의 유형 (선택 사항)
+    pub token_type: Option<TokenType>,
+}
+
 #[near_bindgen]
 impl Contract {
     /**
-     * 
+     *
      * near call CONTRACT_ID nft_mint '{
         "token_id": "YOUR_TOKEN_ID",
         "metadata": {
@@ -17,7 +38,9 @@ impl Contract {
             "ACCOUNT_ID_1": ROYALTY_PERCENTAGE_1,
             "ACCOUNT_ID_2": ROYALTY_PERCENTAGE_2,
             ...
-        }
+        },
+        "soulbound": OPTIONAL_SOULBOUND_FLAG,
+        "token_type": "OPTIONAL_TOKEN_TYPE"
     }' --accountId YOUR_ACCOUNT_ID --amount SOME_AMOUNT
      */
     #[payable]
@@ -29,46 +52,27 @@ impl Contract {
         receiver_id: AccountId,
         // 영구 로열티를 위한 선택적 매개변수 추가
         perpetual_royalties: Option<HashMap<AccountId, u32>>,
+        // true이면 토큰을 양도할 수 없는 soulbound 토큰으로 발행합니다.
+        soulbound: Option<bool>,
+        // 토큰의 유형 (선택 사항)
+        token_type: Option<TokenType>,
     ) {
+        // 발행이 일시 정지되지 않았고 호출자가 토큰을 발행할 수 있는지 확인합니다.
+        self.assert_not_paused(PauseFeature::Mint);
+        self.assert_can_mint();
+
         // 계약에서 사용하는 초기 저장소를 측정합니다.
         let initial_storage_usage = env::storage_usage();
 
-        // 토큰에 저장할 로열티 맵을 생성합니다.
-        let mut royalty = HashMap::new();
-
-        // 영구 로열티가 함수에 전달되면:
-        if let Some(perpetual_royalties) = perpetual_royalties {
-            // 영구 로열티의 길이가 7 미만인지 확인합니다. 그렇지 않으면 많은 사람들에게 GAS를 지급할 수 없습니다.
-            assert!(perpetual_royalties.len() < 7, "Cannot add more than 6 perpetual royalty amounts");
-            // 영구 로열티를 반복하고 로열티 맵에 계정과 금액을 삽입합니다.
-            for (account, amount) in perpetual_royalties {
-                royalty.insert(account, amount);
-            }
-        }
-
-        // 소유자 ID를 포함하는 토큰 구조를 지정합니다.
-        let token = Token {
-            // 함수에 전달된 수신자 ID와 동일한 소유자 ID를 설정합니다.
-            owner_id: receiver_id,
-            // 승인된 계정 ID를 기본값(빈 맵)으로 설정합니다.
-            approved_account_ids: Default::default(),
-            // 다음 승인 ID를 0으로 설정합니다.
-            next_approval_id: 0,
-            // 토큰의 영구 로열티 맵 (소유자는 100% - 총 영구 로열티를 받게 됩니다.)
-            royalty,
-        };
-
-        // 토큰 ID와 토큰 구조를 삽입하고 토큰이 이미 존재하지 않는지 확인합니다.
-        assert!(
-            self.tokens_by_id.insert(&token_id, &token).is_none(),
-            "Token already exists"
-        );
-
-        // 토큰 ID와 메타데이터를 삽입합니다.
-        self.token_metadata_by_id.insert(&token_id, &metadata);
-
-        // 소유자에게 토큰을 추가하기 위한 내부 메서드를 호출합니다.
-        self.internal_add_token_to_owner(&token.owner_id, &token_id);
+        // 토큰과 메타데이터를 저장하고 소유자에게 토큰을 추가합니다. 토큰이 이미 존재하면 패닉이 발생합니다.
+        self.internal_mint(MintArgs {
+            token_id: token_id.clone(),
+            metadata,
+            receiver_id: receiver_id.clone(),
+            perpetual_royalties,
+            soulbound,
+            token_type,
+        });
 
         // 이벤트 표준에 따라 발행 로그를 구성합니다.
         let nft_mint_log: EventLog = EventLog {
@@ -79,7 +83,7 @@ impl Contract {
             // 이벤트와 관련된 데이터는 벡터에 저장됩니다.
             event: EventLogVariant::NftMint(vec![NftMintLog {
                 // 토큰의 소유자.
-                owner_id: token.owner_id.to_string(),
+                owner_id: receiver_id.to_string(),
                 // 발행된 토큰 ID의 벡터.
                 token_ids: vec![token_id.to_string()],
                 // 포함할 선택적 메모.
@@ -93,7 +97,110 @@ impl Contract {
         // 사용된 저장소 - 초기 저장소로 필요한 저장소를 계산합니다.
         let required_storage_in_bytes = env::storage_usage() - initial_storage_usage;
 
-        // 사용자가 너무 많이 첨부한 경우 초과 저장소를 환불합니다. 충분한 금액을 첨부하지 않아 필요한 경우에는 패닉이 발생합니다.
-        refund_deposit(required_storage_in_bytes);
+        // 화이트리스트 릴레이어가 아무것도 첨부하지 않았다면 후원 풀에서 비용을 차감합니다.
+        // 그 외에는 초과 저장소를 환불하고, 첨부한 금액이 부족하면 저장소 잔액에서 차감하며, 그래도 부족하면 패닉이 발생합니다.
+        self.charge_mint_storage(vec![(receiver_id, token_id, required_storage_in_bytes)]);
+    }
+
+    /**
+     * 여러 토큰을 한 번의 호출로 발행합니다. 하나라도 실패하면 전체 호출이 되돌려집니다.
+     *
+     * near call CONTRACT_ID nft_batch_mint '{
+        "tokens": [
+            {
+                "token_id": "TOKEN_ID_1",
+                "metadata": { "title": "TOKEN_TITLE_1" },
+                "receiver_id": "RECEIVER_ACCOUNT_ID_1",
+                "perpetual_royalties": { "ACCOUNT_ID_1": ROYALTY_PERCENTAGE_1 }
+            },
+            {
+                "token_id": "TOKEN_ID_2",
+                "metadata": { "title": "TOKEN_TITLE_2" },
+                "receiver_id": "RECEIVER_ACCOUNT_ID_2"
+            }
+        ]
+    }' --accountId YOUR_ACCOUNT_ID --amount SOME_AMOUNT
+     */
+    #[payable]
+    pub fn nft_batch_mint(&mut self, tokens: Vec<MintArgs>) {
+        // 발행이 일시 정지되지 않았고 호출자가 토큰을 발행할 수 있는지 확인합니다.
+        self.assert_not_paused(PauseFeature::Mint);
+        self.assert_can_mint();
+
+        // 발행할 토큰이 하나 이상이고 최대 개수를 넘지 않는지 확인합니다.
+        assert!(!tokens.is_empty(), "Must mint at least one token");
+        assert!(
+            tokens.len() <= MAX_BATCH_MINT_SIZE,
+            "Cannot mint more than {} tokens at once",
+            MAX_BATCH_MINT_SIZE
+        );
+
+        // 저장소를 사용하기 전에 모든 항목을 먼저 검증합니다.
+        let mut seen_token_ids = HashSet::new();
+        for args in tokens.iter() {
+            // 같은 호출 안에서 토큰 ID가 중복되지 않는지 확인합니다.
+            assert!(
+                seen_token_ids.insert(&args.token_id),
+                "Duplicate token ID {} in batch",
+                args.token_id
+            );
+            // 토큰 ID가 이미 발행되지 않았는지 확인합니다.
+            assert!(
+                !self.tokens_by_id.contains_key(&args.token_id),
+                "Token {} already exists",
+                args.token_id
+            );
+            // 영구 로열티를 지급할 수 있는지 확인합니다.
+            if let Some(perpetual_royalties) = args.perpetual_royalties.as_ref() {
+                self.assert_valid_royalty(perpetual_royalties);
+            }
+        }
+
+        // 후원 풀의 수신자별 상한을 적용할 수 있도록 토큰마다 사용된 저장소를 수신자, 토큰 ID와 함께 기록합니다.
+        let mut storage_by_token: Vec<(AccountId, TokenId, u64)> = Vec::new();
+
+        // 소유자별로 발행된 토큰 ID를 모읍니다. 로그의 순서는 소유자가 처음 나타난 순서를 따릅니다.
+        let mut mint_logs: Vec<NftMintLog> = Vec::new();
+
+        for args in tokens {
+            let token_id = args.token_id.clone();
+            let receiver_id = args.receiver_id.clone();
+
+            // 토큰과 메타데이터를 저장하고 소유자에게 토큰을 추가합니다.
+            let initial_storage_usage = env::storage_usage();
+            self.internal_mint(args);
+            storage_by_token.push((
+                receiver_id.clone(),
+                token_id.clone(),
+                env::storage_usage() - initial_storage_usage,
+            ));
+
+            // 해당 소유자의 로그가 이미 있다면 토큰 ID를 추가하고, 없다면 새 로그를 만듭니다.
+            if let Some(log) = mint_logs.iter_mut().find(|log| log.owner_id == receiver_id.as_str()) {
+                log.token_ids.push(token_id);
+            } else {
+                mint_logs.push(NftMintLog {
+                    owner_id: receiver_id.to_string(),
+                    token_ids: vec![token_id],
+                    memo: None,
+                });
+            }
+        }
+
+        // 이벤트 표준에 따라 모든 발행을 하나의 로그로 구성합니다.
+        let nft_mint_log: EventLog = EventLog {
+            // 표준 이름 ("nep171").
+            standard: NFT_STANDARD_NAME.to_string(),
+            // 표준의 버전 ("nft-1.0.0").
+            version: NFT_METADATA_SPEC.to_string(),
+            // 소유자별로 묶인 발행 데이터입니다.
+            event: EventLogVariant::NftMint(mint_logs),
+        };
+
+        // 직렬화된 json을 로그에 기록합니다.
+        env::log_str(&nft_mint_log.to_string());
+
+        // 전체 일괄 발행의 저장소 비용을 한 번에 지불합니다. 충분히 지불할 수 없다면 패닉이 발생하고 모든 발행이 되돌려집니다.
+        self.charge_mint_storage(storage_by_token);
     }
 }
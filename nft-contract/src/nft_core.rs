@@ -4,6 +4,8 @@ use near_sdk::{ext_contract, Gas, PromiseResult};
 
 const GAS_FOR_RESOLVE_TRANSFER: Gas = Gas(10_000_000_000_000);
 const GAS_FOR_NFT_ON_TRANSFER: Gas = Gas(25_000_000_000_000);
+/// 한 번의 호출로 전송할 수 있는 최대 토큰 수입니다. GAS 한도 안에서 처리할 수 있도록 제한합니다.
+pub const MAX_BATCH_TRANSFER_SIZE: usize = 100;
 
 pub trait NonFungibleTokenCore {
     //transfers an NFT to a receiver ID
@@ -62,7 +64,7 @@ trait NonFungibleTokenReceiver {
     수신자 계약에 대한 교차 계약 통화 promise 을 해결합니다
     이는 본 계약서에 저장되어 있으며, nft_transfer_call 메서드의 일부로 nft_on_transfer가 호출되었을 때 교차 계약 호출에서 무슨 일이 있었는지 분석하기 위한 것입니다
 */ 
-trait NonFungibleTokenResolver {
+pub trait NonFungibleTokenResolver {
     // nft_transfer_call 메서드에서 nft_on_transfer를 호출할 때 교차 계약 호출을 해결합니다.
     // 토큰이 receiver_id에게 성공적으로 전송되었는지 여부를 반환합니다. 
     fn nft_resolve_transfer(
@@ -120,6 +122,9 @@ impl NonFungibleTokenCore for Contract {
             previous_token.owner_id.clone(),
             &previous_token.approved_account_ids,
         );
+        //the expiries of those approvals are gone as well
+        // 해당 승인의 만료 시각도 제거합니다.
+        self.internal_remove_approval_expiries(&token_id, previous_token.owner_id, |_| true);
     }
 
     //implementation of the transfer call method. This will transfer the NFT and call a method on the receiver_id contract
@@ -200,6 +205,15 @@ impl NonFungibleTokenCore for Contract {
             //we'll get the metadata for that token
             // 해당 토큰의 메타데이터를 가져옵니다.
             let metadata = self.token_metadata_by_id.get(&token_id).unwrap();
+            //and whether the token is soulbound
+            // 토큰이 soulbound인지도 확인합니다.
+            let soulbound = self.soulbound_tokens.contains(&token_id);
+            //and its type
+            // 토큰의 유형도 가져옵니다.
+            let token_type = self.token_type_by_id.get(&token_id);
+            //and whether it has expired
+            // 토큰이 만료되었는지도 확인합니다.
+            let expired = metadata.is_expired(env::block_timestamp_ms());
             //we return the JsonToken (wrapped by Some since we return an option)
             // JsonToken을 반환합니다(Option으로 감싸서 반환합니다).
             Some(JsonToken {
@@ -208,6 +222,9 @@ impl NonFungibleTokenCore for Contract {
                 metadata,
                 approved_account_ids: token.approved_account_ids,
                 royalty: token.royalty,
+                soulbound,
+                token_type,
+                expired,
             })
         } else { //if there wasn't a token ID in the tokens_by_id collection, we return None
                 // 토큰 ID가 tokens_by_id 컬렉션에 없으면 None을 반환합니다.
@@ -256,7 +273,17 @@ impl NonFungibleTokenResolver for Contract {
                     */
                     //we refund the owner for releasing the storage used up by the approved account IDs
                     //승인된 계정 ID에 의해 사용된 스토리지를 출고할 경우 소유자에게 환불해 드립니다
-                    refund_approved_account_ids(owner_id, &approved_account_ids);
+                    refund_approved_account_ids(owner_id.clone(), &approved_account_ids);
+                    //only the approvals the receiver has given since keep their expiries
+                    // 수신자가 그 이후에 준 승인만 만료 시각을 유지합니다.
+                    let current_approvals = self
+                        .tokens_by_id
+                        .get(&token_id)
+                        .map(|token| token.approved_account_ids)
+                        .unwrap_or_default();
+                    self.internal_remove_approval_expiries(&token_id, owner_id, |id| {
+                        !current_approvals.values().any(|approval_id| approval_id == id)
+                    });
                     return true;
                 }
             }
@@ -268,7 +295,10 @@ impl NonFungibleTokenResolver for Contract {
             if token.owner_id != receiver_id {
                 //we refund the owner for releasing the storage used up by the approved account IDs
                 //승인된 계정 ID에 의해 사용된 스토리지를 출고할 경우 소유자에게 환불해 드립니다
-                refund_approved_account_ids(owner_id, &approved_account_ids);
+                refund_approved_account_ids(owner_id.clone(), &approved_account_ids);
+                self.internal_remove_approval_expiries(&token_id, owner_id, |id| {
+                    !token.approved_account_ids.values().any(|approval_id| approval_id == id)
+                });
                 // The token is not owner by the receiver anymore. Can't return it.
                 // 토큰이 더 이상 receiver에게 소유되어 있지 않습니다. 반환할 수 없습니다.
                 return true;
@@ -297,9 +327,12 @@ impl NonFungibleTokenResolver for Contract {
         //we refund the receiver any approved account IDs that they may have set on the token
         // receiver에게 토큰에 설정한 승인된 계정 ID를 환불합니다.
         refund_approved_account_ids(receiver_id.clone(), &token.approved_account_ids);
-        //reset the approved account IDs to what they were before the transfer
-        // 전송 전 승인 상태로 다시 설정합니다.
+        //reset the approved account IDs to what they were before the transfer, along with their expiries
+        // 전송 전 승인 상태와 만료 시각으로 다시 설정합니다.
         token.approved_account_ids = approved_account_ids;
+        self.internal_remove_approval_expiries(&token_id, receiver_id.clone(), |id| {
+            !token.approved_account_ids.values().any(|approval_id| approval_id == id)
+        });
 
         //we inset the token back into the tokens_by_id collection
         // tokens_by_id 컬렉션에 토큰을 다시 삽입합니다.
@@ -338,4 +371,107 @@ impl NonFungibleTokenResolver for Contract {
         //return false
         false
     }
-}
\ No newline at end of file
+}
+
+#[near_bindgen]
+impl Contract {
+    //transfers several NFTs of the same owner to a receiver ID. If any transfer fails, the whole call is reverted.
+    // 같은 소유자의 여러 NFT를 수신자 ID에게 전송합니다. 하나라도 실패하면 전체 호출이 되돌려집니다.
+    /**
+     * near call CONTRACT_ID nft_batch_transfer '{"receiver_id": "RECEIVER_ACCOUNT_ID", "token_ids": ["TOKEN_ID_1", "TOKEN_ID_2"], "memo": "OPTIONAL_MEMO"}'
+     * --accountId YOUR_ACCOUNT_ID --depositYocto 1
+     */
+    #[payable]
+    pub fn nft_batch_transfer(
+        &mut self,
+        receiver_id: AccountId,
+        token_ids: Vec<TokenId>,
+        memo: Option<String>,
+    ) {
+        //assert that the user attached exactly 1 yoctoNEAR for security reasons
+        // 보안을 위해 사용자가 정확히 1 yoctoNEAR를 첨부했는지 확인합니다.
+        assert_one_yocto();
+
+        // 전송할 토큰이 하나 이상이고 최대 개수를 넘지 않는지 확인합니다.
+        assert!(!token_ids.is_empty(), "Must transfer at least one token");
+        assert!(
+            token_ids.len() <= MAX_BATCH_TRANSFER_SIZE,
+            "Cannot transfer more than {} tokens at once",
+            MAX_BATCH_TRANSFER_SIZE
+        );
+        let mut seen_token_ids = HashSet::new();
+        for token_id in token_ids.iter() {
+            assert!(seen_token_ids.insert(token_id), "Duplicate token ID {} in batch", token_id);
+        }
+
+        //get the sender ID
+        // 발신자 ID를 가져옵니다.
+        let sender_id = env::predecessor_account_id();
+
+        //move every token and keep track of the approvals that were reset along the way
+        // 모든 토큰을 옮기고 그 과정에서 초기화된 승인을 추적합니다.
+        let mut old_owner_id: Option<AccountId> = None;
+        let mut approved_account_ids: Vec<AccountId> = Vec::new();
+        let mut expiry_storage_freed: u64 = 0;
+        for token_id in token_ids.iter() {
+            let previous_token = self.internal_move_token(&sender_id, &receiver_id, token_id, None);
+
+            //all tokens must come from the same owner so that they fit in one transfer log
+            // 하나의 전송 로그에 담을 수 있도록 모든 토큰은 같은 소유자의 것이어야 합니다.
+            let owner_id = old_owner_id.get_or_insert_with(|| previous_token.owner_id.clone());
+            assert_eq!(owner_id, &previous_token.owner_id, "All tokens must have the same owner");
+
+            approved_account_ids.extend(previous_token.approved_account_ids.into_keys());
+            //the expiries of those approvals are gone as well. Only their removal is measured, since the
+            //bytes of the approvals themselves are refunded below
+            // 해당 승인의 만료 시각도 제거합니다. 승인 자체의 바이트는 아래에서 환불되므로 만료 시각의 제거만 측정합니다.
+            let initial_storage_usage = env::storage_usage();
+            self.approval_expires_at.remove(token_id);
+            expiry_storage_freed += initial_storage_usage.saturating_sub(env::storage_usage());
+        }
+        let old_owner_id = old_owner_id.unwrap();
+
+        //refund the previous owner once for the approvals and their expiries
+        // 승인과 만료 시각에 사용된 저장소를 이전 소유자에게 한 번에 환불합니다.
+        let storage_released: u64 = approved_account_ids
+            .iter()
+            .map(bytes_for_approved_account_id)
+            .sum::<u64>()
+            + expiry_storage_freed;
+        if storage_released > 0 {
+            Promise::new(old_owner_id.clone())
+                .transfer(Balance::from(storage_released) * env::storage_byte_cost());
+        }
+
+        //if there was some memo attached, we log it.
+        // 메모가 첨부되어 있다면 로그에 기록합니다.
+        if let Some(memo) = memo.as_ref() {
+            env::log_str(&format!("Memo: {}", memo));
+        }
+
+        //if the sender isn't the owner of the tokens, we set the authorized ID equal to the sender.
+        // 발신자가 토큰의 소유자가 아닌 경우 authorized_id를 발신자로 설정합니다.
+        let authorized_id = if sender_id != old_owner_id {
+            Some(sender_id.to_string())
+        } else {
+            None
+        };
+
+        // Construct a single transfer log for every token as per the events standard.
+        // 이벤트 표준에 따라 모든 토큰에 대한 하나의 전송 로그를 구성합니다.
+        let nft_transfer_log: EventLog = EventLog {
+            standard: NFT_STANDARD_NAME.to_string(),
+            version: NFT_METADATA_SPEC.to_string(),
+            event: EventLogVariant::NftTransfer(vec![NftTransferLog {
+                authorized_id,
+                old_owner_id: old_owner_id.to_string(),
+                new_owner_id: receiver_id.to_string(),
+                token_ids,
+                memo,
+            }]),
+        };
+
+        // Log the serialized json.
+        env::log_str(&nft_transfer_log.to_string());
+    }
+}
@@ -0,0 +1,91 @@
+//토큰 소유자가 자신의 모든 토큰을 대신 전송할 수 있는 운영자를 승인하는 기능이 포함되어 있습니다.
+//토큰마다 nft_approve를 호출하지 않고도 한 번의 승인으로 운영자가 소유자의 모든 토큰을 전송하거나 nft_transfer_payout으로 정산할 수 있습니다.
+//운영자 승인은 nft_on_approve를 호출하지 않으므로, 마켓에 판매를 등록하려면 여전히 토큰마다 nft_approve가 필요합니다.
+use crate::*;
+use crate::owner::log_extension_event;
+
+#[near_bindgen]
+impl Contract {
+    //approve an account to transfer every token of the caller, including the tokens they receive later.
+    //the caller pays for the storage used by the approval.
+    // 호출자가 나중에 받는 토큰을 포함하여 호출자의 모든 토큰을 전송할 수 있도록 계정을 승인합니다.
+    // 호출자는 승인에 사용된 저장소 비용을 지불합니다.
+    /**
+     * near call CONTRACT_ID nft_approve_operator '{"account_id": "OPERATOR_ACCOUNT_ID"}'
+     * --accountId YOUR_ACCOUNT_ID --amount ATTACHED_AMOUNT
+     */
+    #[payable]
+    pub fn nft_approve_operator(&mut self, account_id: AccountId) {
+        //assert at least one yocto for security reasons and to pay for storage
+        // 보안과 저장소 비용 지불을 위해 적어도 1 yocto가 첨부되었는지 확인합니다.
+        assert_at_least_one_yocto();
+
+        let owner_id = env::predecessor_account_id();
+        assert_ne!(owner_id, account_id, "Cannot approve yourself as an operator");
+
+        //measure the initial storage being used on the contract
+        // 계약에서 사용하는 초기 저장소를 측정합니다.
+        let initial_storage_usage = env::storage_usage();
+
+        //only log when the operator wasn't approved yet
+        // 운영자가 아직 승인되지 않은 경우에만 로그를 기록합니다.
+        if self.operator_approvals.insert(&(owner_id.clone(), account_id.clone())) {
+            log_extension_event(EventLogVariant::OperatorApprove(vec![OperatorLog {
+                owner_id: owner_id.to_string(),
+                operator_id: account_id.to_string(),
+                memo: None,
+            }]));
+        }
+
+        //refund any excess storage attached by the user. If the user didn't attach enough, the rest is drawn from their storage balance.
+        // 사용자가 첨부한 초과 저장소를 환불합니다. 충분히 첨부하지 않았다면 나머지는 저장소 잔액에서 차감됩니다.
+        let storage_used = env::storage_usage() - initial_storage_usage;
+        self.charge_storage(storage_used);
+    }
+
+    //revoke an operator of the caller. The freed storage is refunded to the caller.
+    // 호출자의 운영자 승인을 취소합니다. 해제된 저장소는 호출자에게 환불됩니다.
+    /**
+     * near call CONTRACT_ID nft_revoke_operator '{"account_id": "OPERATOR_ACCOUNT_ID"}'
+     * --accountId YOUR_ACCOUNT_ID --depositYocto 1
+     */
+    #[payable]
+    pub fn nft_revoke_operator(&mut self, account_id: AccountId) {
+        //assert that the user attached exactly 1 yoctoNEAR for security reasons
+        // 보안을 위해 사용자가 정확히 1 yoctoNEAR를 첨부했는지 확인합니다.
+        assert_one_yocto();
+
+        let owner_id = env::predecessor_account_id();
+        let initial_storage_usage = env::storage_usage();
+
+        if self.operator_approvals.remove(&(owner_id.clone(), account_id.clone())) {
+            //refund the storage released by the approval to the owner, who paid for it
+            // 승인이 해제한 저장소를 비용을 지불한 소유자에게 환불합니다.
+            let freed_storage_in_bytes = initial_storage_usage.saturating_sub(env::storage_usage());
+            if freed_storage_in_bytes > 0 {
+                Promise::new(owner_id.clone())
+                    .transfer(Balance::from(freed_storage_in_bytes) * env::storage_byte_cost());
+            }
+
+            log_extension_event(EventLogVariant::OperatorRevoke(vec![OperatorLog {
+                owner_id: owner_id.to_string(),
+                operator_id: account_id.to_string(),
+                memo: None,
+            }]));
+        }
+    }
+
+    //check if an account is an operator of the passed in owner
+    // 계정이 전달된 소유자의 운영자인지 확인합니다.
+    pub fn nft_is_operator(&self, owner_id: AccountId, account_id: AccountId) -> bool {
+        self.is_operator(&owner_id, &account_id)
+    }
+}
+
+impl Contract {
+    //check if an account can transfer every token of the owner
+    // 계정이 소유자의 모든 토큰을 전송할 수 있는지 확인합니다.
+    pub(crate) fn is_operator(&self, owner_id: &AccountId, account_id: &AccountId) -> bool {
+        self.operator_approvals.contains(&(owner_id.clone(), account_id.clone()))
+    }
+}
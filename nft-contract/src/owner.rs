@@ -0,0 +1,187 @@
+//계약 소유자가 발행 권한과 계약 소유권을 관리하는 기능이 포함되어 있습니다.
+use crate::*;
+
+#[near_bindgen]
+impl Contract {
+    /// Add a specified account as an approved minter
+    /// 지정된 계정을 승인된 발행자로 추가합니다.
+    pub fn add_approved_minter(&mut self, account_id: AccountId) {
+        self.assert_contract_owner();
+        //only log when the minter set actually changed
+        // 발행자 세트가 실제로 바뀐 경우에만 로그를 기록합니다.
+        if self.approved_minters.insert(&account_id) {
+            log_extension_event(EventLogVariant::MinterAdd(vec![MinterUpdateLog {
+                account_ids: vec![account_id.to_string()],
+                memo: None,
+            }]));
+        }
+    }
+
+    /// Remove a specified account as an approved minter
+    /// 지정된 계정을 승인된 발행자에서 제거합니다.
+    pub fn remove_approved_minter(&mut self, account_id: AccountId) {
+        self.assert_contract_owner();
+        //only log when the minter set actually changed
+        // 발행자 세트가 실제로 바뀐 경우에만 로그를 기록합니다.
+        if self.approved_minters.remove(&account_id) {
+            log_extension_event(EventLogVariant::MinterRemove(vec![MinterUpdateLog {
+                account_ids: vec![account_id.to_string()],
+                memo: None,
+            }]));
+        }
+    }
+
+    /// Check if a specified account is an approved minter
+    /// 지정된 계정이 승인된 발행자인지 확인합니다.
+    pub fn is_approved_minter(&self, account_id: AccountId) -> bool {
+        self.approved_minters.contains(&account_id)
+    }
+
+    /// Paginate through the approved minters
+    /// 승인된 발행자 목록을 페이징하여 반환합니다.
+    //near view $ID get_approved_minters '{"from_index": "0", "limit": 50}'
+    pub fn get_approved_minters(&self, from_index: Option<U128>, limit: Option<u64>) -> Vec<AccountId> {
+        //where to start pagination - if we have a from_index, we'll use that - otherwise start from 0 index
+        //페이징을 시작할 위치 - from_index가 있으면 그것을 사용하고, 그렇지 않으면 0 인덱스에서 시작합니다.
+        let start = u128::from(from_index.unwrap_or(U128(0)));
+
+        self.approved_minters
+            .iter()
+            .skip(start as usize)
+            .take(limit.unwrap_or(50) as usize)
+            .collect()
+    }
+
+    /// Turn public minting on (anyone can mint) or off (only approved minters can mint)
+    /// 공개 발행을 켜거나(누구나 발행 가능) 끕니다(승인된 발행자만 발행 가능).
+    pub fn set_public_minting(&mut self, enabled: bool) {
+        self.assert_contract_owner();
+        self.public_minting = enabled;
+    }
+
+    /// Check if anyone can mint
+    /// 누구나 발행할 수 있는지 확인합니다.
+    pub fn is_public_minting(&self) -> bool {
+        self.public_minting
+    }
+
+    /// Set the cap on the total perpetual royalty of a token, in basis points (10000 = 100%).
+    /// Only applies to tokens minted afterwards.
+    /// 토큰 하나의 영구 로열티 합계 상한을 베이시스 포인트(10000 = 100%)로 설정합니다.
+    /// 이후에 발행되는 토큰에만 적용됩니다.
+    pub fn set_royalty_cap(&mut self, royalty_cap: u32) {
+        self.assert_contract_owner();
+        assert!(royalty_cap <= 10000, "Royalty cap cannot exceed 10000");
+        self.royalty_cap = royalty_cap;
+    }
+
+    /// Lock the transfers of every token of a type, e.g. until a reveal
+    /// 유형의 모든 토큰 전송을 잠급니다. 예를 들어 공개 전까지 사용할 수 있습니다.
+    pub fn nft_lock_token_type(&mut self, token_type: TokenType) {
+        self.assert_contract_owner();
+        self.token_types_locked.insert(&token_type);
+    }
+
+    /// Unlock the transfers of the tokens of a type
+    /// 유형의 토큰 전송 잠금을 해제합니다.
+    pub fn nft_unlock_token_type(&mut self, token_type: TokenType) {
+        self.assert_contract_owner();
+        self.token_types_locked.remove(&token_type);
+    }
+
+    /// Check if the transfers of the tokens of a type are locked
+    /// 유형의 토큰 전송이 잠겨 있는지 확인합니다.
+    pub fn nft_is_token_type_locked(&self, token_type: TokenType) -> bool {
+        self.token_types_locked.contains(&token_type)
+    }
+
+    /// Propose a new contract owner. The transfer only happens once the proposed account calls `accept_ownership`
+    /// 새 계약 소유자를 제안합니다. 제안된 계정이 `accept_ownership`을 호출해야 이전이 완료됩니다.
+    //near call $ID propose_owner '{"new_owner_id": "multisig.near"}' --accountId $OWNER --depositYocto 1
+    #[payable]
+    pub fn propose_owner(&mut self, new_owner_id: AccountId) {
+        //assert that the user attached exactly 1 yoctoNEAR for security reasons
+        // 보안을 위해 사용자가 정확히 1 yoctoNEAR를 첨부했는지 확인합니다.
+        assert_one_yocto();
+        self.assert_contract_owner();
+        assert_ne!(new_owner_id, self.owner_id, "Account is already the contract owner");
+
+        //a new proposal replaces any pending one
+        // 새 제안은 진행 중인 제안을 대체합니다.
+        self.pending_owner_id = Some(new_owner_id.clone());
+        log_extension_event(EventLogVariant::OwnershipTransferPropose(vec![OwnershipTransferLog {
+            old_owner_id: self.owner_id.to_string(),
+            new_owner_id: new_owner_id.to_string(),
+            memo: None,
+        }]));
+    }
+
+    /// Accept a pending ownership transfer. Must be called by the proposed owner
+    /// 진행 중인 소유권 이전을 수락합니다. 제안된 소유자가 호출해야 합니다.
+    //near call $ID accept_ownership '{}' --accountId $NEW_OWNER --depositYocto 1
+    #[payable]
+    pub fn accept_ownership(&mut self) {
+        //assert that the user attached exactly 1 yoctoNEAR for security reasons
+        // 보안을 위해 사용자가 정확히 1 yoctoNEAR를 첨부했는지 확인합니다.
+        assert_one_yocto();
+        let new_owner_id = self.pending_owner_id.take().expect("No pending ownership transfer");
+        assert_eq!(
+            env::predecessor_account_id(),
+            new_owner_id,
+            "only pending owner"
+        );
+
+        let old_owner_id = std::mem::replace(&mut self.owner_id, new_owner_id.clone());
+        log_extension_event(EventLogVariant::OwnershipTransferAccept(vec![OwnershipTransferLog {
+            old_owner_id: old_owner_id.to_string(),
+            new_owner_id: new_owner_id.to_string(),
+            memo: None,
+        }]));
+    }
+
+    /// Cancel a pending ownership transfer
+    /// 진행 중인 소유권 이전을 취소합니다.
+    //near call $ID cancel_ownership_transfer '{}' --accountId $OWNER --depositYocto 1
+    #[payable]
+    pub fn cancel_ownership_transfer(&mut self) {
+        //assert that the user attached exactly 1 yoctoNEAR for security reasons
+        // 보안을 위해 사용자가 정확히 1 yoctoNEAR를 첨부했는지 확인합니다.
+        assert_one_yocto();
+        self.assert_contract_owner();
+        let new_owner_id = self.pending_owner_id.take().expect("No pending ownership transfer");
+
+        log_extension_event(EventLogVariant::OwnershipTransferCancel(vec![OwnershipTransferLog {
+            old_owner_id: self.owner_id.to_string(),
+            new_owner_id: new_owner_id.to_string(),
+            memo: None,
+        }]));
+    }
+
+    /// Get the contract owner
+    /// 계약 소유자를 반환합니다.
+    pub fn get_owner(&self) -> AccountId {
+        self.owner_id.clone()
+    }
+
+    /// Get the account the ownership transfer was proposed to, if any
+    /// 소유권 이전이 제안된 계정이 있다면 반환합니다.
+    pub fn get_pending_owner(&self) -> Option<AccountId> {
+        self.pending_owner_id.clone()
+    }
+}
+
+//log one of this contract's own events, which aren't part of the NFT standard
+// NFT 표준에 없는 이 계약 고유의 이벤트를 로그에 기록합니다.
+pub(crate) fn log_extension_event(event: EventLogVariant) {
+    let extension_log: EventLog = EventLog {
+        // Standard name ("nft_extension").
+        standard: NFT_EXTENSION_STANDARD_NAME.to_string(),
+        // Version of the standard ("1.0.0").
+        version: NFT_EXTENSION_SPEC.to_string(),
+        // The data related with the event stored in a vector.
+        event,
+    };
+
+    // Log the serialized json.
+    env::log_str(&extension_log.to_string());
+}
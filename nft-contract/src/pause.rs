@@ -0,0 +1,94 @@
+//계약 소유자나 일시 정지 권한을 가진 계정이 발행과 전송을 멈출 수 있는 비상 정지 기능이 포함되어 있습니다.
+//뷰 메서드는 일시 정지 중에도 계속 동작합니다.
+use crate::*;
+use crate::owner::log_extension_event;
+
+/// 독립적으로 일시 정지할 수 있는 기능입니다.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "snake_case")]
+pub enum PauseFeature {
+    // nft_mint, nft_batch_mint
+    Mint,
+    // nft_transfer, nft_transfer_call, nft_transfer_payout
+    Transfer,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Pause a feature. Can be called by the contract owner or a pauser
+    /// 기능을 일시 정지합니다. 계약 소유자나 일시 정지 권한을 가진 계정이 호출할 수 있습니다.
+    //near call $ID pause '{"feature": "transfer"}' --accountId $OWNER
+    pub fn pause(&mut self, feature: PauseFeature) {
+        self.assert_owner_or_pauser();
+        //only log when the feature wasn't paused yet
+        // 기능이 아직 일시 정지되지 않은 경우에만 로그를 기록합니다.
+        if self.paused_features.insert(&feature) {
+            log_extension_event(EventLogVariant::Pause(vec![PauseLog {
+                feature,
+                account_id: env::predecessor_account_id().to_string(),
+                memo: None,
+            }]));
+        }
+    }
+
+    /// Unpause a feature. Can be called by the contract owner or a pauser
+    /// 기능의 일시 정지를 해제합니다. 계약 소유자나 일시 정지 권한을 가진 계정이 호출할 수 있습니다.
+    //near call $ID unpause '{"feature": "transfer"}' --accountId $OWNER
+    pub fn unpause(&mut self, feature: PauseFeature) {
+        self.assert_owner_or_pauser();
+        //only log when the feature was paused
+        // 기능이 일시 정지된 경우에만 로그를 기록합니다.
+        if self.paused_features.remove(&feature) {
+            log_extension_event(EventLogVariant::Unpause(vec![PauseLog {
+                feature,
+                account_id: env::predecessor_account_id().to_string(),
+                memo: None,
+            }]));
+        }
+    }
+
+    /// Check if a feature is paused
+    /// 기능이 일시 정지되었는지 확인합니다.
+    pub fn is_paused(&self, feature: PauseFeature) -> bool {
+        self.paused_features.contains(&feature)
+    }
+
+    /// Give a specified account the right to pause and unpause
+    /// 지정된 계정에 일시 정지 및 해제 권한을 부여합니다.
+    pub fn add_pauser(&mut self, account_id: AccountId) {
+        self.assert_contract_owner();
+        self.pausers.insert(&account_id);
+    }
+
+    /// Take the right to pause and unpause away from a specified account
+    /// 지정된 계정의 일시 정지 및 해제 권한을 제거합니다.
+    pub fn remove_pauser(&mut self, account_id: AccountId) {
+        self.assert_contract_owner();
+        self.pausers.remove(&account_id);
+    }
+
+    /// Check if a specified account can pause and unpause
+    /// 지정된 계정이 일시 정지 및 해제 권한을 가지고 있는지 확인합니다.
+    pub fn is_pauser(&self, account_id: AccountId) -> bool {
+        self.pausers.contains(&account_id)
+    }
+}
+
+impl Contract {
+    //make sure that the feature isn't paused
+    // 기능이 일시 정지되지 않았는지 확인합니다.
+    pub(crate) fn assert_not_paused(&self, feature: PauseFeature) {
+        assert!(!self.paused_features.contains(&feature), "{:?} is paused", feature);
+    }
+
+    //make sure that the caller is the owner of the contract or a pauser
+    // 호출자가 계약 소유자이거나 일시 정지 권한을 가진 계정인지 확인합니다.
+    fn assert_owner_or_pauser(&self) {
+        let predecessor_id = env::predecessor_account_id();
+        assert!(
+            predecessor_id == self.owner_id || self.pausers.contains(&predecessor_id),
+            "only contract owner or pauser"
+        );
+    }
+}
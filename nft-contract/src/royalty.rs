@@ -17,6 +17,15 @@ pub trait NonFungibleTokenCore {
     ) -> Payout;
 }
 
+#[near_bindgen]
+impl Contract {
+    //get the cap on the total perpetual royalty of a token, in basis points (10000 = 100%)
+    // 토큰 하나의 영구 로열티 합계 상한을 베이시스 포인트(10000 = 100%)로 반환합니다.
+    pub fn nft_royalty_cap(&self) -> u32 {
+        self.royalty_cap
+    }
+}
+
 #[near_bindgen]
 impl NonFungibleTokenCore for Contract {
 
@@ -89,6 +98,8 @@ impl NonFungibleTokenCore for Contract {
             previous_token.owner_id.clone(),
             &previous_token.approved_account_ids,
         );
+        //the expiries of those approvals are gone as well
+        self.internal_remove_approval_expiries(&token_id, previous_token.owner_id.clone(), |_| true);
 
         //get the owner of the token
         let owner_id = previous_token.owner_id;
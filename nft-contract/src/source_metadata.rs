@@ -0,0 +1,63 @@
+//NEP-330 계약 소스 메타데이터 뷰가 포함되어 있습니다.
+//탐색기와 감사자가 배포된 wasm이 어떤 버전과 표준을 구현하는지 확인할 수 있습니다.
+use crate::*;
+
+// 계약이 구현하는 표준과 그 버전입니다.
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Standard {
+    // 표준의 이름. 예: "nep171"
+    pub standard: String,
+    // 구현된 표준의 버전. 예: "1.0.0"
+    pub version: String,
+}
+
+// NEP-330 계약 소스 메타데이터입니다.
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ContractSourceMetadata {
+    // 빌드 시점의 크레이트 버전
+    pub version: Option<String>,
+    // 소스 코드 저장소 링크
+    pub link: Option<String>,
+    // 구현된 표준 목록
+    pub standards: Vec<Standard>,
+}
+
+//link to the source the wasm was built from. build.sh passes the commit in GIT_COMMIT, otherwise it's the repository
+// wasm이 빌드된 소스의 링크입니다. build.sh가 GIT_COMMIT으로 커밋을 전달하며, 없으면 저장소 링크입니다.
+fn source_link(repository: &str) -> String {
+    match option_env!("GIT_COMMIT").filter(|commit| !commit.is_empty()) {
+        Some(commit) => format!("{}/tree/{}", repository, commit),
+        None => repository.to_string(),
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    //get the version, source code link and supported standards of the deployed contract. Filled in at build time
+    //배포된 계약의 버전, 소스 코드 링크, 지원하는 표준을 가져옵니다. 빌드 시점에 채워집니다.
+    //near view $ID contract_source_metadata '{}'
+    pub fn contract_source_metadata(&self) -> ContractSourceMetadata {
+        ContractSourceMetadata {
+            version: Some(env!("CARGO_PKG_VERSION").to_string()),
+            link: option_env!("CARGO_PKG_REPOSITORY").map(source_link),
+            standards: [
+                ("nep145", "1.0.0"),
+                ("nep171", NFT_METADATA_UPDATE_SPEC),
+                ("nep177", "2.0.0"),
+                ("nep178", "1.0.0"),
+                ("nep181", "1.0.0"),
+                ("nep199", "2.0.0"),
+                ("nep297", "1.0.0"),
+                ("nep330", "1.1.0"),
+            ]
+            .iter()
+            .map(|(standard, version)| Standard {
+                standard: standard.to_string(),
+                version: version.to_string(),
+            })
+            .collect(),
+        }
+    }
+}
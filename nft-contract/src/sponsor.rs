@@ -0,0 +1,201 @@
+//계약 소유자가 자금을 채운 후원 풀로 발행 저장소 비용을 대신 지불하는 기능이 포함되어 있습니다.
+//NEAR가 없는 신규 사용자를 위해, 화이트리스트에 등록된 릴레이어가 금액을 첨부하지 않고 발행하면 그 비용을 풀에서 차감합니다.
+use crate::*;
+use crate::owner::log_extension_event;
+
+/// 하루의 길이(나노초)입니다. 일일 상한은 block timestamp를 이 값으로 나눈 날짜마다 초기화됩니다.
+const NANOS_PER_DAY: u64 = 24 * 60 * 60 * 1_000_000_000;
+
+// 후원 풀의 잔액과 상한입니다. 상한이 0이면 풀에서 아무것도 차감할 수 없습니다.
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct SponsorPool {
+    // 풀에 남아 있는 금액
+    pub balance: Balance,
+    // 수신자 한 명이 평생 후원받을 수 있는 최대 금액
+    pub per_receiver_cap: Balance,
+    // 하루 동안 풀에서 차감할 수 있는 최대 금액
+    pub per_day_cap: Balance,
+    // drawn_today가 집계된 날짜 (block timestamp / NANOS_PER_DAY)
+    pub current_day: u64,
+    // current_day에 풀에서 차감된 금액
+    pub drawn_today: Balance,
+}
+
+// 뷰에서 반환하는 후원 풀의 상태입니다.
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct JsonSponsorPool {
+    pub balance: U128,
+    pub per_receiver_cap: U128,
+    pub per_day_cap: U128,
+    // 오늘 풀에서 더 차감할 수 있는 금액
+    pub remaining_today: U128,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Add the attached deposit to the sponsor pool
+    /// 첨부된 금액을 후원 풀에 추가합니다.
+    //near call $ID sponsor_pool_fund '{}' --accountId $OWNER --amount 10
+    #[payable]
+    pub fn sponsor_pool_fund(&mut self) -> U128 {
+        self.assert_contract_owner();
+        let amount = env::attached_deposit();
+        assert!(amount > 0, "Must attach a deposit to fund the sponsor pool");
+        self.sponsor_pool.balance += amount;
+        U128(self.sponsor_pool.balance)
+    }
+
+    /// Withdraw funds that weren't drawn yet from the sponsor pool to the contract owner
+    /// 아직 차감되지 않은 후원 풀의 금액을 계약 소유자에게 인출합니다.
+    //near call $ID sponsor_pool_withdraw '{"amount": "1000"}' --accountId $OWNER --depositYocto 1
+    #[payable]
+    pub fn sponsor_pool_withdraw(&mut self, amount: U128) -> U128 {
+        //assert that the user attached exactly 1 yoctoNEAR for security reasons
+        // 보안을 위해 사용자가 정확히 1 yoctoNEAR를 첨부했는지 확인합니다.
+        assert_one_yocto();
+        self.assert_contract_owner();
+        assert!(
+            amount.0 <= self.sponsor_pool.balance,
+            "The amount is greater than the sponsor pool balance"
+        );
+
+        self.sponsor_pool.balance -= amount.0;
+        if amount.0 > 0 {
+            Promise::new(self.owner_id.clone()).transfer(amount.0);
+        }
+        U128(self.sponsor_pool.balance)
+    }
+
+    /// Set how much a single receiver and all receivers in a day can be sponsored, in yoctoNEAR
+    /// 수신자 한 명과 하루 동안 전체 수신자가 후원받을 수 있는 금액을 yoctoNEAR 단위로 설정합니다.
+    //near call $ID set_sponsor_caps '{"per_receiver_cap": "10000000000000000000000", "per_day_cap": "1000000000000000000000000"}' --accountId $OWNER
+    pub fn set_sponsor_caps(&mut self, per_receiver_cap: U128, per_day_cap: U128) {
+        self.assert_contract_owner();
+        self.sponsor_pool.per_receiver_cap = per_receiver_cap.0;
+        self.sponsor_pool.per_day_cap = per_day_cap.0;
+    }
+
+    /// Whitelist a relayer whose mints without an attached deposit are paid by the sponsor pool.
+    /// The relayer still needs to be allowed to mint.
+    /// 금액을 첨부하지 않은 발행 비용을 후원 풀이 지불하는 릴레이어를 화이트리스트에 추가합니다.
+    /// 릴레이어는 여전히 발행 권한이 있어야 합니다.
+    pub fn add_sponsor_relayer(&mut self, account_id: AccountId) {
+        self.assert_contract_owner();
+        self.sponsor_relayers.insert(&account_id);
+    }
+
+    /// Remove a relayer from the whitelist
+    /// 릴레이어를 화이트리스트에서 제거합니다.
+    pub fn remove_sponsor_relayer(&mut self, account_id: AccountId) {
+        self.assert_contract_owner();
+        self.sponsor_relayers.remove(&account_id);
+    }
+
+    /// Check if a specified account is a whitelisted relayer
+    /// 지정된 계정이 화이트리스트에 등록된 릴레이어인지 확인합니다.
+    pub fn is_sponsor_relayer(&self, account_id: AccountId) -> bool {
+        self.sponsor_relayers.contains(&account_id)
+    }
+
+    /// Get the remaining balance of the sponsor pool
+    /// 후원 풀의 남은 잔액을 가져옵니다.
+    pub fn sponsor_pool_balance(&self) -> U128 {
+        U128(self.sponsor_pool.balance)
+    }
+
+    /// Get the balance and caps of the sponsor pool, along with how much can still be drawn today
+    /// 후원 풀의 잔액과 상한, 그리고 오늘 더 차감할 수 있는 금액을 가져옵니다.
+    pub fn sponsor_pool_info(&self) -> JsonSponsorPool {
+        let pool = &self.sponsor_pool;
+        let drawn_today = if pool.current_day == env::block_timestamp() / NANOS_PER_DAY {
+            pool.drawn_today
+        } else {
+            0
+        };
+        JsonSponsorPool {
+            balance: U128(pool.balance),
+            per_receiver_cap: U128(pool.per_receiver_cap),
+            per_day_cap: U128(pool.per_day_cap),
+            remaining_today: U128(pool.per_day_cap.saturating_sub(drawn_today).min(pool.balance)),
+        }
+    }
+
+    /// Get how much the sponsor pool has paid for a receiver so far
+    /// 후원 풀이 지금까지 수신자를 위해 지불한 금액을 가져옵니다.
+    pub fn sponsored_for_receiver(&self, account_id: AccountId) -> U128 {
+        U128(self.sponsored_per_receiver.get(&account_id).unwrap_or(0))
+    }
+}
+
+impl Contract {
+    //pay for the storage of minted tokens. A whitelisted relayer that attached nothing is paid for by the sponsor pool,
+    //everyone else pays with their deposit or storage balance.
+    // 발행된 토큰의 저장소 비용을 지불합니다. 아무것도 첨부하지 않은 화이트리스트 릴레이어의 비용은 후원 풀이 지불하며,
+    // 그 외에는 첨부된 금액이나 저장소 잔액으로 지불합니다.
+    pub(crate) fn charge_mint_storage(&mut self, storage_by_token: Vec<(AccountId, TokenId, u64)>) {
+        if env::attached_deposit() == 0
+            && self.sponsor_relayers.contains(&env::predecessor_account_id())
+        {
+            self.draw_sponsor_pool(storage_by_token);
+        } else {
+            self.charge_storage(storage_by_token.iter().map(|(_, _, storage_used)| storage_used).sum());
+        }
+    }
+
+    //draw the storage cost of each token from the sponsor pool, within the per-receiver and per-day caps.
+    //the pool also pays for the bookkeeping of the draw, and the token is marked so burning it refunds the pool.
+    // 수신자별 상한과 일일 상한 안에서 각 토큰의 저장소 비용을 후원 풀에서 차감합니다.
+    // 차감 기록에 필요한 저장소도 풀이 지불하며, 토큰을 소각하면 풀에 환불되도록 토큰을 표시합니다.
+    fn draw_sponsor_pool(&mut self, storage_by_token: Vec<(AccountId, TokenId, u64)>) {
+        //start counting again on a new day
+        // 날짜가 바뀌면 일일 집계를 다시 시작합니다.
+        let today = env::block_timestamp() / NANOS_PER_DAY;
+        if self.sponsor_pool.current_day != today {
+            self.sponsor_pool.current_day = today;
+            self.sponsor_pool.drawn_today = 0;
+        }
+
+        let relayer_id = env::predecessor_account_id();
+        let mut draw_logs = Vec::new();
+        for (receiver_id, token_id, storage_used) in storage_by_token {
+            //record the draw first so the storage of the records is charged to the pool as well
+            // 기록의 저장소도 풀에 청구되도록 차감 내역을 먼저 기록합니다.
+            let initial_storage_usage = env::storage_usage();
+            let previously_sponsored = self.sponsored_per_receiver.get(&receiver_id).unwrap_or(0);
+            self.sponsored_per_receiver.insert(&receiver_id, &previously_sponsored);
+            self.sponsored_tokens.insert(&token_id);
+            let storage_used = storage_used + (env::storage_usage() - initial_storage_usage);
+
+            let amount = env::storage_byte_cost() * Balance::from(storage_used);
+            let sponsored = previously_sponsored + amount;
+            assert!(
+                sponsored <= self.sponsor_pool.per_receiver_cap,
+                "Sponsoring {} would exceed the per-receiver cap",
+                receiver_id
+            );
+            assert!(
+                self.sponsor_pool.drawn_today + amount <= self.sponsor_pool.per_day_cap,
+                "Sponsoring would exceed the daily cap of the sponsor pool"
+            );
+            assert!(
+                amount <= self.sponsor_pool.balance,
+                "Not enough balance in the sponsor pool"
+            );
+
+            self.sponsored_per_receiver.insert(&receiver_id, &sponsored);
+            self.sponsor_pool.drawn_today += amount;
+            self.sponsor_pool.balance -= amount;
+
+            draw_logs.push(SponsorPoolDrawLog {
+                relayer_id: relayer_id.to_string(),
+                receiver_id: receiver_id.to_string(),
+                amount: amount.to_string(),
+                remaining_balance: self.sponsor_pool.balance.to_string(),
+                memo: None,
+            });
+        }
+
+        log_extension_event(EventLogVariant::SponsorPoolDraw(draw_logs));
+    }
+}
@@ -0,0 +1,185 @@
+//NEP-145 저장소 관리 표준을 구현합니다.
+//사용자나 후원자가 저장소 비용을 미리 예치해 두면 nft_mint와 nft_approve가 첨부된 금액이 부족할 때 그 잔액에서 차감합니다.
+use crate::*;
+
+/// 저장소 잔액 항목 하나가 차지하는 최대 바이트 수입니다.
+/// (레코드 오버헤드 40 + 접두사 1 + 가장 긴 계정 ID 4 + 64 + 잔액 16)
+const ACCOUNT_STORAGE_BYTES: u64 = 40 + 1 + 4 + 64 + 16;
+
+// 계정의 저장소 잔액입니다. total에는 등록을 위한 최소 잔액이 포함되며, available은 사용하거나 인출할 수 있는 금액입니다.
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct StorageBalance {
+    pub total: U128,
+    pub available: U128,
+}
+
+// 계정이 예치해야 하는 최소 및 최대 저장소 잔액입니다. max가 None이면 상한이 없습니다.
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct StorageBalanceBounds {
+    pub min: U128,
+    pub max: Option<U128>,
+}
+
+#[near_bindgen]
+impl Contract {
+    //deposit NEAR to cover storage for an account. If no account ID is passed in, the caller is used.
+    //if registration_only is true, anything above the minimum balance is refunded.
+    // 계정의 저장소 비용을 예치합니다. 계정 ID를 전달하지 않으면 호출자를 사용합니다.
+    // registration_only가 true이면 최소 잔액을 넘는 금액은 환불됩니다.
+    //near call $ID storage_deposit '{"account_id": "alice.near"}' --accountId $SPONSOR --amount 0.1
+    #[payable]
+    pub fn storage_deposit(
+        &mut self,
+        account_id: Option<AccountId>,
+        registration_only: Option<bool>,
+    ) -> StorageBalance {
+        let amount = env::attached_deposit();
+        let account_id = account_id.unwrap_or_else(env::predecessor_account_id);
+        let min_balance = storage_balance_min();
+        let registration_only = registration_only.unwrap_or(false);
+
+        let balance = if let Some(balance) = self.storage_deposits.get(&account_id) {
+            //already registered. A registration only deposit is refunded in full
+            // 이미 등록된 계정입니다. 등록만을 위한 예치금은 전액 환불됩니다.
+            if registration_only {
+                if amount > 0 {
+                    Promise::new(env::predecessor_account_id()).transfer(amount);
+                }
+                balance
+            } else {
+                balance + amount
+            }
+        } else {
+            assert!(
+                amount >= min_balance,
+                "Requires minimum deposit of {}",
+                min_balance
+            );
+            if registration_only {
+                let refund = amount - min_balance;
+                if refund > 0 {
+                    Promise::new(env::predecessor_account_id()).transfer(refund);
+                }
+                min_balance
+            } else {
+                amount
+            }
+        };
+
+        self.storage_deposits.insert(&account_id, &balance);
+        to_storage_balance(balance)
+    }
+
+    //withdraw the available storage balance of the caller. If no amount is passed in, everything available is withdrawn.
+    // 호출자의 사용 가능한 저장소 잔액을 인출합니다. 금액을 전달하지 않으면 사용 가능한 금액 전부를 인출합니다.
+    //near call $ID storage_withdraw '{"amount": "1000"}' --accountId $ACCOUNT --depositYocto 1
+    #[payable]
+    pub fn storage_withdraw(&mut self, amount: Option<U128>) -> StorageBalance {
+        //assert that the user attached exactly 1 yoctoNEAR for security reasons
+        // 보안을 위해 사용자가 정확히 1 yoctoNEAR를 첨부했는지 확인합니다.
+        assert_one_yocto();
+
+        let account_id = env::predecessor_account_id();
+        let balance = self
+            .storage_deposits
+            .get(&account_id)
+            .unwrap_or_else(|| env::panic_str(&format!("The account {} is not registered", account_id)));
+        let available = balance - storage_balance_min();
+        let amount = amount.map(|amount| amount.0).unwrap_or(available);
+        assert!(
+            amount <= available,
+            "The amount is greater than the available storage balance"
+        );
+
+        let balance = balance - amount;
+        self.storage_deposits.insert(&account_id, &balance);
+        if amount > 0 {
+            Promise::new(account_id).transfer(amount);
+        }
+        to_storage_balance(balance)
+    }
+
+    //unregister the caller and refund their whole storage balance. Storage that was already spent on tokens or
+    //approvals isn't held by the balance, so force doesn't change anything. Returns false if the caller wasn't registered.
+    // 호출자의 등록을 해제하고 저장소 잔액 전체를 환불합니다. 토큰이나 승인에 이미 사용된 저장소는 잔액에 묶여 있지 않으므로
+    // force는 아무런 영향을 주지 않습니다. 호출자가 등록되지 않았다면 false를 반환합니다.
+    #[payable]
+    pub fn storage_unregister(&mut self, force: Option<bool>) -> bool {
+        //assert that the user attached exactly 1 yoctoNEAR for security reasons
+        // 보안을 위해 사용자가 정확히 1 yoctoNEAR를 첨부했는지 확인합니다.
+        assert_one_yocto();
+        // force는 표준을 따르기 위해 받지만 사용하지 않습니다.
+        let _ = force;
+
+        let account_id = env::predecessor_account_id();
+        if let Some(balance) = self.storage_deposits.remove(&account_id) {
+            if balance > 0 {
+                Promise::new(account_id).transfer(balance);
+            }
+            true
+        } else {
+            false
+        }
+    }
+
+    //get the storage balance of an account, or None if it isn't registered
+    // 계정의 저장소 잔액을 가져옵니다. 등록되지 않았다면 None을 반환합니다.
+    pub fn storage_balance_of(&self, account_id: AccountId) -> Option<StorageBalance> {
+        self.storage_deposits.get(&account_id).map(to_storage_balance)
+    }
+
+    //get the minimum and maximum storage balance of an account
+    // 계정의 최소 및 최대 저장소 잔액을 가져옵니다.
+    pub fn storage_balance_bounds(&self) -> StorageBalanceBounds {
+        StorageBalanceBounds {
+            min: U128(storage_balance_min()),
+            max: None,
+        }
+    }
+}
+
+impl Contract {
+    //pay for the storage used by the call. The attached deposit is used first and any excess is refunded.
+    //if the attached deposit doesn't cover the cost, the rest is drawn from the caller's storage balance.
+    // 호출에 사용된 저장소 비용을 지불합니다. 첨부된 금액을 먼저 사용하고 초과분은 환불합니다.
+    // 첨부된 금액이 비용을 충당하지 못하면 나머지는 호출자의 저장소 잔액에서 차감합니다.
+    pub(crate) fn charge_storage(&mut self, storage_used: u64) {
+        //get how much it would cost to store the information
+        // 정보를 저장하는 데 드는 비용을 가져옵니다.
+        let required_cost = env::storage_byte_cost() * Balance::from(storage_used);
+        let attached_deposit = env::attached_deposit();
+        if required_cost <= attached_deposit {
+            refund_deposit(storage_used);
+            return;
+        }
+
+        //draw the shortfall from the storage balance, which must stay above the minimum
+        // 부족한 금액을 저장소 잔액에서 차감합니다. 잔액은 최소 잔액 이상으로 유지되어야 합니다.
+        let account_id = env::predecessor_account_id();
+        let shortfall = required_cost - attached_deposit;
+        let balance = self.storage_deposits.get(&account_id).unwrap_or(0);
+        assert!(
+            balance >= storage_balance_min() + shortfall,
+            "Must attach {} yoctoNEAR or deposit it with storage_deposit to cover storage",
+            shortfall,
+        );
+        self.storage_deposits.insert(&account_id, &(balance - shortfall));
+    }
+}
+
+//the minimum balance an account needs to register, which pays for its own entry in the ledger
+// 계정을 등록하는 데 필요한 최소 잔액으로, 잔액 항목 자체의 저장소 비용입니다.
+fn storage_balance_min() -> Balance {
+    Balance::from(ACCOUNT_STORAGE_BYTES) * env::storage_byte_cost()
+}
+
+//turn a stored balance into the balance returned by the views
+// 저장된 잔액을 뷰에서 반환하는 잔액으로 변환합니다.
+fn to_storage_balance(balance: Balance) -> StorageBalance {
+    StorageBalance {
+        total: U128(balance),
+        available: U128(balance.saturating_sub(storage_balance_min())),
+    }
+}
@@ -1,12 +1,22 @@
 /* unit tests */
 #[cfg(test)]
 use crate::Contract;
-use crate::TokenMetadata;
+use crate::migrate::{
+    ContractV1, ContractV2, TokenMetadataV1, UnparsedTimestamps, VersionedContract, MAX_MIGRATION_BATCH_SIZE,
+};
+use crate::{
+    MetadataUpdater, MintArgs, NFTContractMetadata, NFTContractMetadataUpdate, PauseFeature,
+    StorageKey, Token, TokenMetadata,
+};
 use crate::approval::NonFungibleTokenCore;
-use near_sdk::json_types::{U128, U64};
-use near_sdk::test_utils::{accounts, VMContextBuilder};
+use crate::royalty::NonFungibleTokenCore as NonFungibleTokenPayout;
+use near_sdk::json_types::{Base64VecU8, U128, U64};
+use near_sdk::test_utils::{accounts, get_created_receipts, get_logs, VMContextBuilder};
+use near_sdk::mock::VmAction;
+use near_sdk::borsh::BorshSerialize;
+use near_sdk::collections::{LazyOption, LookupMap, UnorderedMap};
 use near_sdk::testing_env;
-use near_sdk::{env, AccountId};
+use near_sdk::{env, AccountId, PromiseOrValue};
 
 use std::collections::HashMap;
 
@@ -66,7 +76,7 @@ fn test_mint_nft() {
         .build());
     let token_metadata: TokenMetadata = sample_token_metadata();
     let token_id = "0".to_string();
-    contract.nft_mint(token_id.clone(), token_metadata, accounts(0), None);
+    contract.nft_mint(token_id.clone(), token_metadata, accounts(0), None, None, None);
     let contract_nft_tokens = contract.nft_tokens(Some(U128(0)), None);
     assert_eq!(contract_nft_tokens.len(), 1);
 
@@ -99,7 +109,7 @@ fn test_internal_transfer() {
         .predecessor_account_id(accounts(0))
         .build());
     let token_id = "0".to_string();
-    contract.nft_mint(token_id.clone(), sample_token_metadata(), accounts(0), None);
+    contract.nft_mint(token_id.clone(), sample_token_metadata(), accounts(0), None, None, None);
 
     testing_env!(context
         .storage_usage(env::storage_usage())
@@ -151,14 +161,14 @@ fn test_nft_approve() {
         .predecessor_account_id(accounts(0))
         .build());
     let token_id = "0".to_string();
-    contract.nft_mint(token_id.clone(), sample_token_metadata(), accounts(0), None);
+    contract.nft_mint(token_id.clone(), sample_token_metadata(), accounts(0), None, None, None);
 
     testing_env!(context
         .storage_usage(env::storage_usage())
         .attached_deposit(MIN_REQUIRED_APPROVAL_YOCTO)
         .predecessor_account_id(accounts(0))
         .build());
-    contract.nft_approve(token_id.clone(), accounts(1), None);
+    contract.nft_approve(token_id.clone(), accounts(1), None, None);
 
     testing_env!(context
         .storage_usage(env::storage_usage())
@@ -181,7 +191,7 @@ fn test_nft_revoke() {
         .predecessor_account_id(accounts(0))
         .build());
     let token_id = "0".to_string();
-    contract.nft_mint(token_id.clone(), sample_token_metadata(), accounts(0), None);
+    contract.nft_mint(token_id.clone(), sample_token_metadata(), accounts(0), None, None, None);
 
     // alice approves bob
     testing_env!(context
@@ -189,7 +199,7 @@ fn test_nft_revoke() {
         .attached_deposit(MIN_REQUIRED_APPROVAL_YOCTO)
         .predecessor_account_id(accounts(0))
         .build());
-    contract.nft_approve(token_id.clone(), accounts(1), None);
+    contract.nft_approve(token_id.clone(), accounts(1), None, None);
 
     // alice revokes bob
     testing_env!(context
@@ -219,7 +229,7 @@ fn test_revoke_all() {
         .predecessor_account_id(accounts(0))
         .build());
     let token_id = "0".to_string();
-    contract.nft_mint(token_id.clone(), sample_token_metadata(), accounts(0), None);
+    contract.nft_mint(token_id.clone(), sample_token_metadata(), accounts(0), None, None, None);
 
     // alice approves bob
     testing_env!(context
@@ -227,7 +237,7 @@ fn test_revoke_all() {
         .attached_deposit(MIN_REQUIRED_APPROVAL_YOCTO)
         .predecessor_account_id(accounts(0))
         .build());
-    contract.nft_approve(token_id.clone(), accounts(1), None);
+    contract.nft_approve(token_id.clone(), accounts(1), None, None);
 
     // alice revokes bob
     testing_env!(context
@@ -257,7 +267,7 @@ fn test_internal_remove_token_from_owner() {
         .predecessor_account_id(accounts(0))
         .build());
     let token_id = "0".to_string();
-    contract.nft_mint(token_id.clone(), sample_token_metadata(), accounts(0), None);
+    contract.nft_mint(token_id.clone(), sample_token_metadata(), accounts(0), None, None, None);
 
     let contract_nft_tokens_before = contract.nft_tokens_for_owner(accounts(0), None, None);
     assert_eq!(contract_nft_tokens_before.len(), 1);
@@ -280,7 +290,7 @@ fn test_nft_payout() {
         .predecessor_account_id(accounts(0))
         .build());
     let token_id = "0".to_string();
-    contract.nft_mint(token_id.clone(), sample_token_metadata(), accounts(0), None);
+    contract.nft_mint(token_id.clone(), sample_token_metadata(), accounts(0), None, None, None);
 
     // alice approves bob
     testing_env!(context
@@ -288,7 +298,7 @@ fn test_nft_payout() {
         .attached_deposit(MIN_REQUIRED_APPROVAL_YOCTO)
         .predecessor_account_id(accounts(0))
         .build());
-    contract.nft_approve(token_id.clone(), accounts(1), None);
+    contract.nft_approve(token_id.clone(), accounts(1), None, None);
 
     let payout = contract.nft_payout(token_id.clone(), U128(10), 1);
     let expected = HashMap::from([(accounts(0), U128(10))]);
@@ -307,8 +317,1708 @@ fn test_nft_total_supply() {
         .predecessor_account_id(accounts(0))
         .build());
     let token_id = "0".to_string();
-    contract.nft_mint(token_id.clone(), sample_token_metadata(), accounts(0), None);
+    contract.nft_mint(token_id.clone(), sample_token_metadata(), accounts(0), None, None, None);
 
     let total_supply = contract.nft_total_supply();
     assert_eq!(total_supply, U128(1));
-}
\ No newline at end of file
+}
+#[test]
+fn test_nft_batch_mint() {
+    let mut context = get_context(accounts(0));
+    testing_env!(context.build());
+    let mut contract = Contract::new_default_meta(accounts(0).into());
+
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(MINT_STORAGE_COST)
+        .predecessor_account_id(accounts(0))
+        .build());
+    let tokens = vec![
+        MintArgs {
+            token_id: "0".to_string(),
+            metadata: sample_token_metadata(),
+            receiver_id: accounts(1),
+            perpetual_royalties: None,
+            soulbound: None,
+            token_type: None,
+        },
+        MintArgs {
+            token_id: "1".to_string(),
+            metadata: sample_token_metadata(),
+            receiver_id: accounts(2),
+            perpetual_royalties: None,
+            soulbound: None,
+            token_type: None,
+        },
+        MintArgs {
+            token_id: "2".to_string(),
+            metadata: sample_token_metadata(),
+            receiver_id: accounts(1),
+            perpetual_royalties: None,
+            soulbound: None,
+            token_type: None,
+        },
+    ];
+    contract.nft_batch_mint(tokens);
+
+    assert_eq!(contract.nft_total_supply(), U128(3));
+    assert_eq!(contract.nft_supply_for_owner(accounts(1)), U128(2));
+    assert_eq!(contract.nft_supply_for_owner(accounts(2)), U128(1));
+
+    // a single event is logged with the token IDs grouped by owner
+    let logs = get_logs();
+    assert_eq!(logs.len(), 1);
+    assert_eq!(
+        logs[0],
+        r#"EVENT_JSON:{"standard":"nep171","version":"1.0.0","event":"nft_mint","data":[{"owner_id":"bob","token_ids":["0","2"]},{"owner_id":"charlie","token_ids":["1"]}]}"#
+    );
+}
+
+#[test]
+#[should_panic(expected = "Token 0 already exists")]
+fn test_nft_batch_mint_existing_token() {
+    let mut context = get_context(accounts(0));
+    testing_env!(context.build());
+    let mut contract = Contract::new_default_meta(accounts(0).into());
+
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(MINT_STORAGE_COST)
+        .predecessor_account_id(accounts(0))
+        .build());
+    contract.nft_mint("0".to_string(), sample_token_metadata(), accounts(0), None, None, None);
+
+    let tokens = vec![
+        MintArgs {
+            token_id: "1".to_string(),
+            metadata: sample_token_metadata(),
+            receiver_id: accounts(1),
+            perpetual_royalties: None,
+            soulbound: None,
+            token_type: None,
+        },
+        MintArgs {
+            token_id: "0".to_string(),
+            metadata: sample_token_metadata(),
+            receiver_id: accounts(1),
+            perpetual_royalties: None,
+            soulbound: None,
+            token_type: None,
+        },
+    ];
+    contract.nft_batch_mint(tokens);
+}
+
+#[test]
+fn test_nft_burn() {
+    let mut context = get_context(accounts(0));
+    testing_env!(context.build());
+    let mut contract = Contract::new_default_meta(accounts(0).into());
+
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(MINT_STORAGE_COST)
+        .predecessor_account_id(accounts(0))
+        .build());
+    let token_id = "0".to_string();
+    contract.nft_mint(token_id.clone(), sample_token_metadata(), accounts(0), None, None, None);
+
+    // alice approves bob
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(MIN_REQUIRED_APPROVAL_YOCTO)
+        .predecessor_account_id(accounts(0))
+        .build());
+    contract.nft_approve(token_id.clone(), accounts(1), None, None);
+
+    // bob burns the token on alice's behalf
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(1)
+        .predecessor_account_id(accounts(1))
+        .build());
+    contract.nft_burn(token_id.clone(), Some(0), None);
+
+    assert!(contract.tokens_by_id.get(&token_id).is_none());
+    assert!(contract.token_metadata_by_id.get(&token_id).is_none());
+    assert_eq!(contract.nft_total_supply(), U128(0));
+    assert_eq!(contract.nft_supply_for_owner(accounts(0)), U128(0));
+    assert_eq!(
+        get_logs(),
+        vec![r#"EVENT_JSON:{"standard":"nep171","version":"1.0.0","event":"nft_burn","data":[{"owner_id":"alice","authorized_id":"bob","token_ids":["0"]}]}"#]
+    );
+}
+
+#[test]
+fn test_nft_update_contract_metadata() {
+    use crate::NonFungibleTokenMetadata;
+    let mut context = get_context(accounts(0));
+    testing_env!(context.build());
+    let mut contract = Contract::new_default_meta(accounts(0).into());
+
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(MINT_STORAGE_COST)
+        .predecessor_account_id(accounts(0))
+        .build());
+    contract.nft_update_contract_metadata(
+        NFTContractMetadataUpdate {
+            icon: Some(Some("data:image/svg+xml,icon".to_string())),
+            reference: Some(Some("https://example.com/reference.json".to_string())),
+            reference_hash: Some(Some(Base64VecU8(vec![0; 32]))),
+            ..Default::default()
+        },
+        None,
+    );
+
+    let metadata = contract.nft_metadata();
+    assert_eq!(metadata.name, "NFT Tutorial Contract");
+    assert_eq!(metadata.icon, Some("data:image/svg+xml,icon".to_string()));
+    assert_eq!(metadata.reference, Some("https://example.com/reference.json".to_string()));
+    assert_eq!(
+        get_logs(),
+        vec![r#"EVENT_JSON:{"standard":"nep171","version":"1.1.0","event":"contract_metadata_update","data":[{}]}"#]
+    );
+}
+
+#[test]
+#[should_panic(expected = "reference and reference_hash must be set together")]
+fn test_nft_update_contract_metadata_requires_reference() {
+    let mut context = get_context(accounts(0));
+    testing_env!(context.build());
+    let mut contract = Contract::new_default_meta(accounts(0).into());
+
+    testing_env!(context
+        .attached_deposit(1)
+        .predecessor_account_id(accounts(0))
+        .build());
+    contract.nft_update_contract_metadata(
+        NFTContractMetadataUpdate {
+            reference_hash: Some(Some(Base64VecU8(vec![0; 32]))),
+            ..Default::default()
+        },
+        None,
+    );
+}
+
+#[test]
+fn test_nft_update_contract_metadata_clears_fields() {
+    use crate::NonFungibleTokenMetadata;
+    let mut context = get_context(accounts(0));
+    testing_env!(context.build());
+    let mut contract = Contract::new_default_meta(accounts(0).into());
+
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(MINT_STORAGE_COST)
+        .predecessor_account_id(accounts(0))
+        .build());
+    contract.nft_update_contract_metadata(
+        NFTContractMetadataUpdate {
+            base_uri: Some(Some("https://example.com".to_string())),
+            reference: Some(Some("https://example.com/reference.json".to_string())),
+            reference_hash: Some(Some(Base64VecU8(vec![0; 32]))),
+            ..Default::default()
+        },
+        None,
+    );
+
+    // null clears a field, a field that isn't passed in is left unchanged
+    let update: NFTContractMetadataUpdate = near_sdk::serde_json::from_str(
+        r#"{"reference": null, "reference_hash": null}"#,
+    )
+    .unwrap();
+    assert!(update.base_uri.is_none());
+    contract.nft_update_contract_metadata(update, None);
+
+    let metadata = contract.nft_metadata();
+    assert_eq!(metadata.base_uri, Some("https://example.com".to_string()));
+    assert_eq!(metadata.reference, None);
+    assert_eq!(metadata.reference_hash, None);
+}
+
+#[test]
+fn test_nft_update_contract_metadata_only_validates_changes() {
+    use crate::NonFungibleTokenMetadata;
+    let mut context = get_context(accounts(0));
+    testing_env!(context.build());
+    // new() doesn't validate the metadata, so a deployment can have a reference without its hash
+    let mut contract = Contract::new(
+        accounts(0),
+        NFTContractMetadata {
+            spec: "nft-1.0.0".to_string(),
+            name: "Old Contract".to_string(),
+            symbol: "OLD".to_string(),
+            icon: None,
+            base_uri: None,
+            reference: Some("https://example.com/reference.json".to_string()),
+            reference_hash: None,
+        },
+    );
+
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(MINT_STORAGE_COST)
+        .predecessor_account_id(accounts(0))
+        .build());
+    contract.nft_update_contract_metadata(
+        NFTContractMetadataUpdate {
+            icon: Some(Some("data:image/svg+xml,icon".to_string())),
+            ..Default::default()
+        },
+        None,
+    );
+    assert_eq!(contract.nft_metadata().icon, Some("data:image/svg+xml,icon".to_string()));
+}
+
+#[test]
+#[should_panic(expected = "only contract owner")]
+fn test_nft_update_contract_metadata_not_owner() {
+    let mut context = get_context(accounts(0));
+    testing_env!(context.build());
+    let mut contract = Contract::new_default_meta(accounts(0).into());
+
+    testing_env!(context
+        .attached_deposit(1)
+        .predecessor_account_id(accounts(1))
+        .build());
+    contract.nft_update_contract_metadata(
+        NFTContractMetadataUpdate {
+            name: Some("Stolen".to_string()),
+            ..Default::default()
+        },
+        None,
+    );
+}
+
+#[test]
+fn test_nft_update_token_metadata() {
+    let mut context = get_context(accounts(0));
+    testing_env!(context.build());
+    let mut contract = Contract::new_default_meta(accounts(0).into());
+
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(MINT_STORAGE_COST)
+        .predecessor_account_id(accounts(0))
+        .build());
+    let token_id = "0".to_string();
+    contract.nft_mint(token_id.clone(), sample_token_metadata(), accounts(1), None, None, None);
+
+    // the contract owner lets the token owner update the metadata
+    contract.nft_set_metadata_updater(token_id.clone(), MetadataUpdater::TokenOwner);
+    assert_eq!(contract.nft_metadata_updater(token_id.clone()), MetadataUpdater::TokenOwner);
+
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(MINT_STORAGE_COST)
+        .predecessor_account_id(accounts(1))
+        .block_timestamp(1_000_000_000)
+        .build());
+    contract.nft_update_token_metadata(
+        token_id.clone(),
+        TokenMetadata {
+            title: None,
+            description: None,
+            media: Some("https://example.com/evolved.png".into()),
+            media_hash: None,
+            copies: None,
+            issued_at: None,
+            expires_at: None,
+            starts_at: None,
+            updated_at: None,
+            extra: None,
+            reference: None,
+            reference_hash: None,
+        },
+        None,
+    );
+
+    let metadata = contract.token_metadata_by_id.get(&token_id).unwrap();
+    assert_eq!(metadata.title, sample_token_metadata().title);
+    assert_eq!(metadata.media, Some("https://example.com/evolved.png".into()));
+    assert_eq!(metadata.updated_at, Some(U64(1000)));
+    assert_eq!(
+        get_logs(),
+        vec![r#"EVENT_JSON:{"standard":"nep171","version":"1.2.0","event":"nft_metadata_update","data":[{"token_ids":["0"]}]}"#]
+    );
+}
+
+#[test]
+fn test_nft_update_token_metadata_refunds_owner() {
+    let mut context = get_context(accounts(0));
+    testing_env!(context.build());
+    let mut contract = Contract::new_default_meta(accounts(0).into());
+
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(MINT_STORAGE_COST)
+        .predecessor_account_id(accounts(0))
+        .build());
+    let token_id = "0".to_string();
+    contract.nft_mint(token_id.clone(), sample_token_metadata(), accounts(1), None, None, None);
+
+    // the contract owner shortens bob's metadata, and the freed storage goes to bob
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(1)
+        .predecessor_account_id(accounts(0))
+        .build());
+    let initial_storage_usage = env::storage_usage();
+    contract.nft_update_token_metadata(
+        token_id,
+        TokenMetadata {
+            title: None,
+            description: Some("Tall".into()),
+            media: None,
+            media_hash: None,
+            copies: None,
+            issued_at: None,
+            expires_at: None,
+            starts_at: None,
+            updated_at: None,
+            extra: None,
+            reference: None,
+            reference_hash: None,
+        },
+        None,
+    );
+    let freed = u128::from(initial_storage_usage - env::storage_usage()) * env::storage_byte_cost();
+    assert!(freed > 0);
+
+    let refunds: Vec<(AccountId, u128)> = get_created_receipts()
+        .into_iter()
+        .flat_map(|receipt| {
+            let receiver_id = receipt.receiver_id;
+            receipt.actions.into_iter().filter_map(move |action| match action {
+                VmAction::Transfer { deposit } => Some((receiver_id.clone(), deposit)),
+                _ => None,
+            })
+        })
+        .collect();
+    assert_eq!(refunds, vec![(accounts(1), freed)]);
+}
+
+#[test]
+#[should_panic(expected = "Not allowed to update the token metadata")]
+fn test_nft_update_token_metadata_not_allowed() {
+    let mut context = get_context(accounts(0));
+    testing_env!(context.build());
+    let mut contract = Contract::new_default_meta(accounts(0).into());
+
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(MINT_STORAGE_COST)
+        .predecessor_account_id(accounts(0))
+        .build());
+    let token_id = "0".to_string();
+    contract.nft_mint(token_id.clone(), sample_token_metadata(), accounts(1), None, None, None);
+
+    // by default only the contract owner can update the metadata
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(1)
+        .predecessor_account_id(accounts(1))
+        .build());
+    contract.nft_update_token_metadata(token_id, sample_token_metadata(), None);
+}
+
+#[test]
+fn test_approved_minters() {
+    let mut context = get_context(accounts(0));
+    testing_env!(context.build());
+    let mut contract = Contract::new_default_meta(accounts(0).into());
+
+    // the owner is the first approved minter
+    assert!(contract.is_approved_minter(accounts(0)));
+    assert!(!contract.is_public_minting());
+
+    contract.add_approved_minter(accounts(1));
+    assert_eq!(
+        get_logs(),
+        vec![format!(
+            r#"EVENT_JSON:{{"standard":"nft_extension","version":"1.0.0","event":"minter_add","data":[{{"account_ids":["{}"]}}]}}"#,
+            accounts(1)
+        )]
+    );
+    assert_eq!(contract.get_approved_minters(None, None), vec![accounts(0), accounts(1)]);
+    assert_eq!(contract.get_approved_minters(Some(U128(1)), Some(1)), vec![accounts(1)]);
+
+    // the newly approved minter can mint
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(MINT_STORAGE_COST)
+        .predecessor_account_id(accounts(1))
+        .build());
+    contract.nft_mint("0".to_string(), sample_token_metadata(), accounts(2), None, None, None);
+    assert_eq!(contract.nft_total_supply(), U128(1));
+
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(0)
+        .predecessor_account_id(accounts(0))
+        .build());
+    contract.remove_approved_minter(accounts(1));
+    assert!(!contract.is_approved_minter(accounts(1)));
+    assert_eq!(contract.get_approved_minters(None, None), vec![accounts(0)]);
+}
+
+#[test]
+fn test_public_minting() {
+    let mut context = get_context(accounts(0));
+    testing_env!(context.build());
+    let mut contract = Contract::new_default_meta(accounts(0).into());
+    contract.set_public_minting(true);
+    assert!(contract.is_public_minting());
+
+    // anyone can mint while public minting is on
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(MINT_STORAGE_COST)
+        .predecessor_account_id(accounts(2))
+        .build());
+    contract.nft_mint("0".to_string(), sample_token_metadata(), accounts(2), None, None, None);
+    assert_eq!(contract.nft_total_supply(), U128(1));
+}
+
+#[test]
+#[should_panic(expected = "Not approved minter")]
+fn test_mint_not_approved_minter() {
+    let mut context = get_context(accounts(0));
+    testing_env!(context.build());
+    let mut contract = Contract::new_default_meta(accounts(0).into());
+
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(MINT_STORAGE_COST)
+        .predecessor_account_id(accounts(1))
+        .build());
+    contract.nft_mint("0".to_string(), sample_token_metadata(), accounts(1), None, None, None);
+}
+
+#[test]
+fn test_ownership_transfer() {
+    let mut context = get_context(accounts(0));
+    testing_env!(context.attached_deposit(1).build());
+    let mut contract = Contract::new_default_meta(accounts(0).into());
+
+    contract.propose_owner(accounts(1));
+    assert_eq!(contract.get_owner(), accounts(0));
+    assert_eq!(contract.get_pending_owner(), Some(accounts(1)));
+
+    // the proposed owner accepts
+    testing_env!(context.predecessor_account_id(accounts(1)).build());
+    contract.accept_ownership();
+    assert_eq!(contract.get_owner(), accounts(1));
+    assert_eq!(contract.get_pending_owner(), None);
+    assert_eq!(
+        get_logs(),
+        vec![format!(
+            r#"EVENT_JSON:{{"standard":"nft_extension","version":"1.0.0","event":"ownership_transfer_accept","data":[{{"old_owner_id":"{}","new_owner_id":"{}"}}]}}"#,
+            accounts(0),
+            accounts(1)
+        )]
+    );
+
+    // the new owner can propose and cancel
+    contract.propose_owner(accounts(2));
+    contract.cancel_ownership_transfer();
+    assert_eq!(contract.get_pending_owner(), None);
+    assert_eq!(contract.get_owner(), accounts(1));
+}
+
+#[test]
+#[should_panic(expected = "only pending owner")]
+fn test_accept_ownership_not_pending_owner() {
+    let mut context = get_context(accounts(0));
+    testing_env!(context.attached_deposit(1).build());
+    let mut contract = Contract::new_default_meta(accounts(0).into());
+    contract.propose_owner(accounts(1));
+
+    testing_env!(context.predecessor_account_id(accounts(2)).build());
+    contract.accept_ownership();
+}
+
+fn v1_token_metadata(title: &str, expires_at: &str) -> TokenMetadataV1 {
+    TokenMetadataV1 {
+        title: Some(title.into()),
+        description: None,
+        media: None,
+        media_hash: None,
+        copies: Some(1u64),
+        issued_at: Some("1700000000000".to_string()),
+        expires_at: Some(expires_at.to_string()),
+        starts_at: None,
+        updated_at: None,
+        extra: None,
+        reference: None,
+        reference_hash: None,
+    }
+}
+
+// 첫 배포 구조의 상태를 토큰 두 개와 함께 저장합니다.
+fn write_v1_state(owner_id: AccountId) {
+    let mut state = ContractV1 {
+        owner_id: owner_id.clone(),
+        tokens_per_owner: LookupMap::new(StorageKey::TokensPerOwner.try_to_vec().unwrap()),
+        tokens_by_id: LookupMap::new(StorageKey::TokensById.try_to_vec().unwrap()),
+        token_metadata_by_id: UnorderedMap::new(StorageKey::TokenMetadataById.try_to_vec().unwrap()),
+        metadata: LazyOption::new(
+            StorageKey::NFTContractMetadata.try_to_vec().unwrap(),
+            Some(&NFTContractMetadata {
+                spec: "nft-1.0.0".to_string(),
+                name: "Old Contract".to_string(),
+                symbol: "OLD".to_string(),
+                icon: None,
+                base_uri: None,
+                reference: None,
+                reference_hash: None,
+            }),
+        ),
+    };
+    for token_id in ["0", "1"] {
+        state.tokens_by_id.insert(
+            &token_id.to_string(),
+            &Token {
+                owner_id: owner_id.clone(),
+                approved_account_ids: HashMap::new(),
+                next_approval_id: 0,
+                royalty: HashMap::new(),
+            },
+        );
+    }
+    state.token_metadata_by_id.insert(&"0".to_string(), &v1_token_metadata("Olympus Mons", "next year"));
+    state.token_metadata_by_id.insert(&"1".to_string(), &v1_token_metadata("Mauna Kea", "1800000000000"));
+    env::state_write(&state);
+}
+
+#[test]
+fn test_migrate_from_v1() {
+    let context = get_context(accounts(0));
+    testing_env!(context.build());
+    write_v1_state(accounts(0));
+
+    // the first call only converts one token and the state keeps its old layout.
+    // its expiry can't be converted, so the token ID is logged
+    assert!(matches!(Contract::migrate(Some(1)), PromiseOrValue::Value(false)));
+    assert!(matches!(VersionedContract::read(), VersionedContract::V1(_)));
+    assert_eq!(
+        get_logs(),
+        vec![
+            "Could not convert the timestamps of tokens 0, see nft_unparsed_timestamps",
+            "Migrated 1 of 2 entries",
+        ]
+    );
+
+    // the second call picks up where the first one stopped and finishes the migration
+    assert!(matches!(Contract::migrate(None), PromiseOrValue::Value(true)));
+    let contract: Contract = env::state_read().unwrap();
+    assert_eq!(contract.owner_id, accounts(0));
+    assert_eq!(contract.nft_total_supply(), U128(2));
+    assert_eq!(contract.tokens_by_id.get(&"0".to_string()).unwrap().owner_id, accounts(0));
+    assert_eq!(contract.metadata.get().unwrap().name, "Old Contract");
+    // timestamps are converted to numbers and the ones that can't be parsed are kept as they were
+    let token_metadata = contract.token_metadata_by_id.get(&"0".to_string()).unwrap();
+    assert_eq!(token_metadata.title, Some("Olympus Mons".to_string()));
+    assert_eq!(token_metadata.issued_at, Some(U64(1_700_000_000_000)));
+    assert_eq!(token_metadata.expires_at, None);
+    assert_eq!(
+        contract.nft_unparsed_timestamps("0".to_string()),
+        Some(UnparsedTimestamps {
+            issued_at: None,
+            expires_at: Some("next year".to_string()),
+            starts_at: None,
+            updated_at: None,
+        })
+    );
+    assert_eq!(contract.nft_unparsed_timestamps("1".to_string()), None);
+    let token_metadata = contract.token_metadata_by_id.get(&"1".to_string()).unwrap();
+    assert_eq!(token_metadata.expires_at, Some(U64(1_800_000_000_000)));
+    // the owner is seeded as the first approved minter
+    assert!(contract.is_approved_minter(accounts(0)));
+    assert!(!contract.is_public_minting());
+    assert_eq!(contract.get_pending_owner(), None);
+
+    // migrating a state that already has the current layout changes nothing
+    assert!(matches!(Contract::migrate(None), PromiseOrValue::Value(true)));
+    let contract: Contract = env::state_read().unwrap();
+    assert_eq!(contract.nft_total_supply(), U128(2));
+    assert!(contract.is_approved_minter(accounts(0)));
+}
+
+#[test]
+fn test_migrate_chains_batches() {
+    let context = get_context(accounts(0));
+    testing_env!(context.build());
+    write_v1_state(accounts(0));
+    // one token more than a single call converts
+    let mut state = env::state_read::<ContractV1>().unwrap();
+    for token_id in 2..=MAX_MIGRATION_BATCH_SIZE {
+        state.token_metadata_by_id.insert(&token_id.to_string(), &v1_token_metadata("Mauna Kea", "1800000000000"));
+    }
+    env::state_write(&state);
+
+    // without a limit a full batch is converted and migrate is called again for the rest
+    assert!(matches!(Contract::migrate(None), PromiseOrValue::Promise(_)));
+    assert!(matches!(VersionedContract::read(), VersionedContract::V1(_)));
+    let receipts = get_created_receipts();
+    assert_eq!(receipts.len(), 1);
+    assert_eq!(receipts[0].receiver_id, env::current_account_id());
+    assert!(matches!(
+        &receipts[0].actions[0],
+        VmAction::FunctionCall { function_name, .. } if function_name == "migrate"
+    ));
+
+    // the chained call finishes the migration
+    assert!(matches!(Contract::migrate(None), PromiseOrValue::Value(true)));
+    let contract: Contract = env::state_read().unwrap();
+    assert_eq!(contract.token_metadata_by_id.len(), MAX_MIGRATION_BATCH_SIZE + 1);
+}
+
+#[test]
+fn test_migrate_from_v2() {
+    let context = get_context(accounts(0));
+    testing_env!(context.build());
+    write_v1_state(accounts(0));
+
+    // a V2 state keeps its approved minters and pending owner
+    let mut state = ContractV2::from(env::state_read::<ContractV1>().unwrap());
+    state.approved_minters.insert(&accounts(1));
+    state.pending_owner_id = Some(accounts(2));
+    env::state_write(&state);
+    env::storage_write(b"STATE_VERSION", &[2]);
+
+    assert!(matches!(Contract::migrate(None), PromiseOrValue::Value(true)));
+    let contract: Contract = env::state_read().unwrap();
+    assert!(contract.is_approved_minter(accounts(1)));
+    assert_eq!(contract.get_pending_owner(), Some(accounts(2)));
+    let token_metadata = contract.token_metadata_by_id.get(&"1".to_string()).unwrap();
+    assert_eq!(token_metadata.issued_at, Some(U64(1_700_000_000_000)));
+    assert_eq!(token_metadata.expires_at, Some(U64(1_800_000_000_000)));
+    assert!(matches!(VersionedContract::read(), VersionedContract::V3(_)));
+}
+
+#[test]
+fn test_burn_keeps_unparsed_timestamps_storage() {
+    let mut context = get_context(accounts(0));
+    testing_env!(context.build());
+    write_v1_state(accounts(0));
+    assert!(matches!(Contract::migrate(None), PromiseOrValue::Value(true)));
+    let mut contract: Contract = env::state_read().unwrap();
+    let unparsed_timestamps = contract.nft_unparsed_timestamps("0".to_string()).unwrap();
+    // write_v1_state doesn't fill tokens_per_owner
+    contract.internal_add_token_to_owner(&accounts(0), &"0".to_string());
+
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(1)
+        .predecessor_account_id(accounts(0))
+        .build());
+    let initial_storage_usage = env::storage_usage();
+    contract.nft_burn("0".to_string(), None, None);
+    let freed = initial_storage_usage - env::storage_usage();
+    assert_eq!(contract.nft_unparsed_timestamps("0".to_string()), None);
+
+    // the migration paid for the unparsed timestamps, so the owner only gets the token's own storage back
+    let refunded: u128 = get_created_receipts()
+        .into_iter()
+        .filter(|receipt| receipt.receiver_id == accounts(0))
+        .flat_map(|receipt| receipt.actions)
+        .map(|action| match action {
+            VmAction::Transfer { deposit } => deposit,
+            _ => 0,
+        })
+        .sum();
+    let storage_usage = env::storage_usage();
+    contract.unparsed_timestamps_by_id.insert(&"0".to_string(), &unparsed_timestamps);
+    let unparsed_storage = env::storage_usage() - storage_usage;
+    assert!(unparsed_storage > 0);
+    assert_eq!(refunded, u128::from(freed - unparsed_storage) * env::storage_byte_cost());
+}
+
+#[test]
+fn test_update_clears_unparsed_timestamps() {
+    let mut context = get_context(accounts(0));
+    testing_env!(context.build());
+    write_v1_state(accounts(0));
+    assert!(matches!(Contract::migrate(None), PromiseOrValue::Value(true)));
+    let mut contract: Contract = env::state_read().unwrap();
+    let patch = || TokenMetadata {
+        title: None,
+        description: None,
+        media: None,
+        media_hash: None,
+        copies: None,
+        issued_at: None,
+        expires_at: None,
+        starts_at: None,
+        updated_at: None,
+        extra: None,
+        reference: None,
+        reference_hash: None,
+    };
+
+    // an update that doesn't set expires_at keeps its unparsed value
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(MINT_STORAGE_COST)
+        .predecessor_account_id(accounts(0))
+        .build());
+    contract.nft_update_token_metadata(
+        "0".to_string(),
+        TokenMetadata { title: Some("Olympus".into()), ..patch() },
+        None,
+    );
+    assert_eq!(
+        contract.nft_unparsed_timestamps("0".to_string()).unwrap().expires_at,
+        Some("next year".to_string())
+    );
+
+    // once expires_at is set the unparsed value is stale and goes away
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(MINT_STORAGE_COST)
+        .predecessor_account_id(accounts(0))
+        .build());
+    contract.nft_update_token_metadata(
+        "0".to_string(),
+        TokenMetadata { expires_at: Some(U64(1_900_000_000_000)), ..patch() },
+        None,
+    );
+    assert_eq!(contract.nft_unparsed_timestamps("0".to_string()), None);
+}
+
+#[test]
+#[should_panic(expected = "only contract owner")]
+fn test_migrate_not_owner() {
+    let mut context = get_context(accounts(0));
+    testing_env!(context.build());
+    write_v1_state(accounts(0));
+
+    testing_env!(context.predecessor_account_id(accounts(1)).build());
+    Contract::migrate(None);
+}
+
+#[test]
+fn test_royalty_cap() {
+    let mut context = get_context(accounts(0));
+    testing_env!(context.build());
+    let mut contract = Contract::new_default_meta(accounts(0).into());
+    assert_eq!(contract.nft_royalty_cap(), 5000);
+
+    contract.set_royalty_cap(8000);
+    assert_eq!(contract.nft_royalty_cap(), 8000);
+
+    // royalties up to the cap are accepted
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(MINT_STORAGE_COST)
+        .predecessor_account_id(accounts(0))
+        .build());
+    let mut royalties = HashMap::new();
+    royalties.insert(accounts(1), 5000);
+    royalties.insert(accounts(2), 3000);
+    contract.nft_mint("0".to_string(), sample_token_metadata(), accounts(0), Some(royalties), None, None);
+    assert_eq!(contract.tokens_by_id.get(&"0".to_string()).unwrap().royalty.len(), 2);
+}
+
+#[test]
+#[should_panic(expected = "Total royalty of 5001 exceeds the cap of 5000")]
+fn test_mint_royalty_over_cap() {
+    let mut context = get_context(accounts(0));
+    testing_env!(context.build());
+    let mut contract = Contract::new_default_meta(accounts(0).into());
+
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(MINT_STORAGE_COST)
+        .predecessor_account_id(accounts(0))
+        .build());
+    let mut royalties = HashMap::new();
+    royalties.insert(accounts(1), 5000);
+    royalties.insert(accounts(2), 1);
+    contract.nft_mint("0".to_string(), sample_token_metadata(), accounts(0), Some(royalties), None, None);
+}
+
+#[test]
+#[should_panic(expected = "must be greater than zero")]
+fn test_batch_mint_zero_royalty() {
+    let mut context = get_context(accounts(0));
+    testing_env!(context.build());
+    let mut contract = Contract::new_default_meta(accounts(0).into());
+
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(MINT_STORAGE_COST)
+        .predecessor_account_id(accounts(0))
+        .build());
+    let mut royalties = HashMap::new();
+    royalties.insert(accounts(1), 0);
+    contract.nft_batch_mint(vec![MintArgs {
+        token_id: "0".to_string(),
+        metadata: sample_token_metadata(),
+        receiver_id: accounts(0),
+        perpetual_royalties: Some(royalties),
+        soulbound: None,
+        token_type: None,
+    }]);
+}
+
+#[test]
+fn test_soulbound_token() {
+    let mut context = get_context(accounts(0));
+    testing_env!(context.build());
+    let mut contract = Contract::new_default_meta(accounts(0).into());
+
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(MINT_STORAGE_COST)
+        .predecessor_account_id(accounts(0))
+        .build());
+    let token_id = "0".to_string();
+    contract.nft_mint(token_id.clone(), sample_token_metadata(), accounts(1), None, Some(true), None);
+    assert!(contract.nft_is_soulbound(token_id.clone()));
+    assert!(contract.nft_tokens(None, None)[0].soulbound);
+
+    // the issuer revokes the token
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(1)
+        .predecessor_account_id(accounts(0))
+        .build());
+    contract.nft_revoke_soulbound(token_id.clone(), Some("expired".to_string()));
+    assert!(contract.tokens_by_id.get(&token_id).is_none());
+    assert!(!contract.nft_is_soulbound(token_id));
+    assert_eq!(
+        get_logs(),
+        vec![format!(
+            r#"EVENT_JSON:{{"standard":"nep171","version":"1.0.0","event":"nft_burn","data":[{{"owner_id":"{}","authorized_id":"{}","token_ids":["0"],"memo":"expired"}}]}}"#,
+            accounts(1),
+            accounts(0)
+        )]
+    );
+}
+
+#[test]
+#[should_panic(expected = "Token is soulbound and cannot be transferred")]
+fn test_soulbound_token_transfer() {
+    let mut context = get_context(accounts(0));
+    testing_env!(context.build());
+    let mut contract = Contract::new_default_meta(accounts(0).into());
+
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(MINT_STORAGE_COST)
+        .predecessor_account_id(accounts(0))
+        .build());
+    let token_id = "0".to_string();
+    contract.nft_mint(token_id.clone(), sample_token_metadata(), accounts(1), None, Some(true), None);
+
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(1)
+        .predecessor_account_id(accounts(1))
+        .build());
+    contract.internal_transfer(&accounts(1), &accounts(2), &token_id, None, None);
+}
+
+#[test]
+fn test_pause() {
+    let mut context = get_context(accounts(0));
+    testing_env!(context.build());
+    let mut contract = Contract::new_default_meta(accounts(0).into());
+    contract.add_pauser(accounts(1));
+
+    // a pauser pauses transfers
+    testing_env!(context.predecessor_account_id(accounts(1)).build());
+    contract.pause(PauseFeature::Transfer);
+    assert!(contract.is_paused(PauseFeature::Transfer));
+    assert!(!contract.is_paused(PauseFeature::Mint));
+    assert_eq!(
+        get_logs(),
+        vec![format!(
+            r#"EVENT_JSON:{{"standard":"nft_extension","version":"1.0.0","event":"pause","data":[{{"feature":"transfer","account_id":"{}"}}]}}"#,
+            accounts(1)
+        )]
+    );
+
+    // minting and views keep working while transfers are paused
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(MINT_STORAGE_COST)
+        .predecessor_account_id(accounts(0))
+        .build());
+    contract.nft_mint("0".to_string(), sample_token_metadata(), accounts(0), None, None, None);
+    assert_eq!(contract.nft_tokens(None, None).len(), 1);
+
+    contract.unpause(PauseFeature::Transfer);
+    assert!(!contract.is_paused(PauseFeature::Transfer));
+    contract.internal_transfer(&accounts(0), &accounts(1), &"0".to_string(), None, None);
+}
+
+#[test]
+#[should_panic(expected = "Mint is paused")]
+fn test_mint_paused() {
+    let mut context = get_context(accounts(0));
+    testing_env!(context.build());
+    let mut contract = Contract::new_default_meta(accounts(0).into());
+    contract.pause(PauseFeature::Mint);
+
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(MINT_STORAGE_COST)
+        .predecessor_account_id(accounts(0))
+        .build());
+    contract.nft_mint("0".to_string(), sample_token_metadata(), accounts(0), None, None, None);
+}
+
+#[test]
+fn test_token_types() {
+    let mut context = get_context(accounts(0));
+    testing_env!(context.build());
+    let mut contract = Contract::new_default_meta(accounts(0).into());
+
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(MINT_STORAGE_COST)
+        .predecessor_account_id(accounts(0))
+        .build());
+    let ticket = "ticket".to_string();
+    contract.nft_mint("0".to_string(), sample_token_metadata(), accounts(0), None, None, Some(ticket.clone()));
+    contract.nft_mint("1".to_string(), sample_token_metadata(), accounts(0), None, None, Some(ticket.clone()));
+    contract.nft_mint("2".to_string(), sample_token_metadata(), accounts(0), None, None, None);
+
+    assert_eq!(contract.nft_supply_for_type(ticket.clone()), U128(2));
+    let tokens = contract.nft_tokens_for_type(ticket.clone(), Some(U128(1)), None);
+    assert_eq!(tokens.len(), 1);
+    assert_eq!(tokens[0].token_id, "1".to_string());
+    assert_eq!(tokens[0].token_type, Some(ticket.clone()));
+    assert_eq!(
+        crate::nft_core::NonFungibleTokenCore::nft_token(&contract, "2".to_string()).unwrap().token_type,
+        None
+    );
+
+    // locking the type doesn't affect untyped tokens, unlocking allows transfers again
+    contract.nft_lock_token_type(ticket.clone());
+    assert!(contract.nft_is_token_type_locked(ticket.clone()));
+    contract.internal_transfer(&accounts(0), &accounts(1), &"2".to_string(), None, None);
+    contract.nft_unlock_token_type(ticket.clone());
+    assert!(!contract.nft_is_token_type_locked(ticket.clone()));
+    contract.internal_transfer(&accounts(0), &accounts(1), &"0".to_string(), None, None);
+
+    // burning removes the token from its type
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(1)
+        .predecessor_account_id(accounts(0))
+        .build());
+    contract.nft_burn("1".to_string(), None, None);
+    assert_eq!(contract.nft_supply_for_type(ticket.clone()), U128(1));
+    assert_eq!(contract.nft_tokens_for_type(ticket, None, None)[0].token_id, "0".to_string());
+}
+
+#[test]
+#[should_panic(expected = "Token type ticket is locked")]
+fn test_token_type_locked_transfer() {
+    let mut context = get_context(accounts(0));
+    testing_env!(context.build());
+    let mut contract = Contract::new_default_meta(accounts(0).into());
+
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(MINT_STORAGE_COST)
+        .predecessor_account_id(accounts(0))
+        .build());
+    contract.nft_mint("0".to_string(), sample_token_metadata(), accounts(0), None, None, Some("ticket".to_string()));
+    contract.nft_lock_token_type("ticket".to_string());
+    contract.internal_transfer(&accounts(0), &accounts(1), &"0".to_string(), None, None);
+}
+
+#[test]
+fn test_operator_approval() {
+    let mut context = get_context(accounts(0));
+    testing_env!(context.build());
+    let mut contract = Contract::new_default_meta(accounts(0).into());
+
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(MINT_STORAGE_COST)
+        .predecessor_account_id(accounts(0))
+        .build());
+    contract.nft_mint("0".to_string(), sample_token_metadata(), accounts(0), None, None, None);
+    contract.nft_mint("1".to_string(), sample_token_metadata(), accounts(0), None, None, None);
+
+    // one approval covers every token of the owner
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(MINT_STORAGE_COST)
+        .predecessor_account_id(accounts(0))
+        .build());
+    contract.nft_approve_operator(accounts(1));
+    assert!(contract.nft_is_operator(accounts(0), accounts(1)));
+    assert!(!contract.nft_is_operator(accounts(1), accounts(0)));
+    assert_eq!(
+        get_logs(),
+        vec![format!(
+            r#"EVENT_JSON:{{"standard":"nft_extension","version":"1.0.0","event":"operator_approve","data":[{{"owner_id":"{}","operator_id":"{}"}}]}}"#,
+            accounts(0),
+            accounts(1)
+        )]
+    );
+
+    // the operator transfers both tokens without a per-token approval
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(1)
+        .predecessor_account_id(accounts(1))
+        .build());
+    contract.internal_transfer(&accounts(1), &accounts(2), &"0".to_string(), None, None);
+    assert_eq!(
+        get_logs(),
+        vec![format!(
+            r#"EVENT_JSON:{{"standard":"nep171","version":"1.0.0","event":"nft_transfer","data":[{{"authorized_id":"{}","old_owner_id":"{}","new_owner_id":"{}","token_ids":["0"]}}]}}"#,
+            accounts(1),
+            accounts(0),
+            accounts(2)
+        )]
+    );
+    contract.internal_transfer(&accounts(1), &accounts(2), &"1".to_string(), None, None);
+    assert_eq!(contract.nft_supply_for_owner(accounts(2)), U128(2));
+
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(1)
+        .predecessor_account_id(accounts(0))
+        .build());
+    contract.nft_revoke_operator(accounts(1));
+    assert!(!contract.nft_is_operator(accounts(0), accounts(1)));
+}
+
+#[test]
+#[should_panic(expected = "Cannot approve yourself as an operator")]
+fn test_approve_self_as_operator() {
+    let mut context = get_context(accounts(0));
+    testing_env!(context.build());
+    let mut contract = Contract::new_default_meta(accounts(0).into());
+
+    testing_env!(context
+        .attached_deposit(MIN_REQUIRED_APPROVAL_YOCTO)
+        .predecessor_account_id(accounts(0))
+        .build());
+    contract.nft_approve_operator(accounts(0));
+}
+
+#[test]
+fn test_operator_transfer_payout() {
+    let mut context = get_context(accounts(0));
+    testing_env!(context.build());
+    let mut contract = Contract::new_default_meta(accounts(0).into());
+
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(MINT_STORAGE_COST)
+        .predecessor_account_id(accounts(0))
+        .build());
+    contract.nft_mint("0".to_string(), sample_token_metadata(), accounts(0), None, None, None);
+    contract.nft_approve_operator(accounts(1));
+
+    // a market acting as an operator completes the sale with the approval ID it was given
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(1)
+        .predecessor_account_id(accounts(1))
+        .build());
+    let payout = contract.nft_transfer_payout(accounts(2), "0".to_string(), 1, None, U128(1_000), 10);
+    assert_eq!(crate::nft_core::NonFungibleTokenCore::nft_token(&contract, "0".to_string()).unwrap().owner_id, accounts(2));
+    assert_eq!(payout.payout.len(), 1);
+    assert_eq!(payout.payout.get(&accounts(0)), Some(&U128(1_000)));
+}
+
+#[test]
+fn test_nft_approve_with_expiry() {
+    let mut context = get_context(accounts(0));
+    testing_env!(context.block_timestamp(1_000).build());
+    let mut contract = Contract::new_default_meta(accounts(0).into());
+
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(MINT_STORAGE_COST)
+        .predecessor_account_id(accounts(0))
+        .build());
+    let token_id = "0".to_string();
+    contract.nft_mint(token_id.clone(), sample_token_metadata(), accounts(0), None, None, None);
+
+    // bob's approval expires, carol's doesn't
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(MINT_STORAGE_COST)
+        .predecessor_account_id(accounts(0))
+        .build());
+    contract.nft_approve(token_id.clone(), accounts(1), None, Some(U64(2_000)));
+    contract.nft_approve(token_id.clone(), accounts(2), None, None);
+    assert!(contract.nft_is_approved(token_id.clone(), accounts(1), None));
+
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(0)
+        .block_timestamp(2_000)
+        .predecessor_account_id(accounts(3))
+        .build());
+    assert!(!contract.nft_is_approved(token_id.clone(), accounts(1), None));
+    assert!(contract.nft_is_approved(token_id.clone(), accounts(2), None));
+
+    // anyone can prune the expired approval
+    contract.nft_prune_expired_approvals(token_id.clone());
+    let token = contract.tokens_by_id.get(&token_id).unwrap();
+    assert!(!token.approved_account_ids.contains_key(&accounts(1)));
+    assert!(token.approved_account_ids.contains_key(&accounts(2)));
+    assert!(contract.approval_expires_at.get(&token_id).is_none());
+}
+
+#[test]
+#[should_panic(expected = "has expired")]
+fn test_transfer_with_expired_approval() {
+    let mut context = get_context(accounts(0));
+    testing_env!(context.block_timestamp(1_000).build());
+    let mut contract = Contract::new_default_meta(accounts(0).into());
+
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(MINT_STORAGE_COST)
+        .predecessor_account_id(accounts(0))
+        .build());
+    let token_id = "0".to_string();
+    contract.nft_mint(token_id.clone(), sample_token_metadata(), accounts(0), None, None, None);
+    contract.nft_approve(token_id.clone(), accounts(1), None, Some(U64(2_000)));
+
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(1)
+        .block_timestamp(3_000)
+        .predecessor_account_id(accounts(1))
+        .build());
+    contract.internal_transfer(&accounts(1), &accounts(2), &token_id, Some(1), None);
+}
+
+#[test]
+fn test_nft_batch_transfer() {
+    let mut context = get_context(accounts(0));
+    testing_env!(context.build());
+    let mut contract = Contract::new_default_meta(accounts(0).into());
+
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(MINT_STORAGE_COST)
+        .predecessor_account_id(accounts(0))
+        .build());
+    contract.nft_mint("0".to_string(), sample_token_metadata(), accounts(0), None, None, None);
+    contract.nft_mint("1".to_string(), sample_token_metadata(), accounts(0), None, None, None);
+    contract.nft_approve("1".to_string(), accounts(2), None, None);
+
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(1)
+        .predecessor_account_id(accounts(0))
+        .build());
+    contract.nft_batch_transfer(accounts(1), vec!["0".to_string(), "1".to_string()], None);
+
+    assert_eq!(contract.nft_supply_for_owner(accounts(0)), U128(0));
+    assert_eq!(contract.nft_supply_for_owner(accounts(1)), U128(2));
+    assert!(contract.tokens_by_id.get(&"1".to_string()).unwrap().approved_account_ids.is_empty());
+    assert_eq!(
+        get_logs(),
+        vec![format!(
+            r#"EVENT_JSON:{{"standard":"nep171","version":"1.0.0","event":"nft_transfer","data":[{{"old_owner_id":"{}","new_owner_id":"{}","token_ids":["0","1"]}}]}}"#,
+            accounts(0),
+            accounts(1)
+        )]
+    );
+}
+
+#[test]
+fn test_nft_batch_transfer_refunds_approval_storage() {
+    let mut context = get_context(accounts(0));
+    testing_env!(context.build());
+    let mut contract = Contract::new_default_meta(accounts(0).into());
+
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(MINT_STORAGE_COST)
+        .predecessor_account_id(accounts(0))
+        .build());
+    contract.nft_mint("0".to_string(), sample_token_metadata(), accounts(0), None, None, None);
+    contract.nft_mint("1".to_string(), sample_token_metadata(), accounts(0), None, None, None);
+
+    //one approval without and one with an expiry
+    let storage_before_approvals = env::storage_usage();
+    contract.nft_approve("0".to_string(), accounts(2), None, None);
+    contract.nft_approve("1".to_string(), accounts(2), None, Some(U64(u64::MAX)));
+    let approvals_cost =
+        u128::from(env::storage_usage() - storage_before_approvals) * env::storage_byte_cost();
+
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(1)
+        .predecessor_account_id(accounts(0))
+        .build());
+    contract.nft_batch_transfer(accounts(1), vec!["0".to_string(), "1".to_string()], None);
+
+    //the previous owner gets back exactly what the approvals cost
+    let refunded: u128 = get_created_receipts()
+        .into_iter()
+        .filter(|receipt| receipt.receiver_id == accounts(0))
+        .flat_map(|receipt| receipt.actions)
+        .map(|action| match action {
+            VmAction::Transfer { deposit } => deposit,
+            _ => 0,
+        })
+        .sum();
+    assert_eq!(refunded, approvals_cost);
+}
+
+#[test]
+#[should_panic(expected = "All tokens must have the same owner")]
+fn test_nft_batch_transfer_different_owners() {
+    let mut context = get_context(accounts(0));
+    testing_env!(context.build());
+    let mut contract = Contract::new_default_meta(accounts(0).into());
+
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(MINT_STORAGE_COST)
+        .predecessor_account_id(accounts(0))
+        .build());
+    contract.nft_mint("0".to_string(), sample_token_metadata(), accounts(0), None, None, None);
+    contract.nft_mint("1".to_string(), sample_token_metadata(), accounts(1), None, None, None);
+
+    // bob lets alice transfer all of his tokens, but they still can't share a transfer log
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(MINT_STORAGE_COST)
+        .predecessor_account_id(accounts(1))
+        .build());
+    contract.nft_approve_operator(accounts(0));
+
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(1)
+        .predecessor_account_id(accounts(0))
+        .build());
+    contract.nft_batch_transfer(accounts(2), vec!["0".to_string(), "1".to_string()], None);
+}
+
+#[test]
+fn test_nft_tokens_page() {
+    let mut context = get_context(accounts(0));
+    testing_env!(context.build());
+    let mut contract = Contract::new_default_meta(accounts(0).into());
+
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(MINT_STORAGE_COST)
+        .predecessor_account_id(accounts(0))
+        .build());
+    for i in 0..5 {
+        contract.nft_mint(i.to_string(), sample_token_metadata(), accounts(0), None, None, None);
+    }
+
+    // walk through the tokens two at a time using the returned cursor
+    let first = contract.nft_tokens_page(None, Some(2));
+    assert_eq!(first.tokens.len(), 2);
+    assert_eq!(first.next_cursor, Some(U128(2)));
+    let second = contract.nft_tokens_for_owner_page(accounts(0), first.next_cursor, Some(2));
+    assert_eq!(second.tokens[0].token_id, "2".to_string());
+    assert_eq!(second.next_cursor, Some(U128(4)));
+    let last = contract.nft_tokens_page(second.next_cursor, Some(2));
+    assert_eq!(last.tokens.len(), 1);
+    assert_eq!(last.next_cursor, None);
+
+    // out of range indexes return an empty page and the limit is capped
+    assert!(contract.nft_tokens(Some(U128(10)), None).is_empty());
+    assert_eq!(contract.nft_tokens(None, Some(1_000)).len(), 5);
+}
+
+#[test]
+fn test_storage_deposit_and_mint() {
+    let mut context = get_context(accounts(0));
+    testing_env!(context.build());
+    let mut contract = Contract::new_default_meta(accounts(0).into());
+
+    // a sponsor prepays storage for alice
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(MINT_STORAGE_COST)
+        .predecessor_account_id(accounts(1))
+        .build());
+    contract.storage_deposit(Some(accounts(0)), None);
+    let min = contract.storage_balance_bounds().min.0;
+    let balance = contract.storage_balance_of(accounts(0)).unwrap();
+    assert_eq!(balance.total, U128(MINT_STORAGE_COST));
+    assert_eq!(balance.available, U128(MINT_STORAGE_COST - min));
+    assert!(contract.storage_balance_of(accounts(1)).is_none());
+
+    // alice mints without attaching a deposit and the storage is drawn from her balance
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(0)
+        .predecessor_account_id(accounts(0))
+        .build());
+    contract.nft_mint("0".to_string(), sample_token_metadata(), accounts(0), None, None, None);
+    let after_mint = contract.storage_balance_of(accounts(0)).unwrap();
+    assert!(after_mint.total.0 < MINT_STORAGE_COST);
+    assert!(after_mint.total.0 >= min);
+
+    // the rest of the available balance can be withdrawn, leaving the minimum
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(1)
+        .predecessor_account_id(accounts(0))
+        .build());
+    let withdrawn = contract.storage_withdraw(None);
+    assert_eq!(withdrawn.total, U128(min));
+    assert_eq!(withdrawn.available, U128(0));
+
+    assert!(contract.storage_unregister(None));
+    assert!(contract.storage_balance_of(accounts(0)).is_none());
+    assert!(!contract.storage_unregister(None));
+}
+
+#[test]
+#[should_panic(expected = "Requires minimum deposit of")]
+fn test_storage_deposit_below_minimum() {
+    let mut context = get_context(accounts(0));
+    testing_env!(context.build());
+    let mut contract = Contract::new_default_meta(accounts(0).into());
+
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(1)
+        .predecessor_account_id(accounts(0))
+        .build());
+    contract.storage_deposit(None, None);
+}
+
+#[test]
+#[should_panic(expected = "or deposit it with storage_deposit to cover storage")]
+fn test_mint_without_storage_balance() {
+    let mut context = get_context(accounts(0));
+    testing_env!(context.build());
+    let mut contract = Contract::new_default_meta(accounts(0).into());
+
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(0)
+        .predecessor_account_id(accounts(0))
+        .build());
+    contract.nft_mint("0".to_string(), sample_token_metadata(), accounts(0), None, None, None);
+}
+
+#[test]
+fn test_sponsored_mint() {
+    let mut context = get_context(accounts(0));
+    testing_env!(context.build());
+    let mut contract = Contract::new_default_meta(accounts(0).into());
+
+    // the owner funds the pool and whitelists bob as a relayer that can mint
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(MINT_STORAGE_COST)
+        .predecessor_account_id(accounts(0))
+        .build());
+    contract.sponsor_pool_fund();
+    contract.set_sponsor_caps(U128(MINT_STORAGE_COST / 10), U128(MINT_STORAGE_COST));
+    contract.add_approved_minter(accounts(1));
+    contract.add_sponsor_relayer(accounts(1));
+    assert!(contract.is_sponsor_relayer(accounts(1)));
+
+    // bob mints for charlie without attaching a deposit and the pool pays for it
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(0)
+        .predecessor_account_id(accounts(1))
+        .build());
+    contract.nft_mint("0".to_string(), sample_token_metadata(), accounts(2), None, None, None);
+
+    let drawn = contract.sponsored_for_receiver(accounts(2)).0;
+    assert!(drawn > 0);
+    assert_eq!(contract.sponsor_pool_balance(), U128(MINT_STORAGE_COST - drawn));
+    let info = contract.sponsor_pool_info();
+    assert_eq!(info.remaining_today, U128(MINT_STORAGE_COST - drawn));
+    assert!(get_logs().last().unwrap().contains("\"event\":\"sponsor_pool_draw\""));
+
+    // the owner can take back what wasn't drawn
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(1)
+        .predecessor_account_id(accounts(0))
+        .build());
+    assert_eq!(contract.sponsor_pool_withdraw(U128(MINT_STORAGE_COST - drawn)), U128(0));
+}
+
+#[test]
+fn test_sponsored_burn_refunds_pool() {
+    let mut context = get_context(accounts(0));
+    testing_env!(context.build());
+    let mut contract = Contract::new_default_meta(accounts(0).into());
+
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(MINT_STORAGE_COST)
+        .predecessor_account_id(accounts(0))
+        .build());
+    contract.sponsor_pool_fund();
+    contract.set_sponsor_caps(U128(MINT_STORAGE_COST / 10), U128(MINT_STORAGE_COST));
+    contract.add_sponsor_relayer(accounts(0));
+
+    // the pool pays for everything the mint stores, including its own records
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(0)
+        .predecessor_account_id(accounts(0))
+        .build());
+    let initial_storage_usage = env::storage_usage();
+    contract.nft_mint("0".to_string(), sample_token_metadata(), accounts(2), None, None, None);
+    let storage_used = env::storage_usage() - initial_storage_usage;
+    let drawn = contract.sponsored_for_receiver(accounts(2)).0;
+    assert_eq!(drawn, u128::from(storage_used) * env::storage_byte_cost());
+
+    // burning the token gives the freed storage back to the pool instead of charlie
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(1)
+        .predecessor_account_id(accounts(2))
+        .build());
+    let initial_storage_usage = env::storage_usage();
+    contract.nft_burn("0".to_string(), None, None);
+    let freed = u128::from(initial_storage_usage - env::storage_usage()) * env::storage_byte_cost();
+    assert_eq!(contract.sponsor_pool_balance(), U128(MINT_STORAGE_COST - drawn + freed));
+    assert!(get_created_receipts().iter().all(|receipt| receipt.receiver_id != accounts(2)));
+}
+
+#[test]
+#[should_panic(expected = "would exceed the per-receiver cap")]
+fn test_sponsored_mint_over_receiver_cap() {
+    let mut context = get_context(accounts(0));
+    testing_env!(context.build());
+    let mut contract = Contract::new_default_meta(accounts(0).into());
+
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(MINT_STORAGE_COST)
+        .predecessor_account_id(accounts(0))
+        .build());
+    contract.sponsor_pool_fund();
+    contract.set_sponsor_caps(U128(1), U128(MINT_STORAGE_COST));
+    contract.add_sponsor_relayer(accounts(0));
+
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(0)
+        .predecessor_account_id(accounts(0))
+        .build());
+    contract.nft_mint("0".to_string(), sample_token_metadata(), accounts(2), None, None, None);
+}
+
+#[test]
+fn test_contract_source_metadata() {
+    let context = get_context(accounts(0));
+    testing_env!(context.build());
+    let contract = Contract::new_default_meta(accounts(0).into());
+
+    let source_metadata = contract.contract_source_metadata();
+    assert_eq!(source_metadata.version, Some(env!("CARGO_PKG_VERSION").to_string()));
+    assert!(source_metadata.link.is_some());
+    // nft_metadata_update was added in NEP-171 1.2.0
+    let nep171 = source_metadata.standards.iter().find(|s| s.standard == "nep171").unwrap();
+    assert_eq!(nep171.version, "1.2.0");
+    let standards: Vec<String> = source_metadata.standards.into_iter().map(|s| s.standard).collect();
+    for standard in ["nep171", "nep177", "nep178", "nep181", "nep199", "nep297"] {
+        assert!(standards.contains(&standard.to_string()));
+    }
+}
+
+#[test]
+fn test_mint_sets_issued_at() {
+    let mut context = get_context(accounts(0));
+    testing_env!(context.build());
+    let mut contract = Contract::new_default_meta(accounts(0).into());
+
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(MINT_STORAGE_COST)
+        .predecessor_account_id(accounts(0))
+        .block_timestamp(5_000_000_000)
+        .build());
+    // values passed in by the minter are replaced by the block timestamp
+    let mut metadata = sample_token_metadata();
+    metadata.issued_at = Some(U64(1));
+    metadata.updated_at = Some(U64(1));
+    contract.nft_mint("0".to_string(), metadata, accounts(0), None, None, None);
+
+    let token = crate::nft_core::NonFungibleTokenCore::nft_token(&contract, "0".to_string()).unwrap();
+    assert_eq!(token.metadata.issued_at, Some(U64(5_000)));
+    assert_eq!(token.metadata.updated_at, None);
+    // NEP-177 timestamps are strings on the wire
+    let json = near_sdk::serde_json::to_value(&token.metadata).unwrap();
+    assert_eq!(json["issued_at"], "5000");
+    assert!(json["updated_at"].is_null());
+}
+
+// 주어진 유효 기간으로 토큰 메타데이터를 만듭니다.
+fn ticket_metadata(starts_at: Option<u64>, expires_at: Option<u64>) -> TokenMetadata {
+    let mut metadata = sample_token_metadata();
+    metadata.starts_at = starts_at.map(U64);
+    metadata.expires_at = expires_at.map(U64);
+    metadata
+}
+
+#[test]
+fn test_token_validity() {
+    let mut context = get_context(accounts(0));
+    testing_env!(context.build());
+    let mut contract = Contract::new_default_meta(accounts(0).into());
+
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(MINT_STORAGE_COST)
+        .predecessor_account_id(accounts(0))
+        .block_timestamp(1_000_000_000)
+        .build());
+    contract.nft_mint("early".to_string(), ticket_metadata(Some(2_000), None), accounts(0), None, None, None);
+    contract.nft_mint("ticket".to_string(), ticket_metadata(None, Some(3_000)), accounts(0), None, None, None);
+    contract.nft_mint("forever".to_string(), sample_token_metadata(), accounts(0), None, None, None);
+
+    // at 1000ms the early token hasn't started yet
+    assert!(!contract.nft_is_valid("early".to_string()));
+    assert!(contract.nft_is_valid("ticket".to_string()));
+    assert!(!contract.nft_is_valid("missing".to_string()));
+
+    // at 3000ms the ticket has expired and is flagged in nft_token
+    testing_env!(context.block_timestamp(3_000_000_000).build());
+    assert!(contract.nft_is_valid("early".to_string()));
+    assert!(!contract.nft_is_valid("ticket".to_string()));
+    assert!(contract.nft_is_valid("forever".to_string()));
+    let ticket = crate::nft_core::NonFungibleTokenCore::nft_token(&contract, "ticket".to_string()).unwrap();
+    assert!(ticket.expired);
+
+    // only the ticket expires before 5000ms
+    let expiring = contract.nft_tokens_expiring_before(5_000, None, None);
+    assert_eq!(expiring.tokens.len(), 1);
+    assert_eq!(expiring.tokens[0].token_id, "ticket".to_string());
+    assert_eq!(expiring.next_cursor, None);
+    // pages scan the tokens in order, so the first page of one token doesn't hold the ticket
+    let first = contract.nft_tokens_expiring_before(5_000, None, Some(1));
+    assert!(first.tokens.is_empty());
+    assert_eq!(first.next_cursor, Some(U128(1)));
+}
+
+#[test]
+#[should_panic(expected = "Token ticket has expired")]
+fn test_transfer_expired_token() {
+    let mut context = get_context(accounts(0));
+    testing_env!(context.build());
+    let mut contract = Contract::new_default_meta(accounts(0).into());
+
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(MINT_STORAGE_COST)
+        .predecessor_account_id(accounts(0))
+        .build());
+    contract.nft_mint("ticket".to_string(), ticket_metadata(None, Some(3_000)), accounts(0), None, None, None);
+
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(1)
+        .block_timestamp(3_000_000_000)
+        .build());
+    contract.internal_transfer(&accounts(0), &accounts(1), &"ticket".to_string(), None, None);
+}
+
+#[test]
+#[should_panic(expected = "Token early is not valid until 2000")]
+fn test_transfer_token_before_start() {
+    let mut context = get_context(accounts(0));
+    testing_env!(context.build());
+    let mut contract = Contract::new_default_meta(accounts(0).into());
+
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(MINT_STORAGE_COST)
+        .predecessor_account_id(accounts(0))
+        .build());
+    contract.nft_mint("early".to_string(), ticket_metadata(Some(2_000), None), accounts(0), None, None, None);
+
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(1)
+        .build());
+    contract.internal_transfer(&accounts(0), &accounts(1), &"early".to_string(), None, None);
+}
+
+#[test]
+fn test_nft_resolve_transfer_restores_approval_expiries() {
+    use crate::nft_core::NonFungibleTokenResolver;
+
+    let mut context = get_context(accounts(0));
+    testing_env!(context.build());
+    let mut contract = Contract::new_default_meta(accounts(0).into());
+
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(MINT_STORAGE_COST)
+        .predecessor_account_id(accounts(0))
+        .build());
+    let token_id = "0".to_string();
+    contract.nft_mint(token_id.clone(), sample_token_metadata(), accounts(0), None, None, None);
+    contract.nft_approve(token_id.clone(), accounts(2), None, Some(U64(5_000)));
+    let approved_account_ids = contract.tokens_by_id.get(&token_id).unwrap().approved_account_ids;
+    let approval_expiries = contract.approval_expires_at.get(&token_id).unwrap();
+
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(1)
+        .predecessor_account_id(accounts(0))
+        .build());
+    crate::nft_core::NonFungibleTokenCore::nft_transfer_call(
+        &mut contract,
+        accounts(1),
+        token_id.clone(),
+        None,
+        None,
+        "".to_string(),
+    );
+
+    //the receiver approves and revokes accounts in nft_on_transfer, which keeps the expiry of the previous approval
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(MINT_STORAGE_COST)
+        .predecessor_account_id(accounts(1))
+        .build());
+    contract.nft_approve(token_id.clone(), accounts(3), None, Some(U64(6_000)));
+    testing_env!(context.storage_usage(env::storage_usage()).attached_deposit(1).build());
+    contract.nft_revoke_all(token_id.clone());
+    contract.nft_prune_expired_approvals(token_id.clone());
+    assert_eq!(contract.approval_expires_at.get(&token_id), Some(approval_expiries.clone()));
+
+    //the receiver returns the token, so the approval is restored with its expiry
+    testing_env!(
+        context.storage_usage(env::storage_usage()).predecessor_account_id(accounts(0)).build(),
+        near_sdk::VMConfig::test(),
+        near_sdk::RuntimeFeesConfig::test(),
+        Default::default(),
+        vec![near_sdk::PromiseResult::Successful(b"true".to_vec())]
+    );
+    let transferred = contract.nft_resolve_transfer(
+        None,
+        accounts(0),
+        accounts(1),
+        token_id.clone(),
+        approved_account_ids.clone(),
+        None,
+    );
+    assert!(!transferred);
+    let token = contract.tokens_by_id.get(&token_id).unwrap();
+    assert_eq!(token.owner_id, accounts(0));
+    assert_eq!(token.approved_account_ids, approved_account_ids);
+    assert_eq!(contract.approval_expires_at.get(&token_id), Some(approval_expiries));
+}
@@ -53,6 +53,9 @@ impl NonFungibleTokenCore for Contract {
             "Predecessor must be the token owner."
         );
 
+        //soulbound tokens can't be approved since nobody could ever transfer them
+        self.assert_not_soulbound(&token);
+
         //get the next approval ID if we need a new approval
         let approval_id: u64 = token.next_approval_id;
 
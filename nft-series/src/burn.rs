@@ -0,0 +1,124 @@
+use crate::*;
+
+#[near_bindgen]
+impl Contract {
+    /// Burn a token, removing it from its owner, its series and the token collection. The freed storage
+    /// is refunded to the token owner. The caller must be the token owner or an approved account.
+    /// The token's edition number is never handed out again by `nft_mint`.
+    #[payable]
+    pub fn nft_burn(&mut self, token_id: TokenId, approval_id: Option<u64>, memo: Option<String>) {
+        //assert that the user attached exactly 1 yoctoNEAR for security reasons
+        assert_one_yocto();
+
+        //get the sender and the token object
+        let sender_id = env::predecessor_account_id();
+        let token = self.tokens_by_id.get(&token_id).expect("No token");
+
+        //make sure the sender is the owner or an approved account for the token
+        self.internal_assert_authorized(&sender_id, &token, approval_id);
+
+        //the token owner gets the freed storage back
+        let refund_id = token.owner_id.clone();
+        self.internal_burn(&sender_id, &token_id, token, refund_id, memo);
+    }
+
+    /// Revoke a token of a soulbound series, burning it on behalf of its owner. Only the series owner (the issuer)
+    /// or the contract owner can revoke it, and the freed storage is refunded to the caller.
+    #[payable]
+    pub fn nft_revoke_soulbound(&mut self, token_id: TokenId, memo: Option<String>) {
+        //assert that the user attached exactly 1 yoctoNEAR for security reasons
+        assert_one_yocto();
+
+        let sender_id = env::predecessor_account_id();
+        let token = self.tokens_by_id.get(&token_id).expect("No token");
+        let series = self.series_by_id.get(&token.series_id).expect("Not a series");
+        require!(series.soulbound, "Only soulbound tokens can be revoked");
+
+        //make sure the sender issued the series or is the contract owner
+        require!(
+            sender_id == series.owner_id || sender_id == self.owner_id,
+            "Only the issuer can revoke the token"
+        );
+
+        self.internal_burn(&sender_id, &token_id, token, sender_id.clone(), memo);
+    }
+}
+
+impl Contract {
+    /// Remove the token from its owner, its series and the token collection, log the burn and refund the freed
+    /// storage to the passed in account. The storage used by approvals is always refunded to the token owner.
+    pub(crate) fn internal_burn(
+        &mut self,
+        sender_id: &AccountId,
+        token_id: &TokenId,
+        token: Token,
+        refund_id: AccountId,
+        memo: Option<String>,
+    ) {
+        // Measure the initial storage being used on the contract
+        let initial_storage_usage = env::storage_usage();
+
+        // Remove the token from its series. The series keeps its minted count so the edition isn't reused.
+        let mut series = self
+            .series_by_id
+            .get(&token.series_id)
+            .expect("Not a series");
+        series.tokens.remove(token_id);
+        self.series_by_id.insert(&token.series_id, &series);
+
+        //remove the token from its owner and from the tokens by ID
+        self.internal_remove_token_from_owner(&token.owner_id, token_id);
+        self.tokens_by_id.remove(token_id);
+        self.issued_at_by_token_id.remove(token_id);
+
+        //if the sender isn't the owner of the token, we set the authorized ID equal to the sender.
+        let authorized_id = if sender_id != &token.owner_id {
+            Some(sender_id.to_string())
+        } else {
+            None
+        };
+
+        // Construct the burn log as per the events standard.
+        let nft_burn_log: EventLog = EventLog {
+            // Standard name ("nep171").
+            standard: NFT_STANDARD_NAME.to_string(),
+            // Version of the standard ("nft-1.0.0").
+            version: NFT_METADATA_SPEC.to_string(),
+            // The data related with the event stored in a vector.
+            event: EventLogVariant::NftBurn(vec![NftBurnLog {
+                // The owner of the burned token.
+                owner_id: token.owner_id.to_string(),
+                // The optional authorized account ID to burn the token on behalf of the owner.
+                authorized_id,
+                // A vector containing the token IDs as strings.
+                token_ids: vec![token_id.to_string()],
+                // An optional memo to include.
+                memo,
+            }]),
+        };
+
+        // Log the serialized json.
+        env::log_str(&nft_burn_log.to_string());
+
+        //the freed storage includes the approved account IDs, which are refunded separately below
+        let approvals_storage: u64 = token
+            .approved_account_ids
+            .keys()
+            .map(bytes_for_approved_account_id)
+            .sum();
+        let freed_storage_in_bytes = initial_storage_usage
+            .saturating_sub(env::storage_usage())
+            .saturating_sub(approvals_storage);
+
+        //refund the storage released by the token
+        if freed_storage_in_bytes > 0 {
+            Promise::new(refund_id)
+                .transfer(Balance::from(freed_storage_in_bytes) * env::storage_byte_cost());
+        }
+
+        //we refund the owner for releasing the storage used up by the approved account IDs
+        if !token.approved_account_ids.is_empty() {
+            refund_approved_account_ids(token.owner_id, &token.approved_account_ids);
+        }
+    }
+}
@@ -1,5 +1,47 @@
 use crate::*;
 use crate::nft_core::NonFungibleTokenCore;
+use near_sdk::collections::Vector;
+
+/// Number of items returned in a page when no limit is passed in
+pub const DEFAULT_PAGE_LIMIT: u64 = 50;
+/// Maximum number of items returned in a page, to stay within the gas limit of view calls
+pub const MAX_PAGE_LIMIT: u64 = 100;
+
+/// A page of tokens along with the cursor to fetch the next page
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct JsonTokenPage {
+    pub tokens: Vec<JsonToken>,
+    // Value to pass as from_index to get the next page. None on the last page
+    pub next_cursor: Option<U128>,
+}
+
+/// A page of series along with the cursor to fetch the next page
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct JsonSeriesPage {
+    pub series: Vec<JsonSeries>,
+    // Value to pass as from_index to get the next page. None on the last page
+    pub next_cursor: Option<U128>,
+}
+
+//returns the items of one page of the vector, along with the index the next page starts from if there is one.
+//pages index directly into the vector so their cost only depends on the limit.
+pub(crate) fn paginate<T: BorshSerialize + BorshDeserialize>(
+    items: &Vector<T>,
+    from_index: Option<U128>,
+    limit: Option<u64>,
+) -> (Vec<T>, Option<U128>) {
+    //where to start pagination - if we have a from_index, we'll use that - otherwise start from 0 index
+    let start = u128::from(from_index.unwrap_or(U128(0))).min(items.len() as u128) as u64;
+    //take "limit" elements, at most MAX_PAGE_LIMIT. If we didn't specify a limit, use 50
+    let limit = limit.unwrap_or(DEFAULT_PAGE_LIMIT).min(MAX_PAGE_LIMIT);
+    let end = (start + limit).min(items.len());
+
+    let page = (start..end).map(|index| items.get(index).unwrap()).collect();
+    let next_cursor = if end < items.len() { Some(U128(end as u128)) } else { None };
+    (page, next_cursor)
+}
 
 
 /// Struct to return in views to query for specific data related to a series
@@ -13,6 +55,8 @@ pub struct JsonSeries {
     royalty: Option<HashMap<AccountId, u32>>,
     // Owner of the collection
     owner_id: AccountId,
+    // Whether the tokens in the collection are soulbound
+    soulbound: bool,
 }
 
 #[near_bindgen]
@@ -25,20 +69,14 @@ impl Contract {
 
     //Query for nft tokens on the contract regardless of the owner using pagination
     pub fn nft_tokens(&self, from_index: Option<U128>, limit: Option<u64>) -> Vec<JsonToken> {
-        //where to start pagination - if we have a from_index, we'll use that - otherwise start from 0 index
-        let start = u128::from(from_index.unwrap_or(U128(0)));
-
-        //iterate through each token using an iterator
-        self.tokens_by_id
-            .keys()
-            //skip to the index we specified in the start variable
-            .skip(start as usize)
-            //take the first "limit" elements in the vector. If we didn't specify a limit, use 50
-            .take(limit.unwrap_or(50) as usize)
-            //we'll map the token IDs which are strings into Json Tokens
-            .map(|token_id| self.nft_token(token_id.clone()).unwrap())
-            //since we turned the keys into an iterator, we need to turn it back into a vector to return
-            .collect()
+        self.nft_tokens_page(from_index, limit).tokens
+    }
+
+    //Query for nft tokens like nft_tokens, along with the cursor of the next page
+    pub fn nft_tokens_page(&self, from_index: Option<U128>, limit: Option<u64>) -> JsonTokenPage {
+        let (token_ids, next_cursor) =
+            paginate(self.tokens_by_id.keys_as_vector(), from_index, limit);
+        self.json_token_page(token_ids, next_cursor)
     }
 
     //get the total supply of NFTs for a given owner
@@ -62,30 +100,24 @@ impl Contract {
         from_index: Option<U128>,
         limit: Option<u64>,
     ) -> Vec<JsonToken> {
-        //get the set of tokens for the passed in owner
-        let tokens_for_owner_set = self.tokens_per_owner.get(&account_id);
-        //if there is some set of tokens, we'll set the tokens variable equal to that set
-        let tokens = if let Some(tokens_for_owner_set) = tokens_for_owner_set {
-            tokens_for_owner_set
-        } else {
-            //if there is no set of tokens, we'll simply return an empty vector.
-            return vec![];
-        };
+        self.nft_tokens_for_owner_page(account_id, from_index, limit).tokens
+    }
 
-        //where to start pagination - if we have a from_index, we'll use that - otherwise start from 0 index
-        let start = u128::from(from_index.unwrap_or(U128(0)));
-
-        //iterate through the keys vector
-        tokens
-            .iter()
-            //skip to the index we specified in the start variable
-            .skip(start as usize)
-            //take the first "limit" elements in the vector. If we didn't specify a limit, use 50
-            .take(limit.unwrap_or(50) as usize)
-            //we'll map the token IDs which are strings into Json Tokens
-            .map(|token_id| self.nft_token(token_id.clone()).unwrap())
-            //since we turned the keys into an iterator, we need to turn it back into a vector to return
-            .collect()
+    //Query for the tokens of an owner like nft_tokens_for_owner, along with the cursor of the next page
+    pub fn nft_tokens_for_owner_page(
+        &self,
+        account_id: AccountId,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> JsonTokenPage {
+        //get the set of tokens for the passed in owner. If there is none, we return an empty page
+        let (token_ids, next_cursor) = match self.tokens_per_owner.get(&account_id) {
+            Some(tokens_for_owner_set) => {
+                paginate(tokens_for_owner_set.as_vector(), from_index, limit)
+            }
+            None => (vec![], None),
+        };
+        self.json_token_page(token_ids, next_cursor)
     }
 
     // Get the total supply of series on the contract
@@ -95,20 +127,20 @@ impl Contract {
 
     // Paginate through all the series on the contract and return the a vector of JsonSeries
     pub fn get_series(&self, from_index: Option<U128>, limit: Option<u64>) -> Vec<JsonSeries> {
-        //where to start pagination - if we have a from_index, we'll use that - otherwise start from 0 index
-        let start = u128::from(from_index.unwrap_or(U128(0)));
-
-        //iterate through each series using an iterator
-        self.series_by_id
-            .keys()
-            //skip to the index we specified in the start variable
-            .skip(start as usize)
-            //take the first "limit" elements in the vector. If we didn't specify a limit, use 50
-            .take(limit.unwrap_or(50) as usize)
-            //we'll map the series IDs which are strings into Json Series
-            .map(|series_id| self.get_series_details(series_id.clone()).unwrap())
-            //since we turned the keys into an iterator, we need to turn it back into a vector to return
-            .collect()
+        self.get_series_page(from_index, limit).series
+    }
+
+    // Paginate through the series like get_series, along with the cursor of the next page
+    pub fn get_series_page(&self, from_index: Option<U128>, limit: Option<u64>) -> JsonSeriesPage {
+        let (series_ids, next_cursor) =
+            paginate(self.series_by_id.keys_as_vector(), from_index, limit);
+        JsonSeriesPage {
+            series: series_ids
+                .into_iter()
+                .map(|series_id| self.get_series_details(series_id).unwrap())
+                .collect(),
+            next_cursor,
+        }
     }
 
     // get info for a specific series
@@ -122,6 +154,7 @@ impl Contract {
                 metadata: series.metadata,
                 royalty: series.royalty,
                 owner_id: series.owner_id,
+                soulbound: series.soulbound,
             })
         } else {
             //if there isn't a series, we'll return None
@@ -149,27 +182,58 @@ impl Contract {
         from_index: Option<U128>,
         limit: Option<u64>,
     ) -> Vec<JsonToken> {
-        // Get the series and its tokens
-        let series = self.series_by_id.get(&id);
-        let tokens = if let Some(series) = series {
-            series.tokens
-        } else {
-            return vec![];
+        self.nft_tokens_for_series_page(id, from_index, limit).tokens
+    }
+
+    /// Paginate through NFTs within a given series like nft_tokens_for_series, along with the cursor of the next page
+    pub fn nft_tokens_for_series_page(
+        &self,
+        id: u64,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> JsonTokenPage {
+        // Get the series and its tokens. If there is no series, we return an empty page
+        let (token_ids, next_cursor) = match self.series_by_id.get(&id) {
+            Some(series) => paginate(series.tokens.as_vector(), from_index, limit),
+            None => (vec![], None),
         };
+        self.json_token_page(token_ids, next_cursor)
+    }
+
+    /// Query for the tokens whose series expires before a time (Unix epoch milliseconds).
+    /// Each call scans one page of all tokens, so a page can hold fewer tokens than the limit.
+    /// Keep following `next_cursor` until it's None.
+    pub fn nft_tokens_expiring_before(
+        &self,
+        ts: u64,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> JsonTokenPage {
+        let (token_ids, next_cursor) =
+            paginate(self.tokens_by_id.keys_as_vector(), from_index, limit);
+        let token_ids = token_ids
+            .into_iter()
+            .filter(|token_id| {
+                let token = self.tokens_by_id.get(token_id).unwrap();
+                self.series_by_id
+                    .get(&token.series_id)
+                    .and_then(|series| series.metadata.expires_at)
+                    .is_some_and(|expires_at| expires_at.0 < ts)
+            })
+            .collect();
+        self.json_token_page(token_ids, next_cursor)
+    }
+}
 
-        //where to start pagination - if we have a from_index, we'll use that - otherwise start from 0 index
-        let start = u128::from(from_index.unwrap_or(U128(0)));
-
-        //iterate through the tokens
-        tokens
-            .iter()
-            //skip to the index we specified in the start variable
-            .skip(start as usize)
-            //take the first "limit" elements in the vector. If we didn't specify a limit, use 50
-            .take(limit.unwrap_or(50) as usize)
-            //we'll map the token IDs which are strings into Json Tokens
-            .map(|token_id| self.nft_token(token_id.clone()).unwrap())
-            //since we turned the keys into an iterator, we need to turn it back into a vector to return
-            .collect()
+impl Contract {
+    //map a page of token IDs into Json Tokens
+    fn json_token_page(&self, token_ids: Vec<TokenId>, next_cursor: Option<U128>) -> JsonTokenPage {
+        JsonTokenPage {
+            tokens: token_ids
+                .into_iter()
+                .map(|token_id| self.nft_token(token_id).unwrap())
+                .collect(),
+            next_cursor,
+        }
     }
 }
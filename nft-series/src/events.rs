@@ -2,8 +2,12 @@ use std::fmt;
 
 use near_sdk::serde::{Deserialize, Serialize};
 
+use crate::PauseFeature;
+
 /// Enum that represents the data type of the EventLog.
-/// The enum can either be an NftMint or an NftTransfer.
+/// The enum can either be one of the NEP-171 events (NftMint, NftTransfer, NftBurn, ContractMetadataUpdate)
+/// or one of this contract's own extension events (OwnershipTransferPropose, OwnershipTransferAccept,
+/// OwnershipTransferCancel, Pause, Unpause, OperatorApprove, OperatorRevoke).
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(tag = "event", content = "data")]
 #[serde(rename_all = "snake_case")]
@@ -12,6 +16,15 @@ use near_sdk::serde::{Deserialize, Serialize};
 pub enum EventLogVariant {
     NftMint(Vec<NftMintLog>),
     NftTransfer(Vec<NftTransferLog>),
+    NftBurn(Vec<NftBurnLog>),
+    ContractMetadataUpdate(Vec<ContractMetadataUpdateLog>),
+    OwnershipTransferPropose(Vec<OwnershipTransferLog>),
+    OwnershipTransferAccept(Vec<OwnershipTransferLog>),
+    OwnershipTransferCancel(Vec<OwnershipTransferLog>),
+    Pause(Vec<PauseLog>),
+    Unpause(Vec<PauseLog>),
+    OperatorApprove(Vec<OperatorLog>),
+    OperatorRevoke(Vec<OperatorLog>),
 }
 
 /// Interface to capture data about an event
@@ -78,6 +91,86 @@ pub struct NftTransferLog {
     pub memo: Option<String>,
 }
 
+/// An event log to capture token burning
+///
+/// Arguments
+/// * `owner_id`: owner of the burned tokens
+/// * `authorized_id`: approved account to burn
+/// * `token_ids`: ["1", "12345abc"]
+/// * `memo`: optional message
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct NftBurnLog {
+    pub owner_id: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub authorized_id: Option<String>,
+
+    pub token_ids: Vec<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+/// An event log to capture contract metadata updates
+///
+/// Arguments
+/// * `memo`: optional message
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ContractMetadataUpdateLog {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+/// An event log to capture each step of a contract ownership transfer
+///
+/// Arguments
+/// * `old_owner_id`: "owner.near"
+/// * `new_owner_id`: "multisig.near"
+/// * `memo`: optional message
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct OwnershipTransferLog {
+    pub old_owner_id: String,
+    pub new_owner_id: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+/// An event log to capture a feature being paused or unpaused
+///
+/// Arguments
+/// * `feature`: "mint" or "transfer"
+/// * `account_id`: the account that paused or unpaused the feature
+/// * `memo`: optional message
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PauseLog {
+    pub feature: PauseFeature,
+    pub account_id: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+/// An event log to capture an operator being approved or revoked for all of an owner's tokens
+///
+/// Arguments
+/// * `owner_id`: "owner.near"
+/// * `operator_id`: "market.near"
+/// * `memo`: optional message
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct OperatorLog {
+    pub owner_id: String,
+    pub operator_id: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -135,4 +228,78 @@ mod tests {
         };
         assert_eq!(expected, log.to_string());
     }
+
+    #[test]
+    fn nep_format_burn() {
+        let expected = r#"EVENT_JSON:{"standard":"nep171","version":"1.0.0","event":"nft_burn","data":[{"owner_id":"user1.near","token_ids":["1:1","1:2"],"memo":"burn"}]}"#;
+        let log = EventLog {
+            standard: "nep171".to_string(),
+            version: "1.0.0".to_string(),
+            event: EventLogVariant::NftBurn(vec![NftBurnLog {
+                owner_id: "user1.near".to_string(),
+                authorized_id: None,
+                token_ids: vec!["1:1".to_string(), "1:2".to_string()],
+                memo: Some("burn".to_owned()),
+            }]),
+        };
+        assert_eq!(expected, log.to_string());
+    }
+
+    #[test]
+    fn nep_format_contract_metadata_update() {
+        let expected = r#"EVENT_JSON:{"standard":"nep171","version":"1.1.0","event":"contract_metadata_update","data":[{}]}"#;
+        let log = EventLog {
+            standard: "nep171".to_string(),
+            version: "1.1.0".to_string(),
+            event: EventLogVariant::ContractMetadataUpdate(vec![ContractMetadataUpdateLog {
+                memo: None,
+            }]),
+        };
+        assert_eq!(expected, log.to_string());
+    }
+
+    #[test]
+    fn extension_format_ownership_transfer_accept() {
+        let expected = r#"EVENT_JSON:{"standard":"nft_extension","version":"1.0.0","event":"ownership_transfer_accept","data":[{"old_owner_id":"owner.near","new_owner_id":"multisig.near"}]}"#;
+        let log = EventLog {
+            standard: "nft_extension".to_string(),
+            version: "1.0.0".to_string(),
+            event: EventLogVariant::OwnershipTransferAccept(vec![OwnershipTransferLog {
+                old_owner_id: "owner.near".to_string(),
+                new_owner_id: "multisig.near".to_string(),
+                memo: None,
+            }]),
+        };
+        assert_eq!(expected, log.to_string());
+    }
+
+    #[test]
+    fn extension_format_pause() {
+        let expected = r#"EVENT_JSON:{"standard":"nft_extension","version":"1.0.0","event":"pause","data":[{"feature":"mint","account_id":"owner.near"}]}"#;
+        let log = EventLog {
+            standard: "nft_extension".to_string(),
+            version: "1.0.0".to_string(),
+            event: EventLogVariant::Pause(vec![PauseLog {
+                feature: PauseFeature::Mint,
+                account_id: "owner.near".to_string(),
+                memo: None,
+            }]),
+        };
+        assert_eq!(expected, log.to_string());
+    }
+
+    #[test]
+    fn extension_format_operator_approve() {
+        let expected = r#"EVENT_JSON:{"standard":"nft_extension","version":"1.0.0","event":"operator_approve","data":[{"owner_id":"owner.near","operator_id":"market.near"}]}"#;
+        let log = EventLog {
+            standard: "nft_extension".to_string(),
+            version: "1.0.0".to_string(),
+            event: EventLogVariant::OperatorApprove(vec![OperatorLog {
+                owner_id: "owner.near".to_string(),
+                operator_id: "market.near".to_string(),
+                memo: None,
+            }]),
+        };
+        assert_eq!(expected, log.to_string());
+    }
 }
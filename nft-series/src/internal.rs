@@ -117,6 +117,50 @@ impl Contract {
         )
     }
 
+    /// Ensure that a royalty map can be paid out: at most 6 accounts, no zero amounts and a total within the
+    /// royalty cap. Otherwise `10000 - total_perpetual` in the payout could underflow and every sale would fail.
+    pub(crate) fn assert_valid_royalty(&self, royalty: &HashMap<AccountId, u32>) {
+        //make sure that the length of the royalties is below 7 since we won't have enough GAS to pay out that many people
+        require!(royalty.len() < 7, "Cannot add more than 6 royalty amounts");
+
+        let mut total: u64 = 0;
+        for (account_id, amount) in royalty {
+            require!(
+                *amount > 0,
+                format!("Royalty for {} must be greater than zero", account_id)
+            );
+            total += u64::from(*amount);
+        }
+        require!(
+            total <= u64::from(self.royalty_cap),
+            format!("Total royalty of {} exceeds the cap of {}", total, self.royalty_cap)
+        );
+    }
+
+    /// Ensure that the token isn't part of a soulbound series, since soulbound tokens can't be transferred or approved
+    pub(crate) fn assert_not_soulbound(&self, token: &Token) {
+        let series = self.series_by_id.get(&token.series_id).expect("Not a series");
+        require!(!series.soulbound, "Token is soulbound and cannot be transferred");
+    }
+
+    /// Ensure that the token is valid right now: its series has started and hasn't expired
+    pub(crate) fn assert_token_valid(&self, token_id: &TokenId, token: &Token) {
+        let series = self.series_by_id.get(&token.series_id).expect("Not a series");
+        let now = env::block_timestamp_ms();
+        require!(
+            series.metadata.has_started(now),
+            format!(
+                "Token {} is not valid until {}",
+                token_id,
+                series.metadata.starts_at.map_or(0, |starts_at| starts_at.0)
+            )
+        );
+        require!(
+            !series.metadata.is_expired(now),
+            format!("Token {} has expired", token_id)
+        );
+    }
+
     //add a token to the set of tokens an owner has
     pub(crate) fn internal_add_token_to_owner(
         &mut self,
@@ -168,19 +212,14 @@ impl Contract {
         }
     }
 
-    //transfers the NFT to the receiver_id (internal method and can't be called directly via CLI).
-    pub(crate) fn internal_transfer(
-        &mut self,
+    //make sure that the sender is allowed to act on the token: either the owner or an approved account
+    pub(crate) fn internal_assert_authorized(
+        &self,
         sender_id: &AccountId,
-        receiver_id: &AccountId,
-        token_id: &TokenId,
-        //we introduce an approval ID so that people with that approval ID can transfer the token
+        token: &Token,
+        //we introduce an approval ID so that people with that approval ID can act on the token
         approval_id: Option<u64>,
-        memo: Option<String>,
-    ) -> Token {
-        //get the token object by passing in the token_id
-        let token = self.tokens_by_id.get(token_id).expect("No token");
-
+    ) {
         //if the sender doesn't equal the owner, we check if the sender is in the approval list
         if sender_id != &token.owner_id {
             //if the token's approved account IDs doesn't contain the sender, we panic
@@ -205,28 +244,20 @@ impl Contract {
                 );
             }
         }
+    }
 
-        //we make sure that the sender isn't sending the token to themselves
-        assert_ne!(
-            &token.owner_id, receiver_id,
-            "The token owner and the receiver should be different"
-        );
-
-        //we remove the token from it's current owner's set
-        self.internal_remove_token_from_owner(&token.owner_id, token_id);
-        //we then add the token to the receiver_id's set
-        self.internal_add_token_to_owner(receiver_id, token_id);
-
-        //we create a new token struct
-        let new_token = Token {
-            series_id: token.series_id,
-            owner_id: receiver_id.clone(),
-            //reset the approval account IDs
-            approved_account_ids: Default::default(),
-            next_approval_id: token.next_approval_id,
-        };
-        //insert that new token into the tokens_by_id, replacing the old entry
-        self.tokens_by_id.insert(token_id, &new_token);
+    //transfers the NFT to the receiver_id (internal method and can't be called directly via CLI).
+    pub(crate) fn internal_transfer(
+        &mut self,
+        sender_id: &AccountId,
+        receiver_id: &AccountId,
+        token_id: &TokenId,
+        //we introduce an approval ID so that people with that approval ID can transfer the token
+        approval_id: Option<u64>,
+        memo: Option<String>,
+    ) -> Token {
+        //move the token to the receiver and get the previous token object
+        let token = self.internal_move_token(sender_id, receiver_id, token_id, approval_id);
 
         //if there was some memo attached, we log it.
         if let Some(memo) = memo.as_ref() {
@@ -235,8 +266,8 @@ impl Contract {
 
         // Default the authorized ID to be None for the logs.
         let mut authorized_id = None;
-        //if the approval ID was provided, set the authorized ID equal to the sender
-        if approval_id.is_some() {
+        //if the approval ID was provided or an operator transferred the token, set the authorized ID equal to the sender
+        if approval_id.is_some() || sender_id != &token.owner_id {
             authorized_id = Some(sender_id.to_string());
         }
 
@@ -267,4 +298,56 @@ impl Contract {
         //return the previous token object that was transferred.
         token
     }
+
+    //checks that the token can be transferred by the sender and moves it to the receiver without logging anything.
+    //returns the previous token object.
+    pub(crate) fn internal_move_token(
+        &mut self,
+        sender_id: &AccountId,
+        receiver_id: &AccountId,
+        token_id: &TokenId,
+        approval_id: Option<u64>,
+    ) -> Token {
+        //get the token object by passing in the token_id
+        let token = self.tokens_by_id.get(token_id).expect("No token");
+
+        //make sure the token can be transferred at all
+        self.assert_not_paused(PauseFeature::Transfer);
+        self.assert_not_soulbound(&token);
+        self.assert_token_valid(token_id, &token);
+
+        //make sure the sender is the owner, an approved account for the token or an operator of the owner.
+        //operators can only transfer, so they're checked here instead of in internal_assert_authorized, which burning uses as well.
+        //an operator doesn't act through a per-token approval, so the approval ID it passes (nft_transfer_payout always passes one) is ignored
+        let is_operator_transfer = sender_id != &token.owner_id
+            && !token.approved_account_ids.contains_key(sender_id)
+            && self.is_operator(&token.owner_id, sender_id);
+        if !is_operator_transfer {
+            self.internal_assert_authorized(sender_id, &token, approval_id);
+        }
+
+        //we make sure that the sender isn't sending the token to themselves
+        assert_ne!(
+            &token.owner_id, receiver_id,
+            "The token owner and the receiver should be different"
+        );
+
+        //we remove the token from it's current owner's set
+        self.internal_remove_token_from_owner(&token.owner_id, token_id);
+        //we then add the token to the receiver_id's set
+        self.internal_add_token_to_owner(receiver_id, token_id);
+
+        //we create a new token struct
+        let new_token = Token {
+            series_id: token.series_id,
+            owner_id: receiver_id.clone(),
+            //reset the approval account IDs
+            approved_account_ids: Default::default(),
+            next_approval_id: token.next_approval_id,
+        };
+        //insert that new token into the tokens_by_id, replacing the old entry
+        self.tokens_by_id.insert(token_id, &new_token);
+
+        token
+    }
 }
@@ -1,6 +1,6 @@
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
 use near_sdk::collections::{LazyOption, LookupMap, LookupSet, UnorderedMap, UnorderedSet};
-use near_sdk::json_types::{Base64VecU8, U128};
+use near_sdk::json_types::{Base64VecU8, U128, U64};
 use near_sdk::serde::{Deserialize, Serialize};
 use near_sdk::{
     env, near_bindgen, require, AccountId, Balance, BorshStorageKey, CryptoHash, PanicOnDefault,
@@ -11,26 +11,44 @@ use std::collections::HashMap;
 pub use crate::approval::*;
 pub use crate::events::*;
 use crate::internal::*;
+use crate::migrate::*;
 pub use crate::metadata::*;
 pub use crate::nft_core::*;
 pub use crate::owner::*;
+pub use crate::pause::*;
 pub use crate::royalty::*;
 pub use crate::series::*;
 
 mod approval;
+mod burn;
 mod enumeration;
 mod events;
 mod internal;
 mod metadata;
+mod migrate;
 mod nft_core;
+mod operator;
 mod owner;
+mod pause;
 mod royalty;
 mod series;
+mod source_metadata;
+
+#[cfg(test)]
+mod tests;
 
 /// This spec can be treated like a version of the standard.
 pub const NFT_METADATA_SPEC: &str = "1.0.0";
 /// This is the name of the NFT standard we're using
 pub const NFT_STANDARD_NAME: &str = "nep171";
+/// Version of the NFT standard that introduced the contract_metadata_update event
+pub const NFT_CONTRACT_METADATA_UPDATE_SPEC: &str = "1.1.0";
+/// Standard name used for this contract's own events that aren't part of the NFT standard
+pub const NFT_EXTENSION_STANDARD_NAME: &str = "nft_extension";
+/// Version of this contract's own events
+pub const NFT_EXTENSION_SPEC: &str = "1.0.0";
+/// Default cap on the total royalty of a series, in basis points (10000 = 100%, so 5000 = 50%)
+pub const DEFAULT_ROYALTY_CAP: u32 = 5000;
 
 // Represents the series type. All tokens will derive this data.
 #[derive(BorshDeserialize, BorshSerialize)]
@@ -41,6 +59,11 @@ pub struct Series {
     royalty: Option<HashMap<AccountId, u32>>,
     // Set of tokens in the collection
     tokens: UnorderedSet<TokenId>,
+    // How many tokens have ever been minted in the collection, including burned ones.
+    // Edition numbers are derived from this so a burned edition is never reused.
+    editions_minted: u64,
+    // Whether the tokens in the collection are soulbound (can't be transferred or approved)
+    soulbound: bool,
     // What is the price of each token in this series? If this is specified, when minting,
     // Users will need to attach enough $NEAR to cover the price.
     price: Option<Balance>,
@@ -73,6 +96,24 @@ pub struct Contract {
 
     //keeps track of the metadata for the contract
     pub metadata: LazyOption<NFTContractMetadata>,
+
+    //account the ownership was proposed to. It becomes the owner once it calls accept_ownership
+    pub pending_owner_id: Option<AccountId>,
+
+    //cap on the total royalty of a series, in basis points (10000 = 100%)
+    pub royalty_cap: u32,
+
+    //features that are currently paused
+    pub paused_features: LookupSet<PauseFeature>,
+
+    //accounts that can pause and unpause features besides the contract owner
+    pub pausers: LookupSet<AccountId>,
+
+    //(owner, operator) pairs where the operator can transfer every token of the owner
+    pub operator_approvals: LookupSet<(AccountId, AccountId)>,
+
+    //when each token was minted, as a Unix epoch in milliseconds. Tokens minted before this was tracked have no entry
+    pub issued_at_by_token_id: LookupMap<TokenId, u64>,
 }
 
 /// Helper structure for keys of the persistent collections.
@@ -86,6 +127,10 @@ pub enum StorageKey {
     TokenPerOwnerInner { account_id_hash: CryptoHash },
     TokensById,
     NFTContractMetadata,
+    PausedFeatures,
+    Pausers,
+    OperatorApprovals,
+    IssuedAtByTokenId,
 }
 
 #[near_bindgen]
@@ -143,8 +188,21 @@ impl Contract {
                 StorageKey::NFTContractMetadata.try_to_vec().unwrap(),
                 Some(&metadata),
             ),
+            //no ownership transfer is pending
+            pending_owner_id: None,
+            //the total royalty of a series can't exceed 50% by default
+            royalty_cap: DEFAULT_ROYALTY_CAP,
+            paused_features: LookupSet::new(StorageKey::PausedFeatures.try_to_vec().unwrap()),
+            pausers: LookupSet::new(StorageKey::Pausers.try_to_vec().unwrap()),
+            operator_approvals: LookupSet::new(StorageKey::OperatorApprovals.try_to_vec().unwrap()),
+            issued_at_by_token_id: LookupMap::new(
+                StorageKey::IssuedAtByTokenId.try_to_vec().unwrap(),
+            ),
         };
 
+        //record that the stored state uses the current layout
+        write_state_version();
+
         //return the Contract object
         this
     }
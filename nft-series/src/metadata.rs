@@ -1,4 +1,5 @@
 use crate::*;
+use near_sdk::serde::Deserializer;
 pub type TokenId = String;
 //defines the payout type we'll be returning as a part of the royalty standards.
 #[derive(Serialize, Deserialize)]
@@ -19,6 +20,34 @@ pub struct NFTContractMetadata {
     pub reference_hash: Option<Base64VecU8>, // Base64-encoded sha256 hash of JSON from reference field. Required if `reference` is included.
 }
 
+//partial update of the contract metadata. Fields that aren't passed in are left unchanged and optional fields
+//passed in as null are cleared.
+#[derive(Serialize, Deserialize, Default)]
+#[serde(crate = "near_sdk::serde")]
+pub struct NFTContractMetadataUpdate {
+    pub spec: Option<String>,
+    pub name: Option<String>,
+    pub symbol: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_some", skip_serializing_if = "Option::is_none")]
+    pub icon: Option<Option<String>>,
+    #[serde(default, deserialize_with = "deserialize_some", skip_serializing_if = "Option::is_none")]
+    pub base_uri: Option<Option<String>>,
+    #[serde(default, deserialize_with = "deserialize_some", skip_serializing_if = "Option::is_none")]
+    pub reference: Option<Option<String>>, // If `reference` is updated or cleared, `reference_hash` must be passed in as well.
+    #[serde(default, deserialize_with = "deserialize_some", skip_serializing_if = "Option::is_none")]
+    pub reference_hash: Option<Option<Base64VecU8>>,
+}
+
+/// Read a field that was passed in as Some, even if it's null, so that a field that wasn't passed in (None)
+/// can be told apart from one that should be cleared (Some(None))
+fn deserialize_some<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    T::deserialize(deserializer).map(Some)
+}
+
 #[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
 #[serde(crate = "near_sdk::serde")]
 pub struct TokenMetadata {
@@ -27,10 +56,10 @@ pub struct TokenMetadata {
     pub media: Option<String>, // URL to associated media, preferably to decentralized, content-addressed storage
     pub media_hash: Option<Base64VecU8>, // Base64-encoded sha256 hash of content referenced by the `media` field. Required if `media` is included.
     pub copies: Option<u64>, // number of copies of this set of metadata in existence when token was minted.
-    pub issued_at: Option<u64>, // When token was issued or minted, Unix epoch in milliseconds
-    pub expires_at: Option<u64>, // When token expires, Unix epoch in milliseconds
-    pub starts_at: Option<u64>, // When token starts being valid, Unix epoch in milliseconds
-    pub updated_at: Option<u64>, // When token was last updated, Unix epoch in milliseconds
+    pub issued_at: Option<U64>, // When token was issued or minted, Unix epoch in milliseconds
+    pub expires_at: Option<U64>, // When token expires, Unix epoch in milliseconds
+    pub starts_at: Option<U64>, // When token starts being valid, Unix epoch in milliseconds
+    pub updated_at: Option<U64>, // When token was last updated, Unix epoch in milliseconds
     pub extra: Option<String>, // anything extra the NFT wants to store on-chain. Can be stringified JSON.
     pub reference: Option<String>, // URL to an off-chain JSON file with more info.
     pub reference_hash: Option<Base64VecU8>, // Base64-encoded sha256 hash of JSON from reference field. Required if `reference` is included.
@@ -64,6 +93,22 @@ pub struct JsonToken {
     pub approved_account_ids: HashMap<AccountId, u64>,
     //keep track of the royalty percentages for the token in a hash map
     pub royalty: Option<HashMap<AccountId, u32>>,
+    //whether the token is soulbound (can't be transferred or approved)
+    pub soulbound: bool,
+    //whether the token has expired (expires_at has passed). Expired tokens can't be transferred
+    pub expired: bool,
+}
+
+impl TokenMetadata {
+    /// Whether the token has expired at the passed in time (Unix epoch milliseconds)
+    pub(crate) fn is_expired(&self, now: u64) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at.0 <= now)
+    }
+
+    /// Whether the token has started being valid at the passed in time (Unix epoch milliseconds)
+    pub(crate) fn has_started(&self, now: u64) -> bool {
+        self.starts_at.is_none_or(|starts_at| starts_at.0 <= now)
+    }
 }
 
 pub trait NonFungibleTokenMetadata {
@@ -77,3 +122,105 @@ impl NonFungibleTokenMetadata for Contract {
         self.metadata.get().unwrap()
     }
 }
+
+#[near_bindgen]
+impl Contract {
+    /// Update the contract metadata. Only the contract owner can call this and only the fields
+    /// that are passed in will be changed, optional fields passed in as null are cleared. Only the changed
+    /// fields are validated. Caller must attach enough $NEAR to cover any extra storage.
+    #[payable]
+    pub fn nft_update_contract_metadata(
+        &mut self,
+        metadata: NFTContractMetadataUpdate,
+        memo: Option<String>,
+    ) {
+        // Assert at least one yocto for security reasons and ensure the caller is the contract owner
+        assert_at_least_one_yocto();
+        self.assert_contract_owner();
+
+        // Measure the initial storage being used on the contract
+        let initial_storage_usage = env::storage_usage();
+
+        // The old hash is no longer valid once the reference changes, so a new one is required
+        require!(
+            metadata.reference.is_none() || metadata.reference_hash.is_some(),
+            "reference_hash must be provided when updating reference"
+        );
+        let reference_updated = metadata.reference.is_some() || metadata.reference_hash.is_some();
+
+        // Merge the passed in fields into the current metadata
+        let mut contract_metadata = self.metadata.get().unwrap();
+        if let Some(spec) = metadata.spec {
+            contract_metadata.spec = spec;
+        }
+        if let Some(name) = metadata.name {
+            contract_metadata.name = name;
+        }
+        if let Some(symbol) = metadata.symbol {
+            contract_metadata.symbol = symbol;
+        }
+        if let Some(icon) = metadata.icon {
+            contract_metadata.icon = icon;
+        }
+        if let Some(base_uri) = metadata.base_uri {
+            contract_metadata.base_uri = base_uri;
+        }
+        if let Some(reference) = metadata.reference {
+            contract_metadata.reference = reference;
+        }
+        if let Some(reference_hash) = metadata.reference_hash {
+            contract_metadata.reference_hash = reference_hash;
+        }
+
+        // Ensure the merged `reference` and `reference_hash` are consistent if either of them changed
+        if reference_updated {
+            contract_metadata.assert_valid();
+        }
+
+        // Store the updated metadata
+        self.metadata.set(&contract_metadata);
+
+        // Construct the contract metadata update log as per the events standard (NEP-171 v1.1.0).
+        let contract_metadata_update_log: EventLog = EventLog {
+            // Standard name ("nep171").
+            standard: NFT_STANDARD_NAME.to_string(),
+            // Version of the standard that introduced contract_metadata_update ("1.1.0").
+            version: NFT_CONTRACT_METADATA_UPDATE_SPEC.to_string(),
+            // The data related with the event stored in a vector.
+            event: EventLogVariant::ContractMetadataUpdate(vec![ContractMetadataUpdateLog {
+                // An optional memo to include.
+                memo,
+            }]),
+        };
+
+        // Log the serialized json.
+        env::log_str(&contract_metadata_update_log.to_string());
+
+        // Charge for any extra storage if the metadata grew and refund the rest of the deposit
+        let required_storage_in_bytes = env::storage_usage().saturating_sub(initial_storage_usage);
+        refund_deposit(required_storage_in_bytes);
+    }
+
+    /// Check if a token is valid right now: its series has started (starts_at) and hasn't expired (expires_at).
+    /// Returns false if the token doesn't exist.
+    pub fn nft_is_valid(&self, token_id: TokenId) -> bool {
+        let now = env::block_timestamp_ms();
+        self.tokens_by_id
+            .get(&token_id)
+            .and_then(|token| self.series_by_id.get(&token.series_id))
+            .is_some_and(|series| series.metadata.has_started(now) && !series.metadata.is_expired(now))
+    }
+}
+
+impl NFTContractMetadata {
+    // `reference` and `reference_hash` must be set together and the hash must be a 32 byte sha256
+    pub(crate) fn assert_valid(&self) {
+        require!(
+            self.reference.is_some() == self.reference_hash.is_some(),
+            "reference and reference_hash must be set together"
+        );
+        if let Some(reference_hash) = &self.reference_hash {
+            require!(reference_hash.0.len() == 32, "reference_hash must be 32 bytes");
+        }
+    }
+}
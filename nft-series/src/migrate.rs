@@ -0,0 +1,298 @@
+use crate::*;
+use near_sdk::{Gas, GasWeight};
+
+/// The key the state version is stored under. Deployments made before state versioning don't have it and are V1.
+const STATE_VERSION_KEY: &[u8] = b"STATE_VERSION";
+/// Version of the current state layout. Bump it and add a conversion to `VersionedContract` whenever a release
+/// changes the fields of `Contract` or `Series`.
+pub const CURRENT_STATE_VERSION: u8 = 3;
+/// The key the index of the next series to migrate is stored under while a migration is in progress
+const MIGRATION_CURSOR_KEY: &[u8] = b"MIGRATION_CURSOR";
+/// Maximum number of series converted by a single `migrate` call
+pub const MAX_MIGRATION_BATCH_SIZE: u64 = 100;
+
+/// Minimum GAS attached to the `migrate` call. Any unused GAS is forwarded to it as well.
+const GAS_FOR_MIGRATE: Gas = Gas(10_000_000_000_000);
+
+/// Series layout of the first deployments (before burned editions were tracked)
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct SeriesV1 {
+    pub metadata: TokenMetadata,
+    pub royalty: Option<HashMap<AccountId, u32>>,
+    pub tokens: UnorderedSet<TokenId>,
+    pub price: Option<Balance>,
+    pub owner_id: AccountId,
+}
+
+/// State layout of the first deployments
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct ContractV1 {
+    pub owner_id: AccountId,
+    pub approved_minters: LookupSet<AccountId>,
+    pub approved_creators: LookupSet<AccountId>,
+    pub series_by_id: UnorderedMap<SeriesId, SeriesV1>,
+    pub tokens_by_id: UnorderedMap<TokenId, Token>,
+    pub tokens_per_owner: LookupMap<AccountId, UnorderedSet<TokenId>>,
+    pub metadata: LazyOption<NFTContractMetadata>,
+}
+
+/// Series layout that tracked burned editions, before soulbound series
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct SeriesV2 {
+    pub metadata: TokenMetadata,
+    pub royalty: Option<HashMap<AccountId, u32>>,
+    pub tokens: UnorderedSet<TokenId>,
+    pub editions_minted: u64,
+    pub price: Option<Balance>,
+    pub owner_id: AccountId,
+}
+
+impl From<SeriesV1> for SeriesV2 {
+    fn from(series: SeriesV1) -> Self {
+        SeriesV2 {
+            metadata: series.metadata,
+            royalty: series.royalty,
+            // Nothing could be burned before, so every minted edition still exists
+            editions_minted: series.tokens.len(),
+            tokens: series.tokens,
+            price: series.price,
+            owner_id: series.owner_id,
+        }
+    }
+}
+
+impl From<SeriesV2> for Series {
+    fn from(series: SeriesV2) -> Self {
+        Series {
+            metadata: series.metadata,
+            royalty: series.royalty,
+            tokens: series.tokens,
+            editions_minted: series.editions_minted,
+            soulbound: false,
+            price: series.price,
+            owner_id: series.owner_id,
+        }
+    }
+}
+
+/// State layout that introduced state versioning and the two-step ownership transfer
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct ContractV2 {
+    pub owner_id: AccountId,
+    pub approved_minters: LookupSet<AccountId>,
+    pub approved_creators: LookupSet<AccountId>,
+    pub series_by_id: UnorderedMap<SeriesId, SeriesV2>,
+    pub tokens_by_id: UnorderedMap<TokenId, Token>,
+    pub tokens_per_owner: LookupMap<AccountId, UnorderedSet<TokenId>>,
+    pub metadata: LazyOption<NFTContractMetadata>,
+    pub pending_owner_id: Option<AccountId>,
+}
+
+impl From<ContractV1> for ContractV2 {
+    fn from(state: ContractV1) -> Self {
+        ContractV2 {
+            owner_id: state.owner_id,
+            approved_minters: state.approved_minters,
+            approved_creators: state.approved_creators,
+            series_by_id: retype(state.series_by_id),
+            tokens_by_id: state.tokens_by_id,
+            tokens_per_owner: state.tokens_per_owner,
+            metadata: state.metadata,
+            pending_owner_id: None,
+        }
+    }
+}
+
+impl From<ContractV2> for Contract {
+    /// Every series must already have been rewritten with the current layout (see `migrate`)
+    fn from(state: ContractV2) -> Self {
+        Contract {
+            owner_id: state.owner_id,
+            approved_minters: state.approved_minters,
+            approved_creators: state.approved_creators,
+            series_by_id: retype(state.series_by_id),
+            tokens_by_id: state.tokens_by_id,
+            tokens_per_owner: state.tokens_per_owner,
+            metadata: state.metadata,
+            pending_owner_id: state.pending_owner_id,
+            royalty_cap: DEFAULT_ROYALTY_CAP,
+            paused_features: LookupSet::new(StorageKey::PausedFeatures.try_to_vec().unwrap()),
+            pausers: LookupSet::new(StorageKey::Pausers.try_to_vec().unwrap()),
+            operator_approvals: LookupSet::new(StorageKey::OperatorApprovals.try_to_vec().unwrap()),
+            issued_at_by_token_id: LookupMap::new(
+                StorageKey::IssuedAtByTokenId.try_to_vec().unwrap(),
+            ),
+        }
+    }
+}
+
+/// The map handle only stores its prefix and length, so it can be reinterpreted once its values were rewritten with a new type
+fn retype<V, W>(map: UnorderedMap<SeriesId, V>) -> UnorderedMap<SeriesId, W>
+where
+    V: BorshSerialize + BorshDeserialize,
+    W: BorshSerialize + BorshDeserialize,
+{
+    UnorderedMap::try_from_slice(&map.try_to_vec().unwrap()).unwrap()
+}
+
+/// Rewrite at most `limit` series from the passed in index in place with the current layout, converting each one
+/// with `convert`. Returns the index to continue from.
+fn migrate_series<V>(
+    series_by_id: &mut UnorderedMap<SeriesId, V>,
+    from_index: u64,
+    limit: u64,
+    convert: fn(V) -> Series,
+) -> u64
+where
+    V: BorshSerialize + BorshDeserialize,
+{
+    let end = (from_index + limit).min(series_by_id.len());
+    let page: Vec<(SeriesId, V)> = (from_index..end)
+        .map(|index| {
+            (
+                series_by_id.keys_as_vector().get(index).unwrap(),
+                series_by_id.values_as_vector().get(index).unwrap(),
+            )
+        })
+        .collect();
+    for (series_id, series) in page {
+        series_by_id.insert_raw(
+            &series_id.try_to_vec().unwrap(),
+            &convert(series).try_to_vec().unwrap(),
+        );
+    }
+    end
+}
+
+/// Every state layout this contract has been deployed with. The last variant is the current layout.
+pub enum VersionedContract {
+    V1(ContractV1),
+    V2(ContractV2),
+    V3(Contract),
+}
+
+impl VersionedContract {
+    /// Read the stored state using the layout of its stored version
+    pub(crate) fn read() -> Self {
+        let version = env::storage_read(STATE_VERSION_KEY)
+            .map(|version| version[0])
+            .unwrap_or(1);
+        match version {
+            1 => Self::V1(env::state_read().expect("Failed to read old state")),
+            2 => Self::V2(env::state_read().expect("Failed to read old state")),
+            3 => Self::V3(env::state_read().expect("Failed to read old state")),
+            _ => env::panic_str("Unknown state version"),
+        }
+    }
+
+    /// The contract owner stored in the state
+    pub(crate) fn owner_id(&self) -> &AccountId {
+        match self {
+            Self::V1(state) => &state.owner_id,
+            Self::V2(state) => &state.owner_id,
+            Self::V3(state) => &state.owner_id,
+        }
+    }
+
+    /// Convert at most `limit` stored series from the passed in index into the current layout and return the index
+    /// to continue from. Only the top-level state is left to convert once it returns the number of series.
+    fn migrate_entries(&mut self, from_index: u64, limit: u64) -> u64 {
+        match self {
+            Self::V1(state) => migrate_series(&mut state.series_by_id, from_index, limit, |series| {
+                SeriesV2::from(series).into()
+            }),
+            Self::V2(state) => migrate_series(&mut state.series_by_id, from_index, limit, Series::from),
+            Self::V3(_) => 0,
+        }
+    }
+
+    /// The number of stored series that have to be converted
+    fn entries_len(&self) -> u64 {
+        match self {
+            Self::V1(state) => state.series_by_id.len(),
+            Self::V2(state) => state.series_by_id.len(),
+            Self::V3(_) => 0,
+        }
+    }
+
+    /// Convert the top-level state into the current layout, one version at a time. Every series must have been converted.
+    fn into_current(self) -> Contract {
+        match self {
+            Self::V1(state) => Self::V2(state.into()).into_current(),
+            Self::V2(state) => state.into(),
+            Self::V3(state) => state,
+        }
+    }
+}
+
+/// Record that the stored state uses the current layout
+pub(crate) fn write_state_version() {
+    env::storage_write(STATE_VERSION_KEY, &[CURRENT_STATE_VERSION]);
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Migrate the stored state to the current layout, converting at most `limit` series per call so that any number
+    /// of series fits in the GAS limit. With a limit, call it again until it returns true. Without one, each call
+    /// converts a full batch and then calls `migrate` on this contract for the next one, so a single call (like the one
+    /// `update_contract` makes) converts every series. If that chain runs out of GAS, calling `migrate` again resumes it.
+    /// The progress is stored under its own key and the state keeps its old layout until every series was converted,
+    /// so every other method fails until then. Migrating a state that already has the current layout changes nothing.
+    /// Can only be called by the contract owner or by the contract itself (see `update_contract`).
+    pub fn migrate(limit: Option<u64>) -> PromiseOrValue<bool> {
+        //read the old state. If it can't be read, panic
+        let mut state = VersionedContract::read();
+
+        let predecessor_id = env::predecessor_account_id();
+        require!(
+            &predecessor_id == state.owner_id() || predecessor_id == env::current_account_id(),
+            "only contract owner"
+        );
+
+        // Convert the next batch of series, continuing where the previous call stopped
+        let from_index = env::storage_read(MIGRATION_CURSOR_KEY)
+            .map(|cursor| u64::try_from_slice(&cursor).unwrap())
+            .unwrap_or(0);
+        let batch_size = limit.unwrap_or(MAX_MIGRATION_BATCH_SIZE).min(MAX_MIGRATION_BATCH_SIZE);
+        let next_index = state.migrate_entries(from_index, batch_size);
+        if next_index < state.entries_len() {
+            env::storage_write(MIGRATION_CURSOR_KEY, &next_index.try_to_vec().unwrap());
+            env::log_str(&format!("Migrated {} of {} series", next_index, state.entries_len()));
+            // Without a limit the next batch is converted by another call, so the whole migration finishes in one go
+            if limit.is_none() {
+                return PromiseOrValue::Promise(call_migrate(Promise::new(env::current_account_id())));
+            }
+            return PromiseOrValue::Value(false);
+        }
+
+        // Every series is converted, so the top-level state can be switched to the current layout
+        env::storage_remove(MIGRATION_CURSOR_KEY);
+        env::state_write(&state.into_current());
+        write_state_version();
+        PromiseOrValue::Value(true)
+    }
+
+    /// Deploy the wasm passed as the raw input of this call to the contract account and run `migrate` without a limit
+    /// in the same batch, which keeps calling itself until every series is converted. If the first call fails the
+    /// whole batch, including the deployment, is reverted. Attach enough GAS for every batch, or call `migrate` again
+    /// if it runs out.
+    pub fn update_contract(&mut self) -> Promise {
+        self.assert_contract_owner();
+
+        // The new code is the raw input of the call
+        let code = env::input().expect("No code was attached");
+
+        call_migrate(Promise::new(env::current_account_id()).deploy_contract(code)).as_return()
+    }
+}
+
+/// Add a `migrate` call without a limit to the passed in promise, forwarding all the unused GAS to it
+fn call_migrate(promise: Promise) -> Promise {
+    promise.function_call_weight(
+        "migrate".to_string(),
+        b"{}".to_vec(),
+        0,
+        GAS_FOR_MIGRATE,
+        GasWeight(1),
+    )
+}
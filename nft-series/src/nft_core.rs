@@ -1,8 +1,11 @@
 use crate::*;
+use std::collections::HashSet;
 use near_sdk::{ext_contract, Gas, PromiseResult};
 
 const GAS_FOR_RESOLVE_TRANSFER: Gas = Gas(10_000_000_000_000);
 const GAS_FOR_NFT_ON_TRANSFER: Gas = Gas(25_000_000_000_000);
+/// Maximum number of tokens that can be transferred in one call, to stay within the gas limit.
+pub const MAX_BATCH_TRANSFER_SIZE: usize = 100;
 
 pub trait NonFungibleTokenCore {
     //transfers an NFT to a receiver ID
@@ -164,6 +167,12 @@ impl NonFungibleTokenCore for Contract {
                 .expect("Not a series");
             // Get the metadata for the series
             let mut metadata = cur_series.metadata;
+            // Tokens expire along with their series
+            let expired = metadata.is_expired(env::block_timestamp_ms());
+            // Tokens share the metadata of their series but each one was issued when it was minted
+            if let Some(issued_at) = self.issued_at_by_token_id.get(&token_id) {
+                metadata.issued_at = Some(U64(issued_at));
+            }
 
             // Get the edition number and series ID
             let split: Vec<&str> = token_id.split(":").collect();
@@ -184,6 +193,8 @@ impl NonFungibleTokenCore for Contract {
                 metadata,
                 approved_account_ids: token.approved_account_ids,
                 royalty: cur_series.royalty,
+                soulbound: cur_series.soulbound,
+                expired,
             })
         } else {
             //if there wasn't a token ID in the tokens_by_id collection, we return None
@@ -291,3 +302,82 @@ impl NonFungibleTokenResolver for Contract {
         false
     }
 }
+
+#[near_bindgen]
+impl Contract {
+    /// Transfer several NFTs of the same owner to a receiver ID. If any transfer fails, the whole call is reverted.
+    #[payable]
+    pub fn nft_batch_transfer(
+        &mut self,
+        receiver_id: AccountId,
+        token_ids: Vec<TokenId>,
+        memo: Option<String>,
+    ) {
+        //assert that the user attached exactly 1 yoctoNEAR for security reasons
+        assert_one_yocto();
+
+        require!(!token_ids.is_empty(), "Must transfer at least one token");
+        require!(
+            token_ids.len() <= MAX_BATCH_TRANSFER_SIZE,
+            format!("Cannot transfer more than {} tokens at once", MAX_BATCH_TRANSFER_SIZE)
+        );
+        let mut seen_token_ids = HashSet::new();
+        for token_id in token_ids.iter() {
+            require!(
+                seen_token_ids.insert(token_id),
+                format!("Duplicate token ID {} in batch", token_id)
+            );
+        }
+
+        //get the sender ID
+        let sender_id = env::predecessor_account_id();
+
+        //move every token and keep track of the approvals that were reset along the way
+        let mut old_owner_id: Option<AccountId> = None;
+        let mut approved_account_ids: Vec<AccountId> = Vec::new();
+        for token_id in token_ids.iter() {
+            let previous_token = self.internal_move_token(&sender_id, &receiver_id, token_id, None);
+
+            //all tokens must come from the same owner so that they fit in one transfer log
+            let owner_id = old_owner_id.get_or_insert_with(|| previous_token.owner_id.clone());
+            require!(
+                owner_id == &previous_token.owner_id,
+                "All tokens must have the same owner"
+            );
+
+            approved_account_ids.extend(previous_token.approved_account_ids.into_keys());
+        }
+        let old_owner_id = old_owner_id.unwrap();
+
+        //refund the previous owner once for releasing the storage used up by the approved account IDs
+        refund_approved_account_ids_iter(old_owner_id.clone(), approved_account_ids.iter());
+
+        //if there was some memo attached, we log it.
+        if let Some(memo) = memo.as_ref() {
+            env::log_str(&format!("Memo: {}", memo));
+        }
+
+        //if the sender isn't the owner of the tokens, we set the authorized ID equal to the sender.
+        let authorized_id = if sender_id != old_owner_id {
+            Some(sender_id.to_string())
+        } else {
+            None
+        };
+
+        // Construct a single transfer log for every token as per the events standard.
+        let nft_transfer_log: EventLog = EventLog {
+            standard: NFT_STANDARD_NAME.to_string(),
+            version: NFT_METADATA_SPEC.to_string(),
+            event: EventLogVariant::NftTransfer(vec![NftTransferLog {
+                authorized_id,
+                old_owner_id: old_owner_id.to_string(),
+                new_owner_id: receiver_id.to_string(),
+                token_ids,
+                memo,
+            }]),
+        };
+
+        // Log the serialized json.
+        env::log_str(&nft_transfer_log.to_string());
+    }
+}
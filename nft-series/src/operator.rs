@@ -0,0 +1,69 @@
+use crate::owner::log_extension_event;
+use crate::*;
+
+#[near_bindgen]
+impl Contract {
+    /// Approve an account to transfer every token of the caller, including tokens received later.
+    /// The caller pays for the storage used by the approval.
+    #[payable]
+    pub fn nft_approve_operator(&mut self, account_id: AccountId) {
+        //assert at least one yocto for security reasons and to pay for storage
+        assert_at_least_one_yocto();
+
+        let owner_id = env::predecessor_account_id();
+        require!(owner_id != account_id, "Cannot approve yourself as an operator");
+
+        //measure the initial storage being used on the contract
+        let initial_storage_usage = env::storage_usage();
+
+        //only log when the operator wasn't approved yet
+        if self.operator_approvals.insert(&(owner_id.clone(), account_id.clone())) {
+            log_extension_event(EventLogVariant::OperatorApprove(vec![OperatorLog {
+                owner_id: owner_id.to_string(),
+                operator_id: account_id.to_string(),
+                memo: None,
+            }]));
+        }
+
+        //refund any excess storage attached by the user. If the user didn't attach enough, panic.
+        let storage_used = env::storage_usage() - initial_storage_usage;
+        refund_deposit(storage_used);
+    }
+
+    /// Revoke an operator of the caller. The freed storage is refunded to the caller.
+    #[payable]
+    pub fn nft_revoke_operator(&mut self, account_id: AccountId) {
+        //assert that the user attached exactly 1 yoctoNEAR for security reasons
+        assert_one_yocto();
+
+        let owner_id = env::predecessor_account_id();
+        let initial_storage_usage = env::storage_usage();
+
+        if self.operator_approvals.remove(&(owner_id.clone(), account_id.clone())) {
+            //refund the storage released by the approval to the owner, who paid for it
+            let freed_storage_in_bytes = initial_storage_usage.saturating_sub(env::storage_usage());
+            if freed_storage_in_bytes > 0 {
+                Promise::new(owner_id.clone())
+                    .transfer(Balance::from(freed_storage_in_bytes) * env::storage_byte_cost());
+            }
+
+            log_extension_event(EventLogVariant::OperatorRevoke(vec![OperatorLog {
+                owner_id: owner_id.to_string(),
+                operator_id: account_id.to_string(),
+                memo: None,
+            }]));
+        }
+    }
+
+    /// Check if an account is an operator of the passed in owner
+    pub fn nft_is_operator(&self, owner_id: AccountId, account_id: AccountId) -> bool {
+        self.is_operator(&owner_id, &account_id)
+    }
+}
+
+impl Contract {
+    //check if an account can transfer every token of the owner
+    pub(crate) fn is_operator(&self, owner_id: &AccountId, account_id: &AccountId) -> bool {
+        self.operator_approvals.contains(&(owner_id.clone(), account_id.clone()))
+    }
+}
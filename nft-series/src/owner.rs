@@ -35,4 +35,85 @@ impl Contract {
     pub fn is_approved_creator(&self, account_id: AccountId) -> bool {
         self.approved_creators.contains(&account_id)
     }
+
+    /// Set the cap on the total royalty of a series, in basis points (10000 = 100%).
+    /// Only applies to series created afterwards.
+    pub fn set_royalty_cap(&mut self, royalty_cap: u32) {
+        self.assert_contract_owner();
+        require!(royalty_cap <= 10000, "Royalty cap cannot exceed 10000");
+        self.royalty_cap = royalty_cap;
+    }
+
+    /// Propose a new contract owner. The transfer only happens once the proposed account calls `accept_ownership`.
+    /// A new proposal replaces any pending one.
+    #[payable]
+    pub fn propose_owner(&mut self, new_owner_id: AccountId) {
+        //assert that the user attached exactly 1 yoctoNEAR for security reasons
+        assert_one_yocto();
+        self.assert_contract_owner();
+        require!(new_owner_id != self.owner_id, "Account is already the contract owner");
+
+        self.pending_owner_id = Some(new_owner_id.clone());
+        log_extension_event(EventLogVariant::OwnershipTransferPropose(vec![OwnershipTransferLog {
+            old_owner_id: self.owner_id.to_string(),
+            new_owner_id: new_owner_id.to_string(),
+            memo: None,
+        }]));
+    }
+
+    /// Accept a pending ownership transfer. Must be called by the proposed owner.
+    #[payable]
+    pub fn accept_ownership(&mut self) {
+        //assert that the user attached exactly 1 yoctoNEAR for security reasons
+        assert_one_yocto();
+        let new_owner_id = self.pending_owner_id.take().expect("No pending ownership transfer");
+        require!(env::predecessor_account_id() == new_owner_id, "only pending owner");
+
+        let old_owner_id = std::mem::replace(&mut self.owner_id, new_owner_id.clone());
+        log_extension_event(EventLogVariant::OwnershipTransferAccept(vec![OwnershipTransferLog {
+            old_owner_id: old_owner_id.to_string(),
+            new_owner_id: new_owner_id.to_string(),
+            memo: None,
+        }]));
+    }
+
+    /// Cancel a pending ownership transfer
+    #[payable]
+    pub fn cancel_ownership_transfer(&mut self) {
+        //assert that the user attached exactly 1 yoctoNEAR for security reasons
+        assert_one_yocto();
+        self.assert_contract_owner();
+        let new_owner_id = self.pending_owner_id.take().expect("No pending ownership transfer");
+
+        log_extension_event(EventLogVariant::OwnershipTransferCancel(vec![OwnershipTransferLog {
+            old_owner_id: self.owner_id.to_string(),
+            new_owner_id: new_owner_id.to_string(),
+            memo: None,
+        }]));
+    }
+
+    /// Get the contract owner
+    pub fn get_owner(&self) -> AccountId {
+        self.owner_id.clone()
+    }
+
+    /// Get the account the ownership transfer was proposed to, if any
+    pub fn get_pending_owner(&self) -> Option<AccountId> {
+        self.pending_owner_id.clone()
+    }
+}
+
+/// Log one of this contract's own events, which aren't part of the NFT standard
+pub(crate) fn log_extension_event(event: EventLogVariant) {
+    let extension_log: EventLog = EventLog {
+        // Standard name ("nft_extension").
+        standard: NFT_EXTENSION_STANDARD_NAME.to_string(),
+        // Version of the standard ("1.0.0").
+        version: NFT_EXTENSION_SPEC.to_string(),
+        // The data related with the event stored in a vector.
+        event,
+    };
+
+    // Log the serialized json.
+    env::log_str(&extension_log.to_string());
 }
@@ -0,0 +1,83 @@
+use crate::owner::log_extension_event;
+use crate::*;
+
+/// Features that can be paused independently
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "snake_case")]
+pub enum PauseFeature {
+    // nft_mint
+    Mint,
+    // nft_transfer, nft_transfer_call, nft_transfer_payout
+    Transfer,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Pause a feature. Can be called by the contract owner or a pauser. View methods keep working while paused.
+    pub fn pause(&mut self, feature: PauseFeature) {
+        self.assert_owner_or_pauser();
+        //only log when the feature wasn't paused yet
+        if self.paused_features.insert(&feature) {
+            log_extension_event(EventLogVariant::Pause(vec![PauseLog {
+                feature,
+                account_id: env::predecessor_account_id().to_string(),
+                memo: None,
+            }]));
+        }
+    }
+
+    /// Unpause a feature. Can be called by the contract owner or a pauser.
+    pub fn unpause(&mut self, feature: PauseFeature) {
+        self.assert_owner_or_pauser();
+        //only log when the feature was paused
+        if self.paused_features.remove(&feature) {
+            log_extension_event(EventLogVariant::Unpause(vec![PauseLog {
+                feature,
+                account_id: env::predecessor_account_id().to_string(),
+                memo: None,
+            }]));
+        }
+    }
+
+    /// Check if a feature is paused
+    pub fn is_paused(&self, feature: PauseFeature) -> bool {
+        self.paused_features.contains(&feature)
+    }
+
+    /// Give a specified account the right to pause and unpause
+    pub fn add_pauser(&mut self, account_id: AccountId) {
+        self.assert_contract_owner();
+        self.pausers.insert(&account_id);
+    }
+
+    /// Take the right to pause and unpause away from a specified account
+    pub fn remove_pauser(&mut self, account_id: AccountId) {
+        self.assert_contract_owner();
+        self.pausers.remove(&account_id);
+    }
+
+    /// Check if a specified account can pause and unpause
+    pub fn is_pauser(&self, account_id: AccountId) -> bool {
+        self.pausers.contains(&account_id)
+    }
+}
+
+impl Contract {
+    /// Ensure that the feature isn't paused
+    pub(crate) fn assert_not_paused(&self, feature: PauseFeature) {
+        require!(
+            !self.paused_features.contains(&feature),
+            format!("{:?} is paused", feature)
+        );
+    }
+
+    /// Ensure that the caller is the owner of the contract or a pauser
+    fn assert_owner_or_pauser(&self) {
+        let predecessor_id = env::predecessor_account_id();
+        require!(
+            predecessor_id == self.owner_id || self.pausers.contains(&predecessor_id),
+            "only contract owner or pauser"
+        );
+    }
+}
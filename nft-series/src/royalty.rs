@@ -16,6 +16,14 @@ pub trait NonFungibleTokenCore {
     ) -> Payout;
 }
 
+#[near_bindgen]
+impl Contract {
+    /// Get the cap on the total royalty of a series, in basis points (10000 = 100%)
+    pub fn nft_royalty_cap(&self) -> u32 {
+        self.royalty_cap
+    }
+}
+
 #[near_bindgen]
 impl NonFungibleTokenCore for Contract {
     //calculates the payout for a token given the passed in balance. This is a view method
@@ -8,6 +8,8 @@ impl Contract {
     /// If copies are set in the metadata, it will enforce that only that number of NFTs can be minted. If not, unlimited NFTs can be minted.
     /// If a title is set in the metadata, enumeration methods will return the `${title} - ${edition}` else, `${series_id} - ${edition}`
     /// All token IDs internally are stored as `${series_id}:${edition}`
+    /// If `soulbound` is true, tokens in the series can't be transferred or approved. They can still be burned by their
+    /// owner or revoked by the series owner.
     /// Caller must attach enough $NEAR to cover storage.
     #[payable]
     pub fn create_series(
@@ -15,7 +17,8 @@ impl Contract {
         id: u64,
         metadata: TokenMetadata,
         royalty: Option<HashMap<AccountId, u32>>,
-        price: Option<U128>
+        price: Option<U128>,
+        soulbound: Option<bool>,
     ) {
         // Measure the initial storage being used on the contract
         let initial_storage_usage = env::storage_usage();
@@ -27,6 +30,11 @@ impl Contract {
             "only approved creators can add a type"
         );
 
+        // Ensure the royalty can be paid out on every sale
+        if let Some(royalty) = royalty.as_ref() {
+            self.assert_valid_royalty(royalty);
+        }
+
         // Insert the series and ensure it doesn't already exist
         require!(
             self.series_by_id
@@ -42,6 +50,8 @@ impl Contract {
                                 id, caller
                             )),
                         }),
+                        editions_minted: 0,
+                        soulbound: soulbound.unwrap_or(false),
                         owner_id: caller,
                         price: price.map(|p| p.into()),
                     }
@@ -61,10 +71,13 @@ impl Contract {
     /// The series ID must exist and if the metadata specifies a copy limit, you cannot exceed it.
     #[payable]
     pub fn nft_mint(&mut self, id: U64, receiver_id: AccountId) {
+        // Ensure minting isn't paused
+        self.assert_not_paused(PauseFeature::Mint);
+
         // Measure the initial storage being used on the contract
         let initial_storage_usage = env::storage_usage();
 
-        // Get the series and how many tokens currently exist (edition number = cur_len + 1)
+        // Get the series and how many tokens have been minted so far (edition number = editions_minted + 1)
         let mut series = self.series_by_id.get(&id.0).expect("Not a series");
         
         // Check if the series has a price per token. If it does, ensure the caller has attached at least that amount
@@ -82,18 +95,20 @@ impl Contract {
             );
         }
 
-        let cur_len = series.tokens.len();
+        // Burned tokens still count towards the copies limit since their editions can't be minted again
+        let editions_minted = series.editions_minted;
         // Ensure we haven't overflowed on the number of copies minted
         if let Some(copies) = series.metadata.copies {
             require!(
-                cur_len < copies,
+                editions_minted < copies,
                 "cannot mint anymore NFTs for the given series. Limit reached"
             );
         }
 
         // The token ID is stored internally as `${series_id}:${edition}`
-        let token_id = format!("{}:{}", id.0, cur_len + 1);
+        let token_id = format!("{}:{}", id.0, editions_minted + 1);
         series.tokens.insert(&token_id);
+        series.editions_minted += 1;
         self.series_by_id.insert(&id.0, &series);
 
         //specify the token struct that contains the owner ID
@@ -117,6 +132,9 @@ impl Contract {
         //call the internal method for adding the token to the owner
         self.internal_add_token_to_owner(&token.owner_id, &token_id);
 
+        // Record when the token was issued. It is returned as the token's `issued_at`
+        self.issued_at_by_token_id.insert(&token_id, &env::block_timestamp_ms());
+
         // Construct the mint log as per the events standard.
         let nft_mint_log: EventLog = EventLog {
             // Standard name ("nep171").
@@ -0,0 +1,486 @@
+/* unit tests */
+use crate::approval::NonFungibleTokenCore as NonFungibleTokenApproval;
+use crate::nft_core::NonFungibleTokenCore;
+use crate::royalty::NonFungibleTokenCore as NonFungibleTokenPayout;
+use crate::internal::hash_account_id;
+use crate::migrate::{
+    ContractV1, ContractV2, SeriesV1, SeriesV2, VersionedContract, MAX_MIGRATION_BATCH_SIZE,
+};
+use crate::{
+    Contract, NFTContractMetadataUpdate, NonFungibleTokenMetadata, StorageKey, TokenMetadata, DEFAULT_ROYALTY_CAP,
+};
+use near_sdk::borsh::BorshSerialize;
+use near_sdk::collections::{LazyOption, LookupMap, LookupSet, UnorderedMap, UnorderedSet};
+use near_sdk::json_types::{Base64VecU8, U128, U64};
+use near_sdk::mock::VmAction;
+use near_sdk::test_utils::{accounts, get_created_receipts, get_logs, VMContextBuilder};
+use near_sdk::testing_env;
+use near_sdk::{env, AccountId, PromiseOrValue};
+
+const STORAGE_COST: u128 = 100_000_000_000_000_000_000_000;
+
+fn get_context(predecessor: AccountId) -> VMContextBuilder {
+    let mut builder = VMContextBuilder::new();
+    builder.predecessor_account_id(predecessor);
+    builder
+}
+
+fn sample_series_metadata() -> TokenMetadata {
+    TokenMetadata {
+        title: Some("Olympus Mons".into()),
+        description: None,
+        media: None,
+        media_hash: None,
+        copies: Some(10),
+        issued_at: None,
+        expires_at: None,
+        starts_at: None,
+        updated_at: None,
+        extra: None,
+        reference: None,
+        reference_hash: None,
+    }
+}
+
+// sum of the NEAR transferred to the account by the receipts created so far
+fn transferred_to(account_id: &AccountId) -> u128 {
+    get_created_receipts()
+        .into_iter()
+        .filter(|receipt| &receipt.receiver_id == account_id)
+        .flat_map(|receipt| receipt.actions)
+        .map(|action| match action {
+            VmAction::Transfer { deposit } => deposit,
+            _ => 0,
+        })
+        .sum()
+}
+
+// alice owns the contract and creates series 1 with the passed in metadata
+fn setup_series(context: &mut VMContextBuilder, metadata: TokenMetadata, soulbound: bool) -> Contract {
+    testing_env!(context.build());
+    let mut contract = Contract::new_default_meta(accounts(0));
+
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(STORAGE_COST)
+        .predecessor_account_id(accounts(0))
+        .build());
+    contract.create_series(1, metadata, None, None, Some(soulbound));
+    contract
+}
+
+// alice mints the next edition of series 1 to the receiver
+fn mint(context: &mut VMContextBuilder, contract: &mut Contract, receiver_id: AccountId) {
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(STORAGE_COST)
+        .predecessor_account_id(accounts(0))
+        .build());
+    contract.nft_mint(U64(1), receiver_id);
+}
+
+#[test]
+fn test_burn_never_reuses_edition() {
+    let mut context = get_context(accounts(0));
+    let mut contract = setup_series(&mut context, sample_series_metadata(), false);
+    mint(&mut context, &mut contract, accounts(1));
+    mint(&mut context, &mut contract, accounts(1));
+    assert_eq!(contract.series_by_id.get(&1).unwrap().tokens.len(), 2);
+
+    // bob burns the second edition and the series forgets about it
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(1)
+        .predecessor_account_id(accounts(1))
+        .build());
+    contract.nft_burn("1:2".to_string(), None, None);
+    assert_eq!(
+        get_logs(),
+        vec![r#"EVENT_JSON:{"standard":"nep171","version":"1.0.0","event":"nft_burn","data":[{"owner_id":"bob","token_ids":["1:2"]}]}"#]
+    );
+    let series = contract.series_by_id.get(&1).unwrap();
+    assert_eq!(series.tokens.len(), 1);
+    assert!(!series.tokens.contains(&"1:2".to_string()));
+    assert_eq!(series.editions_minted, 2);
+    assert!(contract.nft_token("1:2".to_string()).is_none());
+    assert_eq!(contract.nft_supply_for_owner(accounts(1)).0, 1);
+
+    // the next mint continues after the burned edition instead of minting it again
+    mint(&mut context, &mut contract, accounts(1));
+    let series = contract.series_by_id.get(&1).unwrap();
+    assert_eq!(series.tokens.len(), 2);
+    assert!(series.tokens.contains(&"1:3".to_string()));
+    assert_eq!(series.editions_minted, 3);
+    assert!(contract.nft_token("1:2".to_string()).is_none());
+    assert_eq!(contract.nft_supply_for_series(1).0, 2);
+}
+
+#[test]
+fn test_burn_refunds_owner() {
+    let mut context = get_context(accounts(0));
+    let mut contract = setup_series(&mut context, sample_series_metadata(), false);
+    mint(&mut context, &mut contract, accounts(1));
+
+    // bob lets charlie burn the token, but bob gets the freed storage back
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(STORAGE_COST)
+        .predecessor_account_id(accounts(1))
+        .build());
+    contract.nft_approve("1:1".to_string(), accounts(2), None);
+
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(1)
+        .predecessor_account_id(accounts(2))
+        .build());
+    let initial_storage_usage = env::storage_usage();
+    contract.nft_burn("1:1".to_string(), Some(0), None);
+    let freed = u128::from(initial_storage_usage - env::storage_usage()) * env::storage_byte_cost();
+
+    assert!(freed > 0);
+    assert_eq!(transferred_to(&accounts(1)), freed);
+    assert_eq!(transferred_to(&accounts(2)), 0);
+    assert_eq!(contract.series_by_id.get(&1).unwrap().tokens.len(), 0);
+}
+
+#[test]
+fn test_revoke_soulbound() {
+    let mut context = get_context(accounts(0));
+    let mut contract = setup_series(&mut context, sample_series_metadata(), true);
+    mint(&mut context, &mut contract, accounts(1));
+    assert!(contract.nft_token("1:1".to_string()).unwrap().soulbound);
+
+    // alice issued the series, so she can revoke bob's token and gets the storage back
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(1)
+        .predecessor_account_id(accounts(0))
+        .build());
+    let initial_storage_usage = env::storage_usage();
+    contract.nft_revoke_soulbound("1:1".to_string(), None);
+    let freed = u128::from(initial_storage_usage - env::storage_usage()) * env::storage_byte_cost();
+
+    assert_eq!(transferred_to(&accounts(0)), freed);
+    assert!(contract.nft_token("1:1".to_string()).is_none());
+    assert_eq!(contract.series_by_id.get(&1).unwrap().tokens.len(), 0);
+}
+
+#[test]
+fn test_operator_transfer() {
+    let mut context = get_context(accounts(0));
+    let mut contract = setup_series(&mut context, sample_series_metadata(), false);
+    mint(&mut context, &mut contract, accounts(1));
+
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(STORAGE_COST)
+        .predecessor_account_id(accounts(1))
+        .build());
+    contract.nft_approve_operator(accounts(2));
+    assert!(contract.nft_is_operator(accounts(1), accounts(2)));
+
+    // charlie moves bob's token without a per-token approval
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(1)
+        .predecessor_account_id(accounts(2))
+        .build());
+    contract.nft_transfer(accounts(3), "1:1".to_string(), None, None);
+    assert_eq!(contract.nft_token("1:1".to_string()).unwrap().owner_id, accounts(3));
+    assert_eq!(
+        get_logs(),
+        vec![r#"EVENT_JSON:{"standard":"nep171","version":"1.0.0","event":"nft_transfer","data":[{"authorized_id":"charlie","old_owner_id":"bob","new_owner_id":"danny","token_ids":["1:1"]}]}"#]
+    );
+}
+
+#[test]
+fn test_operator_transfer_payout() {
+    let mut context = get_context(accounts(0));
+    let mut contract = setup_series(&mut context, sample_series_metadata(), false);
+    mint(&mut context, &mut contract, accounts(1));
+
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(STORAGE_COST)
+        .predecessor_account_id(accounts(1))
+        .build());
+    contract.nft_approve_operator(accounts(2));
+
+    // charlie, acting as a market operator, completes the sale with the approval ID it was given
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(1)
+        .predecessor_account_id(accounts(2))
+        .build());
+    let payout = contract.nft_transfer_payout(accounts(3), "1:1".to_string(), 1, None, U128(1_000), 10);
+    assert_eq!(contract.nft_token("1:1".to_string()).unwrap().owner_id, accounts(3));
+    assert_eq!(payout.payout.get(&accounts(1)), Some(&U128(1_000)));
+}
+
+#[test]
+fn test_nft_batch_transfer() {
+    let mut context = get_context(accounts(0));
+    let mut contract = setup_series(&mut context, sample_series_metadata(), false);
+    mint(&mut context, &mut contract, accounts(1));
+    mint(&mut context, &mut contract, accounts(1));
+
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(STORAGE_COST)
+        .predecessor_account_id(accounts(1))
+        .build());
+    let initial_storage_usage = env::storage_usage();
+    contract.nft_approve("1:2".to_string(), accounts(2), None);
+    let approvals_cost =
+        u128::from(env::storage_usage() - initial_storage_usage) * env::storage_byte_cost();
+
+    // both tokens move in one call with one transfer log, and bob gets the approval storage back
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(1)
+        .predecessor_account_id(accounts(1))
+        .build());
+    contract.nft_batch_transfer(accounts(3), vec!["1:1".to_string(), "1:2".to_string()], None);
+    assert_eq!(contract.nft_supply_for_owner(accounts(3)).0, 2);
+    assert!(contract.nft_token("1:2".to_string()).unwrap().approved_account_ids.is_empty());
+    assert_eq!(transferred_to(&accounts(1)), approvals_cost);
+    assert_eq!(
+        get_logs(),
+        vec![r#"EVENT_JSON:{"standard":"nep171","version":"1.0.0","event":"nft_transfer","data":[{"old_owner_id":"bob","new_owner_id":"danny","token_ids":["1:1","1:2"]}]}"#]
+    );
+}
+
+#[test]
+fn test_issued_at() {
+    let mut context = get_context(accounts(0));
+    let mut contract = setup_series(&mut context, sample_series_metadata(), false);
+    context.block_timestamp(5_000_000_000);
+    mint(&mut context, &mut contract, accounts(1));
+
+    // each edition reports when it was minted, in milliseconds
+    let token = contract.nft_token("1:1".to_string()).unwrap();
+    assert_eq!(token.metadata.issued_at, Some(U64(5_000)));
+    // NEP-177 timestamps are strings on the wire
+    assert_eq!(near_sdk::serde_json::to_value(&token.metadata).unwrap()["issued_at"], "5000");
+    assert_eq!(contract.series_by_id.get(&1).unwrap().metadata.issued_at, None);
+}
+
+#[test]
+fn test_nft_is_valid() {
+    let mut context = get_context(accounts(0));
+    let metadata = TokenMetadata {
+        starts_at: Some(U64(2_000)),
+        expires_at: Some(U64(4_000)),
+        ..sample_series_metadata()
+    };
+    let mut contract = setup_series(&mut context, metadata, false);
+    mint(&mut context, &mut contract, accounts(1));
+
+    // not started yet
+    testing_env!(context.block_timestamp(1_000_000_000).build());
+    assert!(!contract.nft_is_valid("1:1".to_string()));
+
+    // valid between starts_at and expires_at
+    testing_env!(context.block_timestamp(3_000_000_000).build());
+    assert!(contract.nft_is_valid("1:1".to_string()));
+    assert!(!contract.nft_token("1:1".to_string()).unwrap().expired);
+
+    // expired
+    testing_env!(context.block_timestamp(4_000_000_000).build());
+    assert!(!contract.nft_is_valid("1:1".to_string()));
+    assert!(contract.nft_token("1:1".to_string()).unwrap().expired);
+    assert!(!contract.nft_is_valid("1:2".to_string()));
+}
+
+#[test]
+fn test_nft_update_contract_metadata_clears_fields() {
+    let mut context = get_context(accounts(0));
+    testing_env!(context.build());
+    let mut contract = Contract::new_default_meta(accounts(0));
+
+    testing_env!(context
+        .storage_usage(env::storage_usage())
+        .attached_deposit(STORAGE_COST)
+        .predecessor_account_id(accounts(0))
+        .build());
+    contract.nft_update_contract_metadata(
+        NFTContractMetadataUpdate {
+            icon: Some(Some("data:image/svg+xml,icon".to_string())),
+            reference: Some(Some("https://example.com/reference.json".to_string())),
+            reference_hash: Some(Some(Base64VecU8(vec![0; 32]))),
+            ..Default::default()
+        },
+        None,
+    );
+
+    // null clears a field, a field that isn't passed in is left unchanged
+    let update: NFTContractMetadataUpdate =
+        near_sdk::serde_json::from_str(r#"{"reference": null, "reference_hash": null}"#).unwrap();
+    contract.nft_update_contract_metadata(update, None);
+
+    let metadata = contract.nft_metadata();
+    assert_eq!(metadata.icon, Some("data:image/svg+xml,icon".to_string()));
+    assert_eq!(metadata.reference, None);
+    assert_eq!(metadata.reference_hash, None);
+}
+
+// a state with the layout of the first deployments, with two series
+fn write_v1_state(owner_id: AccountId) {
+    let mut approved_minters = LookupSet::new(StorageKey::ApprovedMinters.try_to_vec().unwrap());
+    approved_minters.insert(&owner_id);
+    let mut approved_creators =
+        LookupSet::new(StorageKey::ApprovedCreators.try_to_vec().unwrap());
+    approved_creators.insert(&owner_id);
+
+    let mut state = ContractV1 {
+        owner_id: owner_id.clone(),
+        approved_minters,
+        approved_creators,
+        series_by_id: UnorderedMap::new(StorageKey::SeriesById.try_to_vec().unwrap()),
+        tokens_by_id: UnorderedMap::new(StorageKey::TokensById.try_to_vec().unwrap()),
+        tokens_per_owner: LookupMap::new(StorageKey::TokensPerOwner.try_to_vec().unwrap()),
+        metadata: LazyOption::new(StorageKey::NFTContractMetadata.try_to_vec().unwrap(), None),
+    };
+
+    // A series with two minted editions
+    let mut tokens = UnorderedSet::new(StorageKey::SeriesByIdInner {
+        account_id_hash: hash_account_id(&format!("{}{}", 1, owner_id)),
+    });
+    tokens.insert(&"1:1".to_string());
+    tokens.insert(&"1:2".to_string());
+    state.series_by_id.insert(
+        &1,
+        &SeriesV1 {
+            metadata: sample_series_metadata(),
+            royalty: None,
+            tokens,
+            price: None,
+            owner_id: owner_id.clone(),
+        },
+    );
+
+    // A series without any edition
+    state.series_by_id.insert(
+        &2,
+        &SeriesV1 {
+            metadata: sample_series_metadata(),
+            royalty: None,
+            tokens: UnorderedSet::new(StorageKey::SeriesByIdInner {
+                account_id_hash: hash_account_id(&format!("{}{}", 2, owner_id)),
+            }),
+            price: None,
+            owner_id,
+        },
+    );
+    env::state_write(&state);
+}
+
+#[test]
+fn test_migrate_from_v1() {
+    let mut context = VMContextBuilder::new();
+    testing_env!(context.predecessor_account_id(accounts(0)).build());
+    write_v1_state(accounts(0));
+
+    // the first call only converts one series and the state keeps its old layout
+    assert!(matches!(Contract::migrate(Some(1)), PromiseOrValue::Value(false)));
+    assert!(matches!(VersionedContract::read(), VersionedContract::V1(_)));
+
+    // the second call picks up where the first one stopped and finishes the migration
+    assert!(matches!(Contract::migrate(None), PromiseOrValue::Value(true)));
+    let contract: Contract = env::state_read().unwrap();
+    assert_eq!(contract.owner_id, accounts(0));
+    assert!(contract.is_approved_minter(accounts(0)));
+    assert!(contract.is_approved_creator(accounts(0)));
+    assert_eq!(contract.nft_royalty_cap(), DEFAULT_ROYALTY_CAP);
+    let series = contract.series_by_id.get(&1).unwrap();
+    assert_eq!(series.tokens.len(), 2);
+    // the next minted edition continues after the existing ones
+    assert_eq!(series.editions_minted, 2);
+    assert!(!series.soulbound);
+    assert_eq!(series.metadata.title, Some("Olympus Mons".into()));
+    assert_eq!(contract.series_by_id.get(&2).unwrap().editions_minted, 0);
+
+    // migrating a state that already has the current layout changes nothing
+    assert!(matches!(Contract::migrate(None), PromiseOrValue::Value(true)));
+    let contract: Contract = env::state_read().unwrap();
+    assert_eq!(contract.series_by_id.get(&1).unwrap().editions_minted, 2);
+}
+
+#[test]
+fn test_migrate_chains_batches() {
+    let mut context = VMContextBuilder::new();
+    testing_env!(context.predecessor_account_id(accounts(0)).build());
+    write_v1_state(accounts(0));
+    // one series more than a single call converts
+    let mut state = env::state_read::<ContractV1>().unwrap();
+    for series_id in 3..=MAX_MIGRATION_BATCH_SIZE + 1 {
+        state.series_by_id.insert(
+            &series_id,
+            &SeriesV1 {
+                metadata: sample_series_metadata(),
+                royalty: None,
+                tokens: UnorderedSet::new(StorageKey::SeriesByIdInner {
+                    account_id_hash: hash_account_id(&format!("{}{}", series_id, accounts(0))),
+                }),
+                price: None,
+                owner_id: accounts(0),
+            },
+        );
+    }
+    env::state_write(&state);
+
+    // without a limit a full batch is converted and migrate is called again for the rest
+    assert!(matches!(Contract::migrate(None), PromiseOrValue::Promise(_)));
+    assert!(matches!(VersionedContract::read(), VersionedContract::V1(_)));
+    let receipts = get_created_receipts();
+    assert_eq!(receipts.len(), 1);
+    assert_eq!(receipts[0].receiver_id, env::current_account_id());
+    assert!(matches!(
+        &receipts[0].actions[0],
+        VmAction::FunctionCall { function_name, .. } if function_name == "migrate"
+    ));
+
+    // the chained call finishes the migration
+    assert!(matches!(Contract::migrate(None), PromiseOrValue::Value(true)));
+    let contract: Contract = env::state_read().unwrap();
+    assert_eq!(contract.series_by_id.len(), MAX_MIGRATION_BATCH_SIZE + 1);
+}
+
+#[test]
+fn test_migrate_from_v2() {
+    let mut context = VMContextBuilder::new();
+    testing_env!(context.predecessor_account_id(accounts(0)).build());
+    write_v1_state(accounts(0));
+
+    // a V2 state keeps its pending owner and the editions it burned
+    let state = env::state_read::<ContractV1>().unwrap();
+    let mut first_series = SeriesV2::from(state.series_by_id.get(&1).unwrap());
+    let second_series = SeriesV2::from(state.series_by_id.get(&2).unwrap());
+    let mut state = ContractV2::from(state);
+    first_series.editions_minted = 3;
+    // the stored series are still V1 so they are overwritten without reading them back
+    for (series_id, series) in [(1u64, first_series), (2, second_series)] {
+        state.series_by_id.insert_raw(&series_id.try_to_vec().unwrap(), &series.try_to_vec().unwrap());
+    }
+    state.pending_owner_id = Some(accounts(1));
+    env::state_write(&state);
+    env::storage_write(b"STATE_VERSION", &[2]);
+
+    assert!(matches!(Contract::migrate(None), PromiseOrValue::Value(true)));
+    let contract: Contract = env::state_read().unwrap();
+    assert_eq!(contract.get_pending_owner(), Some(accounts(1)));
+    assert_eq!(contract.series_by_id.get(&1).unwrap().editions_minted, 3);
+    assert!(!contract.series_by_id.get(&2).unwrap().soulbound);
+    assert!(matches!(VersionedContract::read(), VersionedContract::V3(_)));
+}
+
+#[test]
+#[should_panic(expected = "only contract owner")]
+fn test_migrate_not_owner() {
+    let mut context = VMContextBuilder::new();
+    testing_env!(context.predecessor_account_id(accounts(1)).build());
+    write_v1_state(accounts(0));
+
+    Contract::migrate(None);
+}